@@ -0,0 +1,47 @@
+//! Compile the filter lists into the ad-block engine bundled with a release
+//! (`resources/adblock/default.dat`, embedded by `platform::resources`), the one
+//! a device blocks with until it has fetched the lists itself:
+//!
+//! ```sh
+//! cargo run --example adblock_dat [OUT] [LIST_URL...]
+//! ```
+//!
+//! With no lists given it compiles the `[adblock] lists` defaults. Run it on
+//! the adblock version the app links, or the app discards the DAT.
+
+use adblock::lists::{FilterSet, ParseOptions};
+use adblock::Engine;
+use std::io::Read;
+
+const DEFAULT_OUT: &str = "resources/adblock/default.dat";
+const DEFAULT_LISTS: [&str; 2] = [
+    "https://easylist.to/easylist/easylist.txt",
+    "https://easylist.to/easylist/easyprivacy.txt",
+];
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let out = args.next().unwrap_or_else(|| DEFAULT_OUT.to_string());
+    let mut lists: Vec<String> = args.collect();
+    if lists.is_empty() {
+        lists = DEFAULT_LISTS.iter().map(|url| url.to_string()).collect();
+    }
+
+    // Debug mode keeps each rule's text, as the app's own builds do.
+    let mut filter_set = FilterSet::new(true);
+    for url in &lists {
+        let mut text = String::new();
+        ureq::get(url)
+            .call()
+            .unwrap_or_else(|e| panic!("fetching {url}: {e}"))
+            .into_body()
+            .into_reader()
+            .read_to_string(&mut text)
+            .unwrap_or_else(|e| panic!("reading {url}: {e}"));
+        filter_set.add_filter_list(&text, ParseOptions::default());
+        println!("fetched {url}");
+    }
+    let dat = Engine::new_with_filter_set(filter_set).serialize();
+    std::fs::write(&out, &dat).unwrap_or_else(|e| panic!("writing {out}: {e}"));
+    println!("wrote {out} ({} bytes)", dat.len());
+}
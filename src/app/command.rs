@@ -5,6 +5,7 @@
 //! [`super::router`]).
 
 use crate::browser::BrowserCommand;
+use crate::overlay::blocked::BlockedSection;
 use crate::overlay::menu::Section;
 use crate::overlay::osk::OskCommand;
 use crate::overlay::settings::SettingsSection;
@@ -26,6 +27,33 @@ pub enum AppCommand {
     Prompt(PromptAction),
     /// An action on the settings overlay (see [`crate::overlay::settings`]).
     Settings(SettingsAction),
+    /// An action on the site settings sheet (see [`crate::overlay::site_edit`]).
+    Site(SiteAction),
+    /// An action on the blocked-requests panel (see
+    /// [`crate::overlay::blocked`]).
+    Blocked(BlockedAction),
+    /// An action on the find-in-page bar (see [`crate::overlay::find`]).
+    Find(FindAction),
+    /// Open row `index` of the address-bar suggestions and put the keyboard
+    /// away (see [`crate::overlay::suggest`]): A / R2 on a highlighted row, or
+    /// clicking one.
+    OpenSuggestion(usize),
+    /// The answer to the "reopen tabs after a crash?" question: reopen the
+    /// previous run's tabs (`true`) or keep the fresh start.
+    RestoreSession(bool),
+    /// The answer to a certificate error (see [`crate::browser::CertError`]):
+    /// proceed to `host` for the session, or go back.
+    CertError {
+        webview: servo::WebViewId,
+        host: String,
+        proceed: bool,
+    },
+    /// Load `url` over HTTP after its HTTPS-only upgrade failed, letting its
+    /// host through for the session (see [`crate::browser::UpgradeFailed`]).
+    ContinueOverHttp {
+        webview: servo::WebViewId,
+        url: url::Url,
+    },
 }
 
 /// Actions on the settings overlay. The mouse pushes `Select` then `Activate` /
@@ -65,6 +93,63 @@ pub enum SettingsAction {
     InstallUpdate,
     /// About tab: quit so the launcher re-execs the freshly swapped binary.
     QuitForUpdate,
+    /// Content tab: merge `bookmarks.html` from the data dir into the bookmarks.
+    ImportBookmarks,
+    /// Content tab: write every bookmark to `bookmarks.html` in the data dir.
+    ExportBookmarks,
+}
+
+/// Actions on the site settings sheet. The mouse pushes `Select` then
+/// `Activate`; the gamepad and keyboard come through the router against the
+/// focused row, like settings.
+#[derive(Clone)]
+pub enum SiteAction {
+    /// Open the sheet on the active tab's site (the toolbar's sliders button).
+    /// A no-op on a page without a host.
+    Open,
+    /// Store the edits, re-apply them to the tab, and close (B / ✖).
+    Close,
+    /// Focus row `index` (clicking it).
+    Select(usize),
+    /// Step the focused row forward, or reset the site on its Reset row (A).
+    Activate,
+    /// Step the focused row by a direction (◀ = -1, ▶ = +1).
+    Adjust(i32),
+}
+
+/// Actions on the blocked-requests panel. Up/Down and L1/R1 come through the
+/// router.
+#[derive(Clone)]
+pub enum BlockedAction {
+    /// Open the panel on the active tab's page (the toolbar's shield chip).
+    Open,
+    /// Open it on the tab's Requests section (the `requests` binding).
+    OpenRequests,
+    /// Close it (B / ✖).
+    Close,
+    /// Show a section (clicking its tab).
+    SetSection(BlockedSection),
+    /// Focus row `index` (clicking it).
+    Select(usize),
+    /// Save the tab's requests as a HAR file in the download dir (Y / the
+    /// bar's button).
+    SaveHar,
+}
+
+/// Actions on the find-in-page bar. The bindings push `Open` / `Step`; the
+/// on-screen keyboard's Enter pushes `Search` with the typed query.
+#[derive(Clone)]
+pub enum FindAction {
+    /// Show the bar and open the on-screen keyboard on its query (Ctrl+F).
+    Open,
+    /// Search the page for a query, marking every match (the keyboard's Enter).
+    /// An empty query clears the marks.
+    Search(String),
+    /// Step to the next (`1`) or previous (`-1`) match, reopening the bar when
+    /// it was closed (L1/R1 while it's open, F3 / Shift+F3).
+    Step(i32),
+    /// Hide the bar and clear the marks (B / Esc).
+    Close,
 }
 
 /// Actions on the modal page-prompt overlay. The gamepad and keyboard push
@@ -119,12 +204,38 @@ pub enum MenuAction {
     DialToggleSettings,
     /// Remove the entry at `index` in the active section (clicking its ✖).
     RemoveAt(usize),
+    /// Pause or resume the download at `index` (clicking its ⏸ / ▶).
+    PauseAt(usize),
+    /// Show or hide the finished download at `index`'s details (clicking its ⓘ).
+    DetailsAt(usize),
+    /// Move the queued download at `index` to the front of the queue (clicking
+    /// its row).
+    QueueFirst(usize),
     /// Switch to the tab at `index` and close the menu (clicking a tab row).
     OpenTab(usize),
     /// Close the tab at `index` (clicking a tab's ✖).
     CloseTab(usize),
     /// Open a new tab and close the menu (clicking "+ New tab").
     NewTab,
+    /// Open a new no-history tab and close the menu (clicking "+ New no-history
+    /// tab").
+    NewNoHistoryTab,
+    /// Add a bookmark folder and open the edit sheet on its name (clicking
+    /// "+ New folder").
+    NewFolder,
+    /// Open the bookmark folder at `index` (clicking its row).
+    EnterFolder(usize),
+    /// Go up one bookmark folder (the breadcrumb's back button).
+    FolderUp,
+    /// Move the highlighted bookmark, folder or download up / down a place
+    /// (L2 / R2, Ctrl+Up / Ctrl+Down).
+    MoveEntry(i32),
+    /// Open the edit sheet on the bookmark or folder at `index` (clicking its ✎).
+    EditBookmark(usize),
+    /// Focus and activate row `index` of the edit sheet (clicking it).
+    EditField(usize),
+    /// Close the edit sheet, saving it when `true` (its Save / Cancel buttons).
+    EditClose(bool),
 }
 
 /// A *contextual* input intent from a control device — one whose effect depends
@@ -145,7 +256,8 @@ pub enum InputCommand {
     /// while it's open, otherwise navigate the page back / forward.
     Shoulder(i32),
     /// Trigger (L2 = left, R2 = right) with its press state. Drives the on-screen
-    /// keyboard (L2 Shift, R2 Enter) when it's open, otherwise cycles tabs.
+    /// keyboard (L2 Shift, R2 Enter) when it's open, moves the highlighted entry
+    /// in the menu's Bookmarks section, otherwise cycles tabs.
     Trigger { right: bool, pressed: bool },
     /// A dedicated keyboard key (Y). Applied only while the keyboard is open.
     Osk(OskCommand),
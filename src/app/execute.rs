@@ -4,12 +4,20 @@
 //! to (menu / settings / speed-dial / bookmarks) live here too. Input intents are
 //! mapped earlier, in [`super::router`].
 
-use super::{App, AppCommand, InputCommand, MenuAction, PromptAction, SettingsAction};
-use crate::browser::BrowserCommand;
+use super::{
+    App, AppCommand, BlockedAction, FindAction, InputCommand, MenuAction, PromptAction,
+    SettingsAction, SiteAction,
+};
+use crate::browser::{BlobDownload, BrowserCommand};
 use crate::config::AppConfig;
+use crate::data::bookmarks;
+use crate::data::dial::SETTINGS_PIN;
+use crate::overlay::blocked::BlockedSection;
+use crate::overlay::bookmark_edit::Field;
 use crate::overlay::dial_edit::EditItem;
-use crate::overlay::menu::Section;
+use crate::overlay::menu::{Section, NEW_TAB_ROWS};
 use crate::overlay::osk::OskCommand;
+use crate::ui::Focus;
 
 impl App {
     pub(super) fn execute_command(&mut self, command: &AppCommand, out: &mut Vec<AppCommand>) {
@@ -18,21 +26,40 @@ impl App {
             // On a window resize, size the browser to the new central area straight
             // away from the actual window (egui's reactive sizing can lag a frame).
             AppCommand::Resize => self.ui.resize_browser(&self.window, &self.browser),
-            AppCommand::Browser(command) => {
-                self.browser.execute_command(command, &self.config.browser)
-            }
+            AppCommand::Browser(command) => self.browser.execute_command(command, &self.config),
             AppCommand::Input(command) => self.route_input(command, out),
-            AppCommand::Menu(action) => self.menu_action(action),
+            AppCommand::Menu(action) => self.menu_action(action, out),
             AppCommand::ToggleBookmark => self.toggle_current_bookmark(),
-            AppCommand::Prompt(action) => match action {
-                PromptAction::Activate => self.ui.prompt.activate(),
-                PromptAction::Cancel => self.ui.prompt.cancel(),
-                PromptAction::ClickSlot(index) => {
-                    self.ui.prompt.set_selected(*index);
-                    self.ui.prompt.activate();
-                }
-            },
+            AppCommand::Prompt(action) => {
+                // An app question answers with a command of its own.
+                let answer = match action {
+                    PromptAction::Activate => self.ui.prompt.activate(),
+                    PromptAction::Cancel => self.ui.prompt.cancel(),
+                    PromptAction::ClickSlot(index) => {
+                        self.ui.prompt.set_selected(*index);
+                        self.ui.prompt.activate()
+                    }
+                };
+                out.extend(answer);
+            }
+            AppCommand::RestoreSession(restore) => self.answer_restore(*restore),
+            AppCommand::CertError {
+                webview,
+                host,
+                proceed,
+            } => {
+                let home = &self.config.browser.home_page;
+                self.browser
+                    .answer_cert_error(*webview, host, *proceed, home)
+            }
+            AppCommand::ContinueOverHttp { webview, url } => {
+                self.browser.continue_over_http(*webview, url)
+            }
             AppCommand::Settings(action) => self.settings_action(action, out),
+            AppCommand::Site(action) => self.site_action(action),
+            AppCommand::Blocked(action) => self.blocked_action(action),
+            AppCommand::Find(action) => self.find_action(action, out),
+            AppCommand::OpenSuggestion(index) => self.ui.open_suggestion(*index, out),
         };
 
         // Commands are drained after `ui.update` already built this frame, so a
@@ -51,7 +78,7 @@ impl App {
     }
 
     /// Apply a menu action (Tabs / Bookmarks / History / Downloads overlay).
-    fn menu_action(&mut self, action: &MenuAction) {
+    fn menu_action(&mut self, action: &MenuAction, out: &mut Vec<AppCommand>) {
         match action {
             // Select toggles the menu; the menu button only ever opens it (it's hidden
             // behind the menu once shown).
@@ -64,7 +91,7 @@ impl App {
             }
             MenuAction::Close => self.ui.menu.close(),
             MenuAction::SetSection(section) => self.ui.menu.set_section(*section),
-            MenuAction::OpenSelected => self.menu_open_selected(),
+            MenuAction::OpenSelected => self.menu_open_selected(out),
             MenuAction::RemoveSelected => self.delete_menu_selection(),
             MenuAction::Clear => self.ui.menu.clear_or_arm(),
             MenuAction::OpenUrl(url) => self.open_url(url.clone()),
@@ -73,10 +100,16 @@ impl App {
             MenuAction::DialClose => self.ui.close_pins_editor(),
             MenuAction::DialAdd(url) => self.dial_add(url),
             MenuAction::DialRemoveAt(index) => self.ui.menu.dial.remove(*index),
-            MenuAction::DialToggleSettings => {
-                self.ui.menu.dial.toggle(crate::data::dial::SETTINGS_PIN)
-            }
+            MenuAction::DialToggleSettings => self.ui.menu.dial.toggle(SETTINGS_PIN, ""),
             MenuAction::RemoveAt(index) => self.ui.menu.remove_at(*index),
+            MenuAction::PauseAt(index) => self
+                .ui
+                .menu
+                .downloads
+                .toggle_pause(*index, &self.event_sender),
+            MenuAction::DetailsAt(index) => {
+                self.ui.menu.downloads.toggle_details(*index);
+            }
             MenuAction::OpenTab(index) => {
                 self.browser.switch_to(*index);
                 self.ui.menu.close();
@@ -86,6 +119,51 @@ impl App {
                 self.ui.menu.set_tab_count(self.browser.tab_count());
             }
             MenuAction::NewTab => self.new_tab(),
+            MenuAction::NewNoHistoryTab => self.new_no_history_tab(),
+            MenuAction::NewFolder => self.new_bookmark_folder(out),
+            MenuAction::EnterFolder(index) => self.ui.menu.enter_folder(*index),
+            MenuAction::FolderUp => {
+                self.ui.menu.folder_up();
+            }
+            MenuAction::MoveEntry(delta) => self.ui.menu.move_entry(*delta),
+            MenuAction::QueueFirst(index) => self.ui.menu.downloads.move_to_top(*index),
+            MenuAction::EditBookmark(index) => self.ui.menu.edit_at(*index),
+            MenuAction::EditField(index) => {
+                if let Some(edit) = self.ui.menu.bookmark_edit_mut() {
+                    edit.set_selected(*index);
+                }
+                self.bookmark_edit_confirm(out);
+            }
+            MenuAction::EditClose(save) => self.ui.menu.edit_close(*save),
+        }
+    }
+
+    /// Add a bookmark folder to the open one and start typing its name.
+    fn new_bookmark_folder(&mut self, out: &mut Vec<AppCommand>) {
+        self.ui.menu.new_folder();
+        self.ui.osk(OskCommand::Show, &self.browser, out);
+    }
+
+    /// A on the bookmark edit sheet: type into a text row, cycle the folder or
+    /// flip the pin, or save on the Save row.
+    fn bookmark_edit_confirm(&mut self, out: &mut Vec<AppCommand>) {
+        let Some(edit) = self.ui.menu.bookmark_edit_mut() else {
+            return;
+        };
+        match edit.field() {
+            Field::Title | Field::Tags => self.ui.osk(OskCommand::Show, &self.browser, out),
+            Field::Folder | Field::Pin => edit.adjust(1),
+            Field::Save => self.ui.menu.edit_close(true),
+        }
+    }
+
+    /// B in the menu: close the bookmark edit sheet without saving, else go up
+    /// a bookmark folder, else close the menu.
+    pub(super) fn menu_cancel(&mut self) {
+        if self.ui.menu.bookmark_edit().is_some() {
+            self.ui.menu.edit_close(false);
+        } else if self.ui.menu.section() != Section::Bookmarks || !self.ui.menu.folder_up() {
+            self.ui.menu.close();
         }
     }
 
@@ -96,6 +174,13 @@ impl App {
         self.ui.menu.close();
     }
 
+    /// Open a new no-history tab at the home page and close the menu.
+    fn new_no_history_tab(&mut self) {
+        let home = self.config.browser.home_page.clone();
+        self.browser.open_no_history_tab(&home);
+        self.ui.menu.close();
+    }
+
     /// Toggle the current page in saved bookmarks (the bookmark button / Start).
     fn toggle_current_bookmark(&mut self) {
         let url = self.browser.get_state_mut().page_url().to_string();
@@ -106,24 +191,35 @@ impl App {
 
     /// Open the highlighted menu entry (the **A** button / Enter). In Tabs this
     /// switches to the tab (or opens a new one on the "+ New tab" row); in the URL
-    /// lists it loads the entry. Closes the menu either way.
-    pub(super) fn menu_open_selected(&mut self) {
-        if self.ui.menu.section() == Section::Tabs {
-            let sel = self.ui.menu.tab_selected();
-            if sel == 0 {
-                self.new_tab(); // the "+ New tab" button (index 0)
-            } else {
-                self.browser.switch_to(sel - 1);
-                self.ui.menu.close();
+    /// lists it loads the entry, closing the menu either way. A bookmark folder
+    /// opens in place, and the edit sheet takes A while it's up.
+    pub(super) fn menu_open_selected(&mut self, out: &mut Vec<AppCommand>) {
+        if self.ui.menu.bookmark_edit().is_some() {
+            self.bookmark_edit_confirm(out);
+        } else if self.ui.menu.section() == Section::Tabs {
+            match self.ui.menu.tab_selected() {
+                0 => self.new_tab(), // the "+ New tab" button
+                1 => self.new_no_history_tab(),
+                sel => {
+                    self.browser.switch_to(sel - NEW_TAB_ROWS);
+                    self.ui.menu.close();
+                }
             }
         } else if self.ui.menu.clear_selected() {
             // The section's clear row (index 0): arms, then wipes; stays open.
             self.ui.menu.clear_or_arm();
+        } else if self.ui.menu.new_folder_selected() {
+            self.new_bookmark_folder(out);
+        } else if let Some(index) = self.ui.menu.selected_folder() {
+            self.ui.menu.enter_folder(index);
         } else if let Some(url) = self.ui.menu.selected_url() {
             self.open_url(url);
-        } else if self.ui.menu.section() != Section::Downloads {
-            // A on an active/failed download has nothing to open — keep the menu
-            // up so the user can watch the progress; other sections close.
+        } else if self.ui.menu.section() == Section::Downloads {
+            // A on a queued download moves it to the front of the queue; an
+            // active/failed one has nothing to open. Either way the menu stays up
+            // so the user can watch the progress.
+            self.ui.menu.downloads.move_selected_to_top();
+        } else {
             self.ui.menu.close();
         }
     }
@@ -131,11 +227,13 @@ impl App {
     /// Delete the highlighted menu entry (the **X** button / Delete). In Tabs this
     /// closes the tab; in the URL lists it removes the bookmark / history entry.
     pub(super) fn delete_menu_selection(&mut self) {
-        if self.ui.menu.section() == Section::Tabs {
-            // Index 0 is the "+ New tab" button (nothing to delete); tabs are 1.. .
+        if self.ui.menu.bookmark_edit().is_some() {
+            // The edit sheet is up: nothing under it is deleted.
+        } else if self.ui.menu.section() == Section::Tabs {
+            // The new-tab buttons lead (nothing to delete); tabs follow.
             let sel = self.ui.menu.tab_selected();
-            if sel > 0 {
-                self.browser.close_tab(sel - 1);
+            if sel >= NEW_TAB_ROWS {
+                self.browser.close_tab(sel - NEW_TAB_ROWS);
                 self.ui.menu.set_tab_count(self.browser.tab_count());
             }
         } else {
@@ -144,14 +242,18 @@ impl App {
     }
 
     /// Y in the menu (link-hint toggle elsewhere): the action depends on the
-    /// section. Bookmarks pins/unpins the selected entry on the speed dial;
-    /// History bookmarks (or un-bookmarks) the selected entry; Tabs bookmarks
-    /// the selected tab's URL. Downloads has no Y action.
+    /// section. Bookmarks opens the edit sheet on the selected entry (where it's
+    /// pinned to the speed dial too), or saves the sheet when it's up; History
+    /// bookmarks (or un-bookmarks) the selected entry; Tabs bookmarks the
+    /// selected tab's URL; Downloads pauses or resumes the selected download, or
+    /// shows a finished one's details.
     pub(super) fn menu_y_action(&mut self) {
         match self.ui.menu.section() {
             Section::Bookmarks => {
-                if let Some(url) = self.ui.menu.selected_url() {
-                    self.ui.menu.dial.toggle(&url);
+                if self.ui.menu.bookmark_edit().is_some() {
+                    self.ui.menu.edit_close(true);
+                } else {
+                    self.ui.menu.edit_selected();
                 }
             }
             Section::History => {
@@ -160,20 +262,136 @@ impl App {
                 }
             }
             Section::Tabs => {
-                // Index 0 is the "+ New tab" button; the tabs follow at 1..=N.
+                // The new-tab buttons lead; the tabs follow.
                 let sel = self.ui.menu.tab_selected();
-                if sel > 0 {
-                    if let Some(info) = self.browser.tabs().get(sel - 1) {
+                if sel >= NEW_TAB_ROWS {
+                    if let Some(info) = self.browser.tabs().get(sel - NEW_TAB_ROWS) {
                         if !info.url.is_empty() {
                             self.ui.menu.toggle_bookmark(&info.url);
                         }
                     }
                 }
             }
-            Section::Downloads => {}
+            Section::Downloads => {
+                // Finished entries have nothing to pause: Y shows their details.
+                let downloads = &mut self.ui.menu.downloads;
+                if !downloads.toggle_details_selected() {
+                    downloads.toggle_pause_selected(&self.event_sender);
+                }
+            }
+        }
+    }
+
+    /// Apply a find-in-page action (see [`crate::overlay::find`]).
+    fn find_action(&mut self, action: &FindAction, out: &mut Vec<AppCommand>) {
+        match action {
+            FindAction::Open => {
+                if matches!(self.ui.focus(), Focus::Page | Focus::Find) {
+                    self.ui.find_open();
+                    self.ui.osk(OskCommand::Show, &self.browser, out);
+                }
+            }
+            FindAction::Search(query) => {
+                self.ui.find.begin_search(query);
+                if query.is_empty() {
+                    self.browser.find_clear();
+                } else {
+                    self.browser.find(query, 0);
+                }
+            }
+            FindAction::Step(delta) => self.find_step(*delta, out),
+            FindAction::Close => self.find_close(),
+        }
+    }
+
+    /// Step the find bar `delta` matches. With the bar closed this reopens it on
+    /// the last query (the page searches it afresh, its marks being gone), or
+    /// opens it to type one when there's none yet.
+    pub(super) fn find_step(&mut self, delta: i32, out: &mut Vec<AppCommand>) {
+        if self.ui.find.visible && !self.ui.find.searched().is_empty() {
+            let query = self.ui.find.searched().to_string();
+            self.browser.find(&query, delta);
+            return;
+        }
+        if !matches!(self.ui.focus(), Focus::Page | Focus::Find) {
+            return;
+        }
+        let query = self.ui.find.query().trim().to_string();
+        if query.is_empty() {
+            self.find_action(&FindAction::Open, out);
+        } else {
+            self.ui.find_open();
+            self.ui.find.begin_search(&query);
+            self.browser.find(&query, delta);
+        }
+    }
+
+    /// Apply a site-settings-sheet action (see [`crate::overlay::site_edit`]).
+    fn site_action(&mut self, action: &SiteAction) {
+        match action {
+            SiteAction::Open => {
+                if let Some((host, site, blocking)) = self.browser.site_settings() {
+                    self.ui.site_edit_open(host, site, blocking);
+                }
+            }
+            SiteAction::Close => self.site_close(),
+            SiteAction::Select(index) => self.ui.site_edit.set_selected(*index),
+            SiteAction::Activate => self.ui.site_edit.activate(),
+            SiteAction::Adjust(dx) => self.ui.site_edit.adjust(*dx),
         }
     }
 
+    /// Close the site settings sheet (B / close button): store its edits and
+    /// let the browser re-apply them to the tab.
+    pub(super) fn site_close(&mut self) {
+        if let Some((host, site, blocking)) = self.ui.site_edit.close() {
+            self.browser.set_site_settings(&host, site, blocking);
+        }
+    }
+
+    /// Apply a blocked-requests-panel action (see [`crate::overlay::blocked`]).
+    fn blocked_action(&mut self, action: &BlockedAction) {
+        match action {
+            BlockedAction::Open | BlockedAction::OpenRequests => {
+                let section = match action {
+                    BlockedAction::OpenRequests => BlockedSection::Requests,
+                    _ => BlockedSection::Blocked,
+                };
+                self.ui
+                    .blocked_open(self.browser.block_log(), self.browser.net_log(), section);
+            }
+            BlockedAction::Close => self.ui.blocked.close(),
+            BlockedAction::SetSection(section) => {
+                if *section != self.ui.blocked.section() {
+                    self.ui.blocked.switch_section();
+                }
+            }
+            BlockedAction::Select(index) => self.ui.blocked.set_selected(*index),
+            BlockedAction::SaveHar => self.save_har(),
+        }
+    }
+
+    /// Write the panel's request log as a HAR file, through the downloads list
+    /// so it shows there like any saved file.
+    fn save_har(&mut self) {
+        let log = self.ui.blocked.requests();
+        let filename = log.har_name();
+        let bytes = serde_json::to_vec_pretty(&log.to_har()).map_err(|e| e.to_string());
+        self.ui.menu.downloads.save_captured(BlobDownload {
+            filename: filename.clone(),
+            bytes,
+        });
+        self.ui
+            .blocked
+            .set_note(format!("Saved {filename} to the downloads folder"));
+    }
+
+    /// Close the find bar and clear the page's marks (B / Esc).
+    pub(super) fn find_close(&mut self) {
+        self.ui.find.close();
+        self.browser.find_clear();
+    }
+
     /// Apply a settings-overlay action (see [`crate::overlay::settings`]).
     fn settings_action(&mut self, action: &SettingsAction, out: &mut Vec<AppCommand>) {
         match action {
@@ -224,12 +442,32 @@ impl App {
                 self.settings_close(out);
                 self.shutdown();
             }
+            SettingsAction::ImportBookmarks => {
+                let path = bookmarks::html_path();
+                let note = self
+                    .ui
+                    .menu
+                    .bookmarks_mut()
+                    .import_html(&path)
+                    .map(|added| format!("Imported {added} new bookmarks from {path}"));
+                self.ui.settings.set_action_note(note);
+            }
+            SettingsAction::ExportBookmarks => {
+                let path = bookmarks::html_path();
+                let note = self
+                    .ui
+                    .menu
+                    .bookmarks()
+                    .export_html(&path)
+                    .map(|count| format!("Exported {count} bookmarks to {path}"));
+                self.ui.settings.set_action_note(note);
+            }
         }
     }
 
     /// A / Enter on the focused settings row: add/remove a binding in the Controls
     /// section, open the on-screen keyboard on a text field, or step every other
-    /// kind forward (Left/Right does the rest).
+    /// kind forward (Left/Right does the rest). An action row pushes its action.
     pub(super) fn settings_confirm(&mut self, out: &mut Vec<AppCommand>) {
         if self.ui.settings.is_info_section() {
             // About tab: A activates the focused row (update action or a link);
@@ -241,6 +479,8 @@ impl App {
             self.ui.settings.controls_activate();
         } else if self.ui.settings.selected_is_text() {
             self.ui.osk(OskCommand::Show, &self.browser, out);
+        } else if let Some(action) = self.ui.settings.selected_action() {
+            out.push(AppCommand::Settings(action));
         } else {
             self.ui.settings.adjust(1);
         }
@@ -285,8 +525,14 @@ impl App {
             .set_toolbar_autohide(self.config.display.toolbar_autohide);
         self.ui.set_hint_badges(self.config.input.hint_badges);
         self.ui.menu.history_mut().set_config(&self.config.history);
+        self.ui
+            .menu
+            .downloads
+            .set_config(&self.config.downloads, &self.event_sender);
         self.ui.set_memory_overlay(self.config.debug.memory_overlay);
         self.ui.set_update_config(&self.config.update);
+        self.ui
+            .set_search_engine(self.config.search_engine().cloned());
         // Lightweight-mode block flags take effect on the next subresource load,
         // no restart needed (unlike the engine-thread counts beside them).
         self.browser.set_content_filter(
@@ -295,8 +541,12 @@ impl App {
         // Experimental features apply live too — effective on the next page load.
         self.browser
             .set_experimental_prefs(&self.config.experimental);
+        self.browser
+            .set_game_sites(self.config.input.game_sites.clone());
         // The page theme needs no reload at all: open tabs restyle in place.
         self.browser.set_page_theme(self.config.browser.page_theme);
+        self.browser.set_https_only(self.config.browser.https_only);
+        crate::data::clean_url::configure(&self.config.url_cleaning);
     }
 
     /// A on the start page: open the focused speed-dial tile, open the speed-dial
@@ -324,7 +574,7 @@ impl App {
             // dial; the regular pin tiles are edit-only (delete with X).
             EditItem::Tile(_) => {
                 if self.ui.dial_edit_settings_selected() {
-                    self.ui.menu.dial.toggle(crate::data::dial::SETTINGS_PIN);
+                    self.ui.menu.dial.toggle(SETTINGS_PIN, "");
                 }
             }
         }
@@ -334,10 +584,9 @@ impl App {
     /// the same way navigation is, then clear the field (it stays open to add
     /// more).
     fn dial_add(&mut self, text: &str) {
-        if let Some(url) =
-            crate::browser::try_into_url(text.trim(), &self.config.browser.search_page)
-        {
-            self.ui.menu.dial.pin(url.as_str());
+        if let Some(url) = crate::browser::try_into_url(text.trim(), &self.config) {
+            let title = self.ui.menu.known_title(url.as_str());
+            self.ui.menu.dial.pin(url.as_str(), &title);
         }
         self.ui.dial_edit_clear_input();
     }
@@ -346,14 +595,14 @@ impl App {
     /// sentinel, not a real address: it opens the settings overlay instead of
     /// navigating (so a settings speed-dial tile / menu row behaves like the toolbar's).
     fn open_url(&mut self, url: String) {
-        if url == crate::data::dial::SETTINGS_PIN {
+        if url == SETTINGS_PIN {
             self.ui.menu.close();
             self.ui.settings_open(&self.config);
             return;
         }
         *self.browser.get_state_mut().get_location_mut() = url;
         self.browser
-            .execute_command(&BrowserCommand::Load, &self.config.browser);
+            .execute_command(&BrowserCommand::Load, &self.config);
         self.ui.menu.close();
     }
 }
@@ -7,11 +7,17 @@ mod command;
 mod execute;
 mod router;
 
-pub use command::{AppCommand, InputCommand, MenuAction, PromptAction, SettingsAction};
+pub use command::{
+    AppCommand, BlockedAction, FindAction, InputCommand, MenuAction, PromptAction, SettingsAction,
+    SiteAction,
+};
 
-use crate::browser::AppBrowser;
+use crate::browser::{AppBrowser, CertError, TabInfo, UpgradeFailed};
+use crate::data::favicons;
+use crate::data::session::{Session, SessionTab, Snapshot};
 use crate::event::handler::AppEventHandler;
 use crate::event::user::UserEventSender;
+use crate::overlay::prompt::AppDialog;
 use crate::ui::AppUi;
 use crate::{config::AppConfig, platform::window::AppWindow};
 use sdl2::Sdl;
@@ -46,9 +52,14 @@ pub struct App {
     /// loss to [`HISTORY_FLUSH_INTERVAL`] while browsing, without ever waking the
     /// idle loop — the flush only fires on frames the loop is already running.
     last_history_flush: Instant,
+    /// The open tabs as last saved, snapshotted from the browser on the history
+    /// flush throttle (see [`crate::data::session`]).
+    session: Session,
     /// Last time a memory report was requested (debug overlay only). Throttles
     /// the requests to [`MEMORY_REPORT_INTERVAL`] since each one walks every reporter.
     last_memory_report: Instant,
+    /// Encodes and writes reported favicons off the main thread.
+    favicons: favicons::Writer,
     /// Holds `SDL_INIT_AUDIO` open for the WebAudio backend ([`crate::media`]);
     /// dropping it closes the sinks' devices. `None` when audio is off/unavailable.
     _audio: Option<sdl2::AudioSubsystem>,
@@ -74,7 +85,7 @@ impl App {
         let browser = AppBrowser::new(window.rendering_ctx(), event_sender.clone(), &config)?;
         log::info!("init: browser ready; creating event handler + ui");
         let event_handler = AppEventHandler::new(sdl, config.input.clone())?;
-        let ui = AppUi::new(
+        let mut ui = AppUi::new(
             &window,
             &config.display,
             &config.history,
@@ -84,7 +95,10 @@ impl App {
             &config.debug,
             &config.update,
             crate::browser::effective_user_agent(&config.browser),
+            event_sender.clone(),
         );
+        ui.set_search_engine(config.search_engine().cloned());
+        let favicons = favicons::Writer::new(event_sender.clone());
         log::info!("init: app constructed");
 
         Ok(Self {
@@ -100,16 +114,21 @@ impl App {
             osk_nav_next: Instant::now(),
             hint_press_at: None,
             last_history_flush: Instant::now(),
+            session: Session::load(),
             last_memory_report: Instant::now(),
+            favicons,
             _audio: audio,
         })
     }
 
     pub fn run(mut self) {
-        self.browser.open_tab(&self.config.browser.home_page);
+        self.start_session();
         // Throttled background check for a newer build (`[update] auto_check`); its
         // result surfaces via the toolbar update chip, never a blocking prompt.
         self.ui.update_auto_check(&self.event_sender);
+        // Downloads that were running or queued when the app last quit go back in
+        // the queue, picking up their `.part`.
+        self.ui.menu.downloads.resume_unfinished(&self.event_sender);
         self.state = AppState::Running;
         let mut commands = Vec::with_capacity(4);
 
@@ -128,14 +147,26 @@ impl App {
             for url in self.browser.take_visited() {
                 self.ui.menu.record_history(&url);
             }
+            // Titles and icons after the visits, so a new entry can take its title.
+            for (url, title) in self.browser.take_titles() {
+                self.ui.menu.set_page_title(&url, &title);
+            }
+            for (url, icon) in self.browser.take_favicons() {
+                self.favicons.save(url, icon);
+            }
+            for url in self.favicons.take_saved() {
+                self.ui.favicon_saved(&url);
+            }
 
             // Recording only marks history dirty; flush it on a throttle so a busy
             // browsing burst collapses to one write per interval. This piggybacks
             // on frames the loop is already awake for — it never schedules an idle
             // wake (the blocking wait stays battery-efficient). A clean exit and
-            // menu close flush the remainder.
+            // menu close flush the remainder. The open tabs ride the same tick.
             if self.last_history_flush.elapsed() >= HISTORY_FLUSH_INTERVAL {
-                self.ui.menu.flush_history();
+                self.ui.menu.flush_stores();
+                self.browser.flush_site_settings();
+                self.save_session();
                 self.last_history_flush = Instant::now();
             }
 
@@ -162,7 +193,7 @@ impl App {
 
             // Apply background download progress/finishes before building the UI,
             // and start any downloads the browser denied navigation for.
-            self.ui.menu.downloads.poll();
+            self.ui.menu.downloads.poll(&self.event_sender);
             for request in self.browser.take_download_requests() {
                 self.ui.menu.downloads.start(request, &self.event_sender);
             }
@@ -175,15 +206,28 @@ impl App {
 
             // Modal page controls (select pickers, JS dialogs): queue fresh
             // ones for the prompt overlay and drop ones Servo retracted.
+            // Certificate errors and failed HTTPS upgrades get the same modal,
+            // asking before going on.
             let controls = self.browser.take_embedder_controls();
             let dismissed = self.browser.take_dismissed_controls();
-            let prompt_changed = !controls.is_empty() || !dismissed.is_empty();
+            let cert_errors = self.browser.take_cert_errors();
+            let upgrade_failures = self.browser.take_upgrade_failures();
+            let prompt_changed = !controls.is_empty()
+                || !dismissed.is_empty()
+                || !cert_errors.is_empty()
+                || !upgrade_failures.is_empty();
             for control in controls {
                 self.ui.prompt.push(control);
             }
             for id in dismissed {
                 self.ui.prompt.dismiss(id);
             }
+            for error in cert_errors {
+                self.ui.prompt.push_app(cert_error_dialog(error));
+            }
+            for failure in upgrade_failures {
+                self.ui.prompt.push_app(upgrade_failed_dialog(failure));
+            }
 
             // Hint mode: hand freshly collected clickable rects to the UI, and
             // start a re-collect once a post-scroll refresh comes due.
@@ -194,6 +238,11 @@ impl App {
                 self.browser.collect_hints();
             }
 
+            // Find-in-page: the page's answer to the last search or step.
+            if let Some((current, total)) = self.browser.take_find_result() {
+                self.ui.find.set_result(current, total);
+            }
+
             // Render Servo into its FBO; egui composites that FBO's texture.
             self.browser.paint();
 
@@ -229,7 +278,10 @@ impl App {
 
         // Persist history buffered since the last throttle tick — `Drop` won't
         // run (we `process::exit` below), so this must be explicit.
-        self.ui.menu.flush_history();
+        self.ui.menu.flush_stores();
+        self.browser.flush_site_settings();
+        self.save_session();
+        Session::clear_crashed();
         self.ui.destroy();
 
         // Shut Servo down cleanly first — that's when cookies / localStorage
@@ -242,6 +294,90 @@ impl App {
         std::process::exit(0);
     }
 
+    /// Open the first tabs: the previous run's when `[browser] restore_session`
+    /// is on, else the home page. After a crash the home page opens and the user
+    /// is asked before anything is reopened (see [`crate::data::session`]).
+    fn start_session(&mut self) {
+        let crashed = Session::crashed();
+        // Consumed here: a later kill (not a panic) mustn't ask again.
+        Session::clear_crashed();
+        let (tabs, active) = self.session.saved().restorable();
+        if crashed && !tabs.is_empty() {
+            self.session.hold_offer();
+            let what = match tabs.len() {
+                1 => "its tab".to_string(),
+                n => format!("its {n} tabs"),
+            };
+            self.ui.prompt.push_app(AppDialog {
+                message: format!("retsurf closed unexpectedly. Reopen {what}?"),
+                confirm: "Restore",
+                cancel: "Start fresh",
+                on_confirm: AppCommand::RestoreSession(true),
+                on_cancel: Some(AppCommand::RestoreSession(false)),
+                focus_cancel: false,
+            });
+        } else if self.config.browser.restore_session && self.restore_tabs(&tabs, active) {
+            return;
+        }
+        self.browser.open_tab(&self.config.browser.home_page);
+    }
+
+    /// The answer to the crash question from [`Self::start_session`]. Either
+    /// way snapshots resume, so "Start fresh" forgets the old tabs.
+    fn answer_restore(&mut self, restore: bool) {
+        let Some(offer) = self.session.take_offer() else {
+            return;
+        };
+        if restore {
+            let (tabs, active) = offer.restorable();
+            self.restore_tabs(&tabs, active);
+        }
+    }
+
+    /// Reopen saved tabs in the browser, replacing the open ones.
+    fn restore_tabs(&mut self, tabs: &[SessionTab], active: usize) -> bool {
+        let infos: Vec<TabInfo> = tabs
+            .iter()
+            .enumerate()
+            .map(|(i, tab)| TabInfo {
+                title: tab.title.clone(),
+                url: tab.url.clone(),
+                active: i == active,
+                zoom: tab.zoom,
+                no_history: false,
+            })
+            .collect();
+        self.browser.restore_tabs(&infos)
+    }
+
+    /// Snapshot the open tabs into the session and write it if they changed.
+    /// No-history tabs aren't saved.
+    fn save_session(&mut self) {
+        let infos: Vec<_> = self
+            .browser
+            .tabs()
+            .into_iter()
+            .filter(|t| !t.no_history)
+            .collect();
+        let active = infos.iter().position(|t| t.active).unwrap_or(0);
+        let tabs = infos
+            .into_iter()
+            .map(|t| SessionTab {
+                // The menu falls back to the URL for an untitled page; don't
+                // save that as a title.
+                title: if t.title == t.url {
+                    String::new()
+                } else {
+                    t.title
+                },
+                url: t.url,
+                zoom: t.zoom,
+            })
+            .collect();
+        self.session.update(Snapshot { active, tabs });
+        self.session.flush();
+    }
+
     fn shutdown(&mut self) {
         self.state = AppState::ShuttingDown;
     }
@@ -250,3 +386,55 @@ impl App {
         self.ui.draw(&self.window);
     }
 }
+
+/// The question for a certificate error: what failed and what proceeding
+/// means, with going back focused.
+fn cert_error_dialog(error: CertError) -> AppDialog {
+    let CertError {
+        webview,
+        host,
+        reason,
+    } = error;
+    AppDialog {
+        message: format!(
+            "The connection to {host} isn't private: its certificate couldn't be \
+             verified ({reason}).\n\nSomeone may be impersonating the site. Proceed \
+             only if you trust this network and server; the exception lasts until \
+             retsurf quits."
+        ),
+        confirm: "Proceed anyway",
+        cancel: "Go back",
+        on_confirm: AppCommand::CertError {
+            webview,
+            host: host.clone(),
+            proceed: true,
+        },
+        on_cancel: Some(AppCommand::CertError {
+            webview,
+            host,
+            proceed: false,
+        }),
+        focus_cancel: true,
+    }
+}
+
+/// The question for a page HTTPS-only mode couldn't upgrade, with staying on
+/// the error page focused.
+fn upgrade_failed_dialog(failure: UpgradeFailed) -> AppDialog {
+    let host = failure.url.host_str().unwrap_or_default();
+    AppDialog {
+        message: format!(
+            "{host} doesn't offer a secure connection. Continue over HTTP? Anyone on \
+             the network could read or change the page. HTTP stays allowed for {host} \
+             until retsurf quits."
+        ),
+        confirm: "Continue over HTTP",
+        cancel: "Stay",
+        on_confirm: AppCommand::ContinueOverHttp {
+            webview: failure.webview,
+            url: failure.url,
+        },
+        on_cancel: None,
+        focus_cancel: true,
+    }
+}
@@ -3,10 +3,12 @@
 //! over the page or toolbar?" branches live — the gamepad itself stays
 //! state-agnostic and only emits intents.
 
-use super::{App, AppCommand, InputCommand, PromptAction};
+use super::{App, AppCommand, BlockedAction, InputCommand, PromptAction};
 use crate::browser::BrowserCommand;
 use crate::event::sdl2_servo::{into_mouse_button_event, into_mouse_move_event};
+use crate::overlay::blocked::BlockedSection;
 use crate::overlay::hints::{HintInput, Sym};
+use crate::overlay::menu::Section;
 use crate::overlay::osk::OskCommand;
 use crate::ui::Focus;
 use std::time::{Duration, Instant};
@@ -36,7 +38,7 @@ impl App {
                 }
                 Focus::Menu => {
                     if *pressed {
-                        self.menu_open_selected();
+                        self.menu_open_selected(out);
                     }
                 }
                 // The settings overlay: A toggles / cycles / steps the focused
@@ -46,6 +48,14 @@ impl App {
                         self.settings_confirm(out);
                     }
                 }
+                // The site settings sheet: A steps the focused row (or resets).
+                Focus::SiteEdit => {
+                    if *pressed {
+                        self.ui.site_edit.activate();
+                    }
+                }
+                // The blocked-requests panel is read-only.
+                Focus::Blocked => {}
                 Focus::Hints => {
                     // Tap vs hold on the selected hint: the press just starts the
                     // clock (so the click lands on release, where the duration is
@@ -69,6 +79,12 @@ impl App {
                         }
                     }
                 }
+                // A steps to the next match, like R1.
+                Focus::Find => {
+                    if *pressed {
+                        self.find_step(1, out);
+                    }
+                }
                 // The start page: A opens the OSK to type into its search field,
                 // or opens the focused speed-dial tile (see [`App::home_confirm`]).
                 Focus::Home => {
@@ -88,9 +104,14 @@ impl App {
             InputCommand::Cancel => match focus {
                 Focus::Osk => self.ui.osk(OskCommand::Hide, &self.browser, out),
                 Focus::Prompt => out.push(AppCommand::Prompt(PromptAction::Cancel)),
-                Focus::Menu => self.ui.menu.close(),
+                // B closes the bookmark edit sheet or leaves a bookmark folder
+                // first (see [`App::menu_cancel`]).
+                Focus::Menu => self.menu_cancel(),
                 // B saves the draft and closes (same as the close button).
                 Focus::Settings => self.settings_close(out),
+                // B stores the site's overrides and closes.
+                Focus::SiteEdit => self.site_close(),
+                Focus::Blocked => self.ui.blocked.close(),
                 // B drops a half-typed combo first, then exits hint mode.
                 Focus::Hints => {
                     if self.ui.hints.has_typed() {
@@ -99,23 +120,36 @@ impl App {
                         self.ui.hints.hide();
                     }
                 }
+                // B closes the find bar, clearing the page's marks.
+                Focus::Find => self.find_close(),
                 // B in the editor returns to the start page.
                 Focus::DialEdit => self.ui.close_pins_editor(),
                 // B on the start page goes back like a normal page.
                 Focus::Home | Focus::Page => self
                     .browser
-                    .execute_command(&BrowserCommand::Back, &self.config.browser),
+                    .execute_command(&BrowserCommand::Back, &self.config),
             },
             InputCommand::ToggleOsk => {
                 if focus == Focus::Menu {
-                    // X deletes the highlighted entry (closes a tab in the Tabs section).
-                    self.delete_menu_selection();
+                    match self.ui.menu.bookmark_edit() {
+                        // On the bookmark edit sheet X types into a text row, as
+                        // A does; the other rows ignore it.
+                        Some(edit) => {
+                            if edit.text_selected() {
+                                self.ui.osk(OskCommand::Show, &self.browser, out);
+                            }
+                        }
+                        // X deletes the highlighted entry (closes a tab in the
+                        // Tabs section).
+                        None => self.delete_menu_selection(),
+                    }
                 } else if focus == Focus::DialEdit {
                     // X deletes the focused pin tile (no-op on the field or the
                     // trailing settings toggle, which pins/unpins with A).
                     self.ui.dial_edit_remove_selected();
-                } else if focus == Focus::Settings {
-                    // X is unused in settings (rows edit with A and Left/Right).
+                } else if matches!(focus, Focus::Settings | Focus::SiteEdit | Focus::Blocked) {
+                    // X is unused in settings, the site sheet (rows edit with A
+                    // and Left/Right) and the blocked-requests panel.
                 } else if focus == Focus::Hints && self.config.input.hint_badges {
                     // In hint mode X is a combo symbol, not the OSK toggle (unless
                     // combos are disabled, when it falls through to the OSK below).
@@ -139,7 +173,10 @@ impl App {
             // Tab switching is parked while a modal prompt is up — it belongs
             // to the page that opened it.
             InputCommand::CycleTab(delta) => {
-                if !self.ui.prompt.visible() && focus != Focus::Settings {
+                if !self.ui.prompt.visible()
+                    && !matches!(focus, Focus::Settings | Focus::SiteEdit | Focus::Blocked)
+                {
+                    self.close_find_for_tab_switch();
                     self.browser.cycle_tab(*delta);
                 }
             }
@@ -150,8 +187,16 @@ impl App {
             InputCommand::Nav(dx, dy) => match focus {
                 Focus::Osk => self.ui.osk(OskCommand::Move(*dx, *dy), &self.browser, out),
                 Focus::Prompt => self.ui.prompt.move_sel(*dx, *dy),
+                // On the bookmark edit sheet Up/Down move between rows and
+                // Left/Right adjust the focused value, like settings.
                 Focus::Menu => {
-                    if *dx != 0 {
+                    if let Some(edit) = self.ui.menu.bookmark_edit_mut() {
+                        if *dy != 0 {
+                            edit.move_sel(*dy);
+                        } else {
+                            edit.adjust(*dx);
+                        }
+                    } else if *dx != 0 {
                         self.ui.menu.switch_section(*dx);
                     } else if *dy != 0 {
                         self.ui.menu.move_sel(*dy);
@@ -165,7 +210,21 @@ impl App {
                         self.ui.settings.adjust(*dx);
                     }
                 }
+                Focus::SiteEdit => {
+                    if *dy != 0 {
+                        self.ui.site_edit.move_sel(*dy);
+                    } else if *dx != 0 {
+                        self.ui.site_edit.adjust(*dx);
+                    }
+                }
+                Focus::Blocked => self.ui.blocked.move_sel(*dy),
                 Focus::Hints => self.hints_nav(*dx, *dy),
+                // Up/Down step through the matches; Left/Right are unused.
+                Focus::Find => {
+                    if *dy != 0 {
+                        self.find_step(*dy, out);
+                    }
+                }
                 Focus::Home => self.ui.home_move(*dx, *dy),
                 Focus::DialEdit => self.ui.dial_edit_move(*dx, *dy),
                 Focus::Page => {}
@@ -186,9 +245,9 @@ impl App {
                     self.hint_key(*c);
                 }
             }
-            // Y / L3: contextually a pin/bookmark toggle or link-hint navigation.
-            // In the menu it depends on the section — Bookmarks pins (or unpins)
-            // the selected entry to the speed dial, while History and Tabs toggle
+            // Y / L3: contextually a bookmark edit/toggle or link-hint navigation.
+            // In the menu it depends on the section — Bookmarks opens (or saves)
+            // the edit sheet of the selected entry, while History and Tabs toggle
             // a bookmark on the selected entry / tab. With the keyboard open it
             // types a space (its dedicated OSK shortcut — see [`crate::overlay::osk`]).
             // On the bare page it toggles link hints (collection is asynchronous —
@@ -198,7 +257,18 @@ impl App {
             InputCommand::Hints => match focus {
                 Focus::Menu => self.menu_y_action(),
                 Focus::Osk => self.ui.osk(OskCommand::Space, &self.browser, out),
-                Focus::Home | Focus::Prompt | Focus::DialEdit | Focus::Settings => {}
+                Focus::Home
+                | Focus::Prompt
+                | Focus::DialEdit
+                | Focus::Settings
+                | Focus::SiteEdit
+                | Focus::Find => {}
+                // Y saves the Requests section as HAR.
+                Focus::Blocked => {
+                    if self.ui.blocked.section() == BlockedSection::Requests {
+                        out.push(AppCommand::Blocked(BlockedAction::SaveHar));
+                    }
+                }
                 // In hint mode Y is a combo symbol (B exits instead); with combos
                 // off it keeps its old meaning of hiding the hints.
                 Focus::Hints if self.config.input.hint_badges => self.hint_sym(Sym::Y),
@@ -209,12 +279,19 @@ impl App {
                 }
             },
             InputCommand::Shoulder(delta) => match focus {
+                // The bookmark edit sheet holds the section until it's closed.
+                Focus::Menu if self.ui.menu.bookmark_edit().is_some() => {}
                 Focus::Menu => self.ui.menu.switch_section(*delta),
                 // L1/R1 switch the settings section (Left/Right edits values).
                 Focus::Settings => self.ui.settings.switch_section(*delta),
+                // The site sheet has a single section; the blocked panel two.
+                Focus::SiteEdit => {}
+                Focus::Blocked => self.ui.blocked.switch_section(),
                 // In the dial editor they reorder the focused pin (Left/Right
                 // moves the selection there).
                 Focus::DialEdit => self.ui.dial_edit_move_selected(*delta),
+                // With the find bar up L1/R1 step to the previous / next match.
+                Focus::Find => self.find_step(*delta, out),
                 // In hint mode L1/R1 are combo symbols; with combos off they fall
                 // through to the page back/forward below.
                 Focus::Hints if self.config.input.hint_badges => {
@@ -229,7 +306,7 @@ impl App {
                     } else {
                         BrowserCommand::Forward
                     };
-                    self.browser.execute_command(&cmd, &self.config.browser);
+                    self.browser.execute_command(&cmd, &self.config);
                 }
             },
             InputCommand::Trigger { right, pressed } => {
@@ -242,8 +319,23 @@ impl App {
                     } else {
                         self.ui.osk(OskCommand::Shift(*pressed), &self.browser, out);
                     }
-                } else if *pressed && !self.ui.prompt.visible() && focus != Focus::Settings {
+                } else if focus == Focus::Menu
+                    && matches!(
+                        self.ui.menu.section(),
+                        Section::Bookmarks | Section::Downloads
+                    )
+                {
+                    // The Bookmarks and Downloads sections: L2 / R2 move the
+                    // highlighted entry up / down instead of switching tabs.
+                    if *pressed {
+                        self.ui.menu.move_entry(if *right { 1 } else { -1 });
+                    }
+                } else if *pressed
+                    && !self.ui.prompt.visible()
+                    && !matches!(focus, Focus::Settings | Focus::SiteEdit | Focus::Blocked)
+                {
                     // Quick tab switch: L2 previous, R2 next (wraps).
+                    self.close_find_for_tab_switch();
                     self.browser.cycle_tab(if *right { 1 } else { -1 });
                 }
             }
@@ -252,9 +344,11 @@ impl App {
             InputCommand::Osk(cmd) => {
                 if focus == Focus::Osk {
                     self.ui.osk(*cmd, &self.browser, out);
-                } else if matches!(cmd, OskCommand::Space) && focus != Focus::Settings {
+                } else if matches!(cmd, OskCommand::Space)
+                    && !matches!(focus, Focus::Settings | Focus::SiteEdit | Focus::Blocked)
+                {
                     self.browser
-                        .execute_command(&BrowserCommand::Reload, &self.config.browser);
+                        .execute_command(&BrowserCommand::Reload, &self.config);
                 }
             }
             InputCommand::Analog {
@@ -304,6 +398,14 @@ impl App {
         self.ui.hints.mark_stale_at((sx, edge_y));
     }
 
+    /// Close the find bar before a tab switch: its marks and counter belong to
+    /// the page being left.
+    fn close_find_for_tab_switch(&mut self) {
+        if self.ui.find.visible {
+            self.find_close();
+        }
+    }
+
     /// Feed a combo symbol to hint mode and, when it resolves to one hint, click
     /// it (via the shared `activate_hint` path). A dead-end or completed-but-unused
     /// combo (`NoMatch`) just clears the buffer inside `push_sym` — the faded
@@ -414,9 +516,9 @@ impl App {
             if self.nav_repeat(dir, now) && dir != (0, 0) {
                 out.push(AppCommand::Input(InputCommand::Nav(dir.0, dir.1)));
             }
-            // In hint mode the right stick still scrolls the page (the badges
-            // go stale as it moves — schedule a re-collect).
-            if self.ui.hints.visible && scroll != 0.0 {
+            // In hint mode and under the find bar the right stick still scrolls
+            // the page (hint badges go stale as it moves — schedule a re-collect).
+            if (self.ui.hints.visible || self.ui.find.visible) && scroll != 0.0 {
                 let dy = scroll * scroll_speed * dt;
                 let (x, y) = self
                     .ui
@@ -2,7 +2,9 @@
 //! network request with its URL, destination, and referrer — enough to run
 //! Brave's adblock-rust engine (EasyList syntax) over it; blocked loads are
 //! intercepted with an empty 200 response in [`crate::browser`]. Toggled with
-//! `[adblock] enabled` in the config.
+//! `[adblock] enabled` in the config. The same engine answers the lists'
+//! element-hiding rules for the cosmetic pass (`[adblock] cosmetic`, see
+//! `browser::cosmetic`).
 //!
 //! The engine is not thread-safe (the crate's faster single-thread build), so
 //! it never leaves the main thread: a background thread downloads the filter
@@ -11,20 +13,56 @@
 //! it lazily on the next request check. With a cache present, startup loads it
 //! directly and only refreshes in the background once it's older than
 //! `update_days`.
+//!
+//! A list can also be a local file (a `file://` URL, or a path — relative ones
+//! under the data dir), read at each build instead of downloaded, so a device
+//! that never gets online still blocks. Until the first build succeeds, the
+//! engine bundled into the binary (`resources/adblock/default.dat`, see
+//! [`crate::platform::resources::AdblockResources`]) stands in when
+//! `[adblock] bundled` allows it.
+//!
+//! The user's own rules live in `filters.txt` in the data dir and are merged
+//! into every build, as is the per-site allowlist: "disable blocking on this
+//! site" adds an `@@||host^$document` line there. Each downloaded list is kept
+//! as text (under `cache/adblock-lists/`), so a change to the user rules or a
+//! local list — the toggle, or an edit seen at startup — rebuilds in the
+//! background from them without waiting for `update_days` or the network.
 
 use crate::config::{self, AdblockConfig};
+use crate::data::site_settings::SiteSettings;
+use crate::platform::resources::AdblockResources;
 use adblock::lists::{FilterSet, ParseOptions};
 use adblock::request::Request;
 use adblock::Engine;
 use content_security_policy::Destination;
 use servo::WebResourceRequest;
+use sha2::{Digest, Sha256};
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime};
 
+/// User rules (EasyList syntax) in the data dir, merged into every build.
+const USER_FILTERS: &str = "filters.txt";
+
+/// One build at a time, so the last one started (with the newest user rules)
+/// is the last to deliver.
+static BUILD: Mutex<()> = Mutex::new(());
+
 pub struct Adblock {
     enabled: bool,
+    /// Element hiding on top of the network blocking.
+    cosmetic: bool,
+    /// `[adblock] lists`, for the rebuilds a user-rule change starts.
+    lists: Vec<String>,
+    /// The bundled engine is standing in (nothing was built yet); a build then
+    /// waits for the lists rather than replace it with the user rules alone.
+    bundled: bool,
+    /// Hosts with an allowlist rule in `filters.txt`: nothing is blocked or
+    /// hidden on their pages (or their subdomains').
+    allowlist: RefCell<HashSet<String>>,
     /// The engine, present once loaded — from cache at startup, or lazily from
     /// `fresh` once the builder thread delivers.
     engine: RefCell<Option<Engine>>,
@@ -35,42 +73,110 @@ pub struct Adblock {
 impl Adblock {
     pub fn new(cfg: &AdblockConfig) -> Self {
         let fresh = Arc::new(Mutex::new(None));
+        let user = read_user_filters();
+        let allowlist = RefCell::new(allowlisted_hosts(&user));
         if !cfg.enabled {
             return Self {
                 enabled: false,
+                cosmetic: false,
+                lists: Vec::new(),
+                bundled: false,
+                allowlist,
                 engine: RefCell::new(None),
                 fresh,
             };
         }
 
         let cache = cache_path();
-        let engine = load_cached(&cache);
-        if (engine.is_none() || cache_is_stale(&cache, cfg.update_days)) && !cfg.lists.is_empty() {
-            let lists = cfg.lists.clone();
-            let fresh = fresh.clone();
-            std::thread::spawn(move || build_engine(lists, cache, fresh));
-        }
-        Self {
+        let cached = load_cached(&cache);
+        let built = cached.is_some();
+        let bundled = !built && cfg.bundled;
+        let engine = cached.or_else(|| bundled.then(load_bundled).flatten());
+        let buildable = !cfg.lists.is_empty() || !user.trim().is_empty();
+        let adblock = Self {
             enabled: true,
+            cosmetic: cfg.cosmetic,
+            lists: cfg.lists.clone(),
+            bundled: bundled && engine.is_some(),
+            allowlist,
             engine: RefCell::new(engine),
             fresh,
+        };
+        if !buildable {
+            return adblock;
+        }
+        if !built || cache_is_stale(&cache, cfg.update_days) {
+            adblock.rebuild(true);
+        } else if sources_changed(&cfg.lists, &cache) {
+            adblock.rebuild(false);
         }
+        adblock
     }
 
-    /// Whether this request should be blocked. Lazily swaps in a freshly built
-    /// engine when the builder thread has delivered one.
-    pub fn should_block(&self, request: &WebResourceRequest) -> bool {
-        if !self.enabled {
+    /// Whether the page at `url` is on the allowlist (its host or a parent
+    /// domain), so nothing is blocked on it.
+    pub fn is_allowlisted(&self, url: &str) -> bool {
+        let Some(host) = SiteSettings::host_key(url) else {
             return false;
+        };
+        let allowlist = self.allowlist.borrow();
+        domains(&host).any(|domain| allowlist.contains(domain))
+    }
+
+    /// Allowlist the page at `url`'s host, or drop the rules covering it (its
+    /// own and any parent domain's), in `filters.txt`, then rebuild the engine
+    /// with them. The allowlist itself applies at once.
+    pub fn set_allowlisted(&self, url: &str, allowed: bool) {
+        let Some(host) = SiteSettings::host_key(url) else {
+            return;
+        };
+        let mut text = read_user_filters();
+        let mut changed = false;
+        let hosts: Vec<&str> = if allowed {
+            vec![host.as_str()]
+        } else {
+            domains(&host).collect()
+        };
+        for host in hosts {
+            if let Some(edited) = with_allow_rule(&text, host, allowed) {
+                text = edited;
+                changed = true;
+            }
+        }
+        if !changed {
+            return;
+        }
+        if let Err(e) = std::fs::write(user_filters_path(), &text) {
+            log::warn!("adblock: could not write {USER_FILTERS}: {e}");
+            return;
+        }
+        *self.allowlist.borrow_mut() = allowlisted_hosts(&text);
+        if self.enabled {
+            self.rebuild(false);
+        }
+    }
+
+    /// Build a new engine in the background, from freshly downloaded lists
+    /// (`refetch`) or the kept copies (downloading only those missing).
+    fn rebuild(&self, refetch: bool) {
+        let lists = self.lists.clone();
+        let bundled = self.bundled;
+        let fresh = self.fresh.clone();
+        std::thread::spawn(move || build_engine(lists, refetch, bundled, fresh));
+    }
+
+    /// The rule blocking this request, if one does. Lazily swaps in a freshly
+    /// built engine when the builder thread has delivered one.
+    pub fn blocking_rule(&self, request: &WebResourceRequest) -> Option<String> {
+        if !self.enabled {
+            return None;
         }
         self.take_fresh();
         let engine = self.engine.borrow();
-        let Some(engine) = engine.as_ref() else {
-            return false;
-        };
+        let engine = engine.as_ref()?;
         // Never block the page itself, only subresources / frames.
         if request.is_for_main_frame {
-            return false;
+            return None;
         }
         let url = request.url.as_str();
         let source = request
@@ -78,15 +184,57 @@ impl Adblock {
             .as_ref()
             .map(url::Url::as_str)
             .unwrap_or(url);
-        let Ok(req) = Request::new(
+        let req = Request::new(
             url,
             source,
             request_type(request.destination),
             request.method.as_str(),
-        ) else {
-            return false;
-        };
-        engine.check_network_request(&req).should_block()
+        )
+        .ok()?;
+        let result = engine.check_network_request(&req);
+        // An engine cached before rule texts were kept can't name the rule.
+        result
+            .should_block()
+            .then(|| result.filter.unwrap_or_else(|| "filter list".to_string()))
+    }
+
+    /// Whether element hiding is on.
+    pub fn cosmetic(&self) -> bool {
+        self.enabled && self.cosmetic
+    }
+
+    /// The element-hiding selectors for the page at `url`: its site's `##`
+    /// rules and the generic ones not keyed on a class or id, less the page's
+    /// `#@#` exceptions. Sorted, so the same page gives the same sheet.
+    pub fn hide_selectors(&self, url: &str) -> Vec<String> {
+        self.with_cosmetic_engine(|engine| {
+            let resources = engine.url_cosmetic_resources(url);
+            let mut selectors: Vec<String> = resources.hide_selectors.into_iter().collect();
+            selectors.sort();
+            selectors
+        })
+    }
+
+    /// The generic class / id hide rules matching the `classes` and `ids` found
+    /// on the page at `url`, less its exceptions; none on a `$generichide` site.
+    pub fn generic_selectors(&self, url: &str, classes: &[String], ids: &[String]) -> Vec<String> {
+        self.with_cosmetic_engine(|engine| {
+            let resources = engine.url_cosmetic_resources(url);
+            if resources.generichide {
+                return Vec::new();
+            }
+            engine.hidden_class_id_selectors(classes, ids, &resources.exceptions)
+        })
+    }
+
+    /// Run `query` on the engine for the cosmetic pass; the default without one
+    /// (off, or not loaded yet).
+    fn with_cosmetic_engine<T: Default>(&self, query: impl FnOnce(&Engine) -> T) -> T {
+        if !self.cosmetic() {
+            return T::default();
+        }
+        self.take_fresh();
+        self.engine.borrow().as_ref().map(query).unwrap_or_default()
     }
 
     /// Move a builder-thread DAT (if one arrived) into the live engine.
@@ -113,6 +261,106 @@ fn cache_path() -> String {
     format!("{}adblock.dat", config::cache_dir())
 }
 
+/// Where a downloaded list's text is kept for rebuilds, named by a hash of its
+/// URL.
+fn kept_list_path(url: &str) -> PathBuf {
+    let digest = Sha256::digest(url.as_bytes());
+    let name: String = digest[..8].iter().map(|b| format!("{b:02x}")).collect();
+    Path::new(&config::cache_dir())
+        .join("adblock-lists")
+        .join(format!("{name}.txt"))
+}
+
+/// Where a `lists` entry is on disk: a `file://` URL, or a plain path, a
+/// relative one under the data dir. `None` for a URL to download.
+fn local_path(source: &str) -> Option<PathBuf> {
+    match url::Url::parse(source) {
+        Ok(url) if url.scheme() == "file" => url.to_file_path().ok(),
+        // A one-letter "scheme" is a Windows drive (`C:\lists\x.txt`).
+        Ok(url) if url.scheme().len() > 1 => None,
+        _ => Some(Path::new(&config::data_dir()).join(source)),
+    }
+}
+
+/// Whether `filters.txt` or a local list changed since the engine at `cache`
+/// was built.
+fn sources_changed(lists: &[String], cache: &str) -> bool {
+    let built = modified(Path::new(cache));
+    let local = lists.iter().filter_map(|source| local_path(source));
+    std::iter::once(PathBuf::from(user_filters_path()))
+        .chain(local)
+        .any(|path| modified(&path) > built)
+}
+
+fn user_filters_path() -> String {
+    format!("{}{USER_FILTERS}", config::data_dir())
+}
+
+/// The user rules; none without the file.
+fn read_user_filters() -> String {
+    std::fs::read_to_string(user_filters_path()).unwrap_or_default()
+}
+
+/// A file's modification time; `None` (oldest) when it has none.
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// `host` and its parent domains, most specific first.
+fn domains(host: &str) -> impl Iterator<Item = &str> {
+    std::iter::successors(Some(host), |rest| {
+        rest.split_once('.').map(|(_, parent)| parent)
+    })
+}
+
+/// The allowlist rule for `host`: its pages (and its subdomains') are exempt.
+fn allow_rule(host: &str) -> String {
+    format!("@@||{host}^$document")
+}
+
+/// The hosts `text` allowlists — exactly the [`allow_rule`] lines; other
+/// exceptions are left to the engine.
+fn allowlisted_hosts(text: &str) -> HashSet<String> {
+    text.lines()
+        .filter_map(|line| {
+            let host = line
+                .trim()
+                .strip_prefix("@@||")?
+                .strip_suffix("^$document")?;
+            let plain = !host.is_empty()
+                && host
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-');
+            plain.then(|| host.to_ascii_lowercase())
+        })
+        .collect()
+}
+
+/// `text` with `host`'s allowlist rule added (at the end) or removed; `None`
+/// when it is already that way.
+fn with_allow_rule(text: &str, host: &str, allowed: bool) -> Option<String> {
+    let rule = allow_rule(host);
+    let present = text.lines().any(|line| line.trim() == rule);
+    if present == allowed {
+        return None;
+    }
+    if allowed {
+        let mut text = text.to_string();
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push_str(&rule);
+        text.push('\n');
+        return Some(text);
+    }
+    let kept: Vec<&str> = text.lines().filter(|line| line.trim() != rule).collect();
+    let mut text = kept.join("\n");
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    Some(text)
+}
+
 fn deserialize(dat: &[u8]) -> Option<Engine> {
     let mut engine = Engine::default();
     match engine.deserialize(dat) {
@@ -124,6 +372,16 @@ fn deserialize(dat: &[u8]) -> Option<Engine> {
     }
 }
 
+/// The engine bundled into the binary, when the build shipped one.
+fn load_bundled() -> Option<Engine> {
+    let dat = AdblockResources::get("default.dat")?;
+    let engine = deserialize(&dat.data);
+    if engine.is_some() {
+        log::info!("adblock: using the bundled engine until the lists are fetched");
+    }
+    engine
+}
+
 fn load_cached(path: &str) -> Option<Engine> {
     let dat = std::fs::read(path).ok()?;
     let engine = deserialize(&dat);
@@ -148,36 +406,89 @@ fn cache_is_stale(path: &str, update_days: u64) -> bool {
         .unwrap_or(false)
 }
 
-/// Builder thread: download the filter lists, build and serialize an engine,
-/// cache the DAT, and hand it to the main thread via `out`. Lists that fail to
-/// download are skipped; with none at all, the existing engine stays as is.
-fn build_engine(lists: Vec<String>, cache: String, out: Arc<Mutex<Option<Vec<u8>>>>) {
-    let mut filter_set = FilterSet::new(false);
-    let mut fetched = 0usize;
-    for url in &lists {
-        match fetch_list(url) {
-            Ok(text) => {
-                filter_set.add_filter_list(text, ParseOptions::default());
-                fetched += 1;
-                log::info!("adblock: fetched list `{url}`");
-            }
-            Err(e) => log::warn!("adblock: could not fetch list `{url}`: {e}"),
-        }
-    }
-    if fetched == 0 {
+/// Builder thread: read the filter lists (see [`read_lists`]), add the user
+/// rules, build and serialize an engine, cache the DAT, and hand it to the main
+/// thread via `out`. With no list to read the existing engine stays as is,
+/// unless there are user rules and no `bundled` engine to keep.
+fn build_engine(
+    lists: Vec<String>,
+    refetch: bool,
+    bundled: bool,
+    out: Arc<Mutex<Option<Vec<u8>>>>,
+) {
+    let _build = BUILD.lock().unwrap_or_else(|e| e.into_inner());
+    let text = read_lists(&lists, refetch);
+    let user = read_user_filters();
+    if text.is_none() && (bundled || user.trim().is_empty()) {
         return;
     }
+    // Debug mode keeps each rule's text, for the blocked-requests panel.
+    let mut filter_set = FilterSet::new(true);
+    if let Some(text) = &text {
+        filter_set.add_filter_list(text, ParseOptions::default());
+    }
+    filter_set.add_filter_list(&user, ParseOptions::default());
     let engine = Engine::new_with_filter_set(filter_set);
     let dat = engine.serialize();
-    if let Err(e) = std::fs::write(&cache, &dat) {
+    if let Err(e) = std::fs::write(cache_path(), &dat) {
         log::warn!("adblock: could not write engine cache: {e}");
     }
     *out.lock().unwrap() = Some(dat);
 }
 
+/// The text of every list that can be had, joined; `None` when none can.
+/// Local files are read as they are; a remote list is downloaded (`refetch`,
+/// or with no kept copy) and kept, else its kept copy is used — each falling
+/// back to the other.
+fn read_lists(lists: &[String], refetch: bool) -> Option<String> {
+    let mut text = String::new();
+    let mut read = 0usize;
+    for source in lists {
+        let list = match local_path(source) {
+            Some(path) => std::fs::read_to_string(&path).map_err(|e| e.to_string()),
+            None => remote_list(source, refetch),
+        };
+        match list {
+            Ok(list) => {
+                text.push_str(&list);
+                text.push('\n');
+                read += 1;
+            }
+            Err(e) => log::warn!("adblock: could not read list `{source}`: {e}"),
+        }
+    }
+    (read > 0).then_some(text)
+}
+
+/// A remote list's text (see [`read_lists`]).
+fn remote_list(url: &str, refetch: bool) -> Result<String, String> {
+    let kept = kept_list_path(url);
+    if !refetch {
+        if let Ok(text) = std::fs::read_to_string(&kept) {
+            return Ok(text);
+        }
+    }
+    match fetch_list(url) {
+        Ok(text) => {
+            log::info!("adblock: fetched list `{url}`");
+            let saved = kept
+                .parent()
+                .map_or(Ok(()), std::fs::create_dir_all)
+                .and_then(|()| std::fs::write(&kept, &text));
+            if let Err(e) = saved {
+                log::warn!("adblock: could not keep list `{url}`: {e}");
+            }
+            Ok(text)
+        }
+        Err(e) if refetch => std::fs::read_to_string(&kept).map_err(|_| e),
+        Err(e) => Err(e),
+    }
+}
+
 fn fetch_list(url: &str) -> Result<String, String> {
     let mut text = String::new();
-    ureq::get(url)
+    crate::net::agent(url)
+        .get(url)
         .call()
         .map_err(|e| e.to_string())?
         .into_body()
@@ -210,3 +521,72 @@ fn request_type(destination: Destination) -> &'static str {
         _ => "other",
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_rules(rules: &[&str]) -> Adblock {
+        Adblock {
+            enabled: true,
+            cosmetic: true,
+            lists: Vec::new(),
+            bundled: false,
+            allowlist: RefCell::default(),
+            engine: RefCell::new(Some(Engine::from_rules(rules, ParseOptions::default()))),
+            fresh: Arc::default(),
+        }
+    }
+
+    /// A site's own hide rule applies only there, and its exception lifts a
+    /// generic class rule the page would otherwise match.
+    #[test]
+    fn cosmetic_rules_follow_the_site() {
+        let adblock = with_rules(&[
+            "##.ad-box",
+            "example.com##.cookie-banner",
+            "example.com#@#.ad-box",
+        ]);
+        let here = adblock.hide_selectors("https://example.com/");
+        assert!(here.contains(&".cookie-banner".to_string()));
+        assert!(adblock.hide_selectors("https://other.org/").is_empty());
+
+        let classes = ["ad-box".to_string()];
+        let generic = |url| adblock.generic_selectors(url, &classes, &[]);
+        assert_eq!(generic("https://other.org/"), [".ad-box"]);
+        assert!(generic("https://example.com/").is_empty());
+    }
+
+    /// The toggle adds and removes exactly its own line; only plain allowlist
+    /// rules count as allowlisted hosts.
+    #[test]
+    fn allow_rule_round_trips() {
+        let user = "||ads.example^\n";
+        let on = with_allow_rule(user, "news.org", true).unwrap();
+        assert_eq!(on, "||ads.example^\n@@||news.org^$document\n");
+        assert!(with_allow_rule(&on, "news.org", true).is_none());
+        assert_eq!(with_allow_rule(&on, "news.org", false).unwrap(), user);
+
+        let hosts = allowlisted_hosts(&format!("{on}@@||*.cdn^$document\n@@||x.com^$script\n"));
+        assert_eq!(hosts, HashSet::from(["news.org".to_string()]));
+    }
+
+    /// `file://` URLs and paths are read from disk, relative paths under the
+    /// data dir; web URLs are downloaded.
+    #[test]
+    fn local_lists_resolve_to_paths() {
+        assert!(local_path("https://easylist.to/easylist/easylist.txt").is_none());
+        assert_eq!(
+            local_path("file:///opt/lists/mine.txt").unwrap(),
+            PathBuf::from("/opt/lists/mine.txt")
+        );
+        assert_eq!(
+            local_path("/opt/lists/mine.txt").unwrap(),
+            PathBuf::from("/opt/lists/mine.txt")
+        );
+        assert_eq!(
+            local_path("lists/mine.txt").unwrap(),
+            Path::new(&config::data_dir()).join("lists/mine.txt")
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! What the network hook in [`super::delegate`] blocked on a tab's current page:
+//! a count per reason and the most recent loads with the rule that matched, so
+//! a site that broke can be checked against the blocking. Kept per tab and
+//! cleared on its top-level navigations, like the image cap's set. The toolbar's
+//! shield chip shows the count; the blocked-requests panel
+//! ([`crate::overlay::blocked`]) lists the loads.
+
+use content_security_policy::Destination;
+use std::collections::VecDeque;
+
+/// Loads kept per page; the counts go on past it.
+const MAX_ENTRIES: usize = 300;
+
+/// Why a load was blocked.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BlockKind {
+    /// A filter-list rule, on something the page shows (a script, frame, image
+    /// or style).
+    Ad,
+    /// A filter-list rule, on a load with nothing to show (fetch, beacon,
+    /// ping) — the shape of tracking.
+    Tracker,
+    /// `block_images`, globally or for the site.
+    Image,
+    /// `block_media`.
+    Media,
+    /// `block_fonts`.
+    Font,
+    /// An image past `max_images_per_page`.
+    ImageCap,
+}
+
+impl BlockKind {
+    pub const ALL: [BlockKind; 6] = [
+        BlockKind::Ad,
+        BlockKind::Tracker,
+        BlockKind::Image,
+        BlockKind::Media,
+        BlockKind::Font,
+        BlockKind::ImageCap,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BlockKind::Ad => "Ads",
+            BlockKind::Tracker => "Trackers",
+            BlockKind::Image => "Images",
+            BlockKind::Media => "Media",
+            BlockKind::Font => "Fonts",
+            BlockKind::ImageCap => "Image cap",
+        }
+    }
+
+    /// A filter-list block of a load to `destination`: an ad, unless nothing
+    /// would show.
+    pub fn filtered(destination: Destination) -> Self {
+        match destination {
+            Destination::None | Destination::Json | Destination::Report => BlockKind::Tracker,
+            _ => BlockKind::Ad,
+        }
+    }
+
+    /// A lightweight-mode block (see [`super::content_filter`]) of a load to
+    /// `destination`.
+    pub fn content(destination: Destination) -> Self {
+        match destination {
+            Destination::Image => BlockKind::Image,
+            Destination::Font => BlockKind::Font,
+            _ => BlockKind::Media,
+        }
+    }
+
+    /// The setting behind a block that isn't a filter rule.
+    pub fn setting(self) -> &'static str {
+        match self {
+            BlockKind::Ad | BlockKind::Tracker => "filter list",
+            BlockKind::Image => "block_images",
+            BlockKind::Media => "block_media",
+            BlockKind::Font => "block_fonts",
+            BlockKind::ImageCap => "max_images_per_page",
+        }
+    }
+
+    fn index(self) -> usize {
+        BlockKind::ALL.iter().position(|k| *k == self).unwrap()
+    }
+}
+
+/// One blocked load.
+#[derive(Clone)]
+pub struct Blocked {
+    pub url: String,
+    pub kind: BlockKind,
+    /// The filter rule that matched (ads and trackers); the setting otherwise.
+    pub rule: String,
+}
+
+#[derive(Clone, Default)]
+pub struct BlockLog {
+    counts: [usize; BlockKind::ALL.len()],
+    /// Oldest first.
+    entries: VecDeque<Blocked>,
+}
+
+impl BlockLog {
+    pub fn record(&mut self, url: &str, kind: BlockKind, rule: String) {
+        self.counts[kind.index()] += 1;
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(Blocked {
+            url: url.to_string(),
+            kind,
+            rule,
+        });
+    }
+
+    /// Forget the page (a new top-level navigation).
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Loads blocked on the page, of every kind.
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    pub fn count(&self, kind: BlockKind) -> usize {
+        self.counts[kind.index()]
+    }
+
+    /// The kept loads, newest first.
+    pub fn entries(&self) -> impl Iterator<Item = &Blocked> {
+        self.entries.iter().rev()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts keep going past the kept entries, which drop the oldest.
+    #[test]
+    fn counts_outlast_the_entries() {
+        let mut log = BlockLog::default();
+        for i in 0..MAX_ENTRIES + 2 {
+            log.record(
+                &format!("https://ads.example/{i}"),
+                BlockKind::Ad,
+                "||ads.example^".into(),
+            );
+        }
+        log.record(
+            "https://t.example/p",
+            BlockKind::Tracker,
+            "||t.example^".into(),
+        );
+        assert_eq!(log.total(), MAX_ENTRIES + 3);
+        assert_eq!(log.count(BlockKind::Tracker), 1);
+        assert_eq!(log.entries().count(), MAX_ENTRIES);
+        assert_eq!(log.entries().next().unwrap().kind, BlockKind::Tracker);
+        log.clear();
+        assert_eq!(log.total(), 0);
+    }
+}
@@ -0,0 +1,106 @@
+//! Certificate errors. Servo answers a page whose TLS certificate doesn't
+//! verify with its own error page (`badcert.html`), whose "allow" button has
+//! its network stack accept that certificate until quit. Once such a page has
+//! loaded it is recognised here and handed to the app, which explains the
+//! failure in its own modal (an [`crate::overlay::prompt::AppDialog`]) and
+//! either goes back or proceeds — pressing the page's allow button and
+//! keeping the host as an exception, so a later error there proceeds without
+//! asking. Exceptions last the session, like Servo's.
+//!
+//! The page is recognised by the marker Servo's copy of it carries (see
+//! [`super::error_page`]), so a site's lookalike is neither reported nor
+//! clicked through.
+
+use super::error_page::{self, ErrorPage};
+use super::{AppBrowser, AppBrowserInner};
+use crate::event::user::UserEvent;
+use ::url::Url;
+use servo::WebView;
+
+/// Accept the certificate; the error page then reloads the site.
+const PROCEED_JS: &str = "document.getElementById('allow').click()";
+
+/// A certificate error page waiting for the user's answer.
+pub struct CertError {
+    pub webview: servo::WebViewId,
+    pub host: String,
+    /// Servo's reason for rejecting the certificate.
+    pub reason: String,
+}
+
+impl AppBrowserInner {
+    /// Check the HTTPS page that `webview` finished loading at `page_url` for
+    /// the certificate error page: queue it for the app, or proceed at once
+    /// for an excepted host.
+    pub(super) fn check_cert_error(&self, webview: &WebView, page_url: &str) {
+        let Some(host) = Url::parse(page_url)
+            .ok()
+            .filter(|url| url.scheme() == "https")
+            .and_then(|url| url.host_str().map(str::to_string))
+        else {
+            return;
+        };
+        let excepted = self.cert_exceptions.borrow().contains(&host);
+        let errors = self.cert_errors.clone();
+        let sender = self.event_sender.clone();
+        let target = webview.clone();
+        error_page::detect(webview, move |page, reason| {
+            if page != ErrorPage::BadCert {
+                return;
+            }
+            log::warn!("certificate error on {host}: {reason}");
+            if excepted {
+                target.evaluate_javascript(PROCEED_JS, |_| {});
+                return;
+            }
+            errors.borrow_mut().push(CertError {
+                webview: target.id(),
+                host,
+                reason,
+            });
+            // Wake the main loop so the question shows even when idle.
+            sender.send(UserEvent::ControlPending);
+        });
+    }
+}
+
+impl AppBrowser {
+    /// Take the certificate errors found since the last call, for the app to
+    /// ask about. Drained once per frame.
+    #[inline]
+    pub fn take_cert_errors(&self) -> Vec<CertError> {
+        std::mem::take(&mut self.inner.cert_errors.borrow_mut())
+    }
+
+    /// The user's answer to a certificate error: proceed (and keep `host` as
+    /// an exception for the session), or go back from the error page — to
+    /// `home` when it is the tab's first page.
+    pub fn answer_cert_error(
+        &self,
+        webview: servo::WebViewId,
+        host: &str,
+        proceed: bool,
+        home: &str,
+    ) {
+        let Some(i) = self.inner.tab_index(webview) else {
+            return;
+        };
+        let webview = self.inner.tabs.borrow()[i].webview.clone();
+        if proceed {
+            log::info!("certificate exception for {host} (this session)");
+            self.inner
+                .cert_exceptions
+                .borrow_mut()
+                .insert(host.to_string());
+            webview.evaluate_javascript(PROCEED_JS, |_| {});
+        } else if webview.can_go_back() {
+            webview.go_back(1);
+        } else {
+            let home = Url::parse(home).or_else(|_| Url::parse(super::HOME_URL));
+            if let Ok(home) = home {
+                self.inner.tabs.borrow_mut()[i].state.loading = true;
+                webview.load(home);
+            }
+        }
+    }
+}
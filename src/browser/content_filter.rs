@@ -5,9 +5,11 @@
 //! each load's [`Destination`], so the unwanted ones get the same empty-200
 //! interception the ad blocker uses and fail soft. Driven by the `block_*`
 //! fields of [`DataSavingConfig`]; the running app re-reads them on every
-//! settings save, so it toggles live.
+//! settings save, so it toggles live. A site's overrides (see
+//! [`crate::data::site_settings`]) win over them on its pages.
 
 use crate::config::DataSavingConfig;
+use crate::data::site_settings::Site;
 use content_security_policy::Destination;
 
 /// Which content categories to block. A `Copy` snapshot of the config's
@@ -31,6 +33,15 @@ impl ContentFilter {
         }
     }
 
+    /// This filter with a site's own `block_*` choices (see
+    /// [`crate::data::site_settings`]) in place of the global ones.
+    pub fn for_site(mut self, site: &Site) -> Self {
+        self.images = site.block_images.unwrap_or(self.images);
+        self.media = site.block_media.unwrap_or(self.media);
+        self.fonts = site.block_fonts.unwrap_or(self.fonts);
+        self
+    }
+
     /// Per-page cap on distinct images, `None` when unlimited. Counted per image,
     /// not per load: a spacer gif reused thirty times must not eat thirty slots.
     /// The allowed set lives on the tab, cleared on each of its navigations.
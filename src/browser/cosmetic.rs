@@ -0,0 +1,118 @@
+//! Cosmetic filtering: the ad blocker's element-hiding (`##`) rules, which the
+//! network hook in [`super::delegate`] can't act on. Once a page has loaded, a
+//! `<style>` of its own gets:
+//!
+//! - its site's hide rules and the generic ones not keyed on a class or id,
+//!   less its `#@#` exceptions;
+//! - the generic class / id rules matching what a script finds in the document
+//!   (skipped on `$generichide` sites). Elements a page adds later don't get
+//!   these.
+//!
+//! Per document, not a sheet on the `UserContentManager`: that one is shared by
+//! every tab, so a site's rules there would land on whichever pages load next.
+//! The cost is that hidden elements show until the load completes. The rules
+//! come from [`super::adblock::Adblock`]; an allowlisted site gets none.
+
+use super::find::js_string;
+use super::AppBrowserInner;
+use servo::WebView;
+
+/// Return the document's distinct class (`.name`) and id (`#name`) names, up
+/// to a cap so a huge page can't stall the script thread.
+const COLLECT_JS: &str = r#"
+(function () {
+    const seen = new Set();
+    for (const el of document.querySelectorAll('[class], [id]')) {
+        if (el.id) seen.add('#' + el.id);
+        for (const c of el.classList) seen.add('.' + c);
+        if (seen.size >= 5000) break;
+    }
+    return Array.from(seen);
+})()
+"#;
+
+/// Put `CSS` (a JS string) in the page's own cosmetic `<style>`.
+const INJECT_JS: &str = r#"
+(function (css) {
+    let style = document.getElementById('retsurf-cosmetic');
+    if (!style) {
+        style = document.createElement('style');
+        style.id = 'retsurf-cosmetic';
+        document.documentElement.appendChild(style);
+    }
+    style.textContent = css;
+})(CSS)
+"#;
+
+/// A rule per selector: one the style engine rejects (an unsupported
+/// pseudo-class) then drops alone instead of the whole list.
+fn css(selectors: &[String]) -> String {
+    let mut css = String::new();
+    for selector in selectors {
+        css.push_str(selector);
+        css.push_str(" { display: none !important; }\n");
+    }
+    css
+}
+
+impl AppBrowserInner {
+    /// Hide the loaded page's elements (see the module docs), for the page at
+    /// `page_url` in `webview`.
+    pub(super) fn hide_elements(&self, webview: &WebView, page_url: &str) {
+        if !self.adblock.cosmetic() {
+            return;
+        }
+        let mut selectors = self.adblock.hide_selectors(page_url);
+        let adblock = self.adblock.clone();
+        let page_url = page_url.to_string();
+        let target = webview.clone();
+        webview.evaluate_javascript(COLLECT_JS, move |result| {
+            // Without the names the site's rules still apply.
+            let names = match result {
+                Ok(servo::JSValue::Array(values)) => values,
+                Ok(other) => {
+                    log::warn!("cosmetic filter: unexpected names: {other:?}");
+                    Vec::new()
+                }
+                Err(e) => {
+                    log::warn!("cosmetic filter: name collection failed: {e:?}");
+                    Vec::new()
+                }
+            };
+            let (mut classes, mut ids) = (Vec::new(), Vec::new());
+            for name in names {
+                let servo::JSValue::String(name) = name else {
+                    continue;
+                };
+                if let Some(class) = name.strip_prefix('.') {
+                    classes.push(class.to_string());
+                } else if let Some(id) = name.strip_prefix('#') {
+                    ids.push(id.to_string());
+                }
+            }
+            selectors.extend(adblock.generic_selectors(&page_url, &classes, &ids));
+            if selectors.is_empty() {
+                return;
+            }
+            let js = INJECT_JS.replace("CSS", &js_string(&css(&selectors)));
+            target.evaluate_javascript(js, |_| {});
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Each selector gets a rule of its own.
+    #[test]
+    fn a_rule_per_selector() {
+        let selectors = [".ad".to_string(), "#banner > div".to_string()];
+        assert_eq!(
+            css(&selectors),
+            ".ad { display: none !important; }\n\
+             #banner > div { display: none !important; }\n"
+        );
+        assert!(css(&[]).is_empty());
+    }
+}
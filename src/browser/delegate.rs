@@ -1,13 +1,20 @@
 //! Every reaction to Servo lives here: the [`servo::WebViewDelegate`] impl on
-//! [`AppBrowserInner`] — frame/URL/load-status notifications, the
-//! download-navigation interception (see [`crate::data::downloads`]), and the
-//! ad-block hook over every resource load (see [`crate::browser::adblock`]). New
-//! delegate hooks (favicons, dialogs, notifications, …) belong in this file.
+//! [`AppBrowserInner`] — frame/URL/load-status/title/favicon notifications, the
+//! download-navigation interception (see [`crate::data::downloads`]), the
+//! ad-block hook over every resource load (see [`crate::browser::adblock`]) and
+//! its element hiding once a page loads (see [`super::cosmetic`]), and the
+//! per-site overrides applied as pages load (see [`crate::data::site_settings`]).
+//! New delegate hooks (favicons, dialogs, notifications, …) belong in this file.
 
+use super::block_log::BlockKind;
+use super::net_log::{self, Outcome};
 use super::{AppBrowserInner, BrowserState, Tab};
+use crate::data::favicons::Icon;
+use crate::data::site_settings::SiteSettings;
 use crate::event::user::UserEvent;
 use content_security_policy::Destination;
 use servo::WebView;
+use std::borrow::Cow;
 use std::cell::RefCell;
 use url::Url;
 
@@ -40,19 +47,79 @@ impl servo::WebViewDelegate for AppBrowserInner {
 
     fn notify_url_changed(&self, webview: WebView, url: Url) {
         // Update whichever tab navigated (so its address bar is right once shown);
-        // only log to history when it's the tab the user is actually viewing.
+        // only log to history when it's the tab the user is actually viewing. A
+        // no-history tab logs nothing.
         if let Some(i) = self.tab_index(webview.id()) {
-            let url = url.to_string();
             let mut tabs = self.tabs.borrow_mut();
+            // Arriving on another site starts at its zoom; within one the tab
+            // keeps whatever the user zoomed to.
+            if SiteSettings::host_key(&tabs[i].state.page_url)
+                != SiteSettings::host_key(url.as_str())
+            {
+                tabs[i].webview.set_page_zoom(self.site_zoom(url.as_str()));
+            }
+            tabs[i].game_mode = super::game_mode::after_navigation(
+                tabs[i].game_mode,
+                &tabs[i].state.page_url,
+                &url,
+                &self.game_sites.borrow(),
+            );
+            let url = url.to_string();
             tabs[i].state.location = url.clone();
             tabs[i].state.page_url = url.clone();
+            tabs[i].saved_title = None;
+            let no_history = tabs[i].no_history;
             drop(tabs);
-            if i == self.active.get() {
+            if !no_history && i == self.active.get() {
                 self.visited.borrow_mut().push(url);
             }
         }
     }
 
+    /// Queue the title for the stores (history, bookmarks, dial). Keyed by the
+    /// tab's page URL at report time, so a title can't land on the next page.
+    /// No-history tabs keep theirs to themselves.
+    fn notify_page_title_changed(&self, webview: WebView, title: Option<String>) {
+        let title = title.unwrap_or_default();
+        let title = title.trim();
+        if title.is_empty() {
+            return;
+        }
+        if let Some(i) = self.tab_index(webview.id()) {
+            let (url, no_history) = {
+                let tab = &self.tabs.borrow()[i];
+                (tab.state.page_url.clone(), tab.no_history)
+            };
+            if !url.is_empty() && !no_history {
+                self.titles.borrow_mut().push((url, title.to_string()));
+                self.event_sender.send(UserEvent::BrowserWakeup);
+            }
+        }
+    }
+
+    /// Copy the page's decoded icon out for the favicon cache (see
+    /// [`crate::data::favicons`]); the main loop shrinks and writes it. Not
+    /// for no-history tabs: the cache is on disk.
+    fn notify_favicon_changed(&self, webview: WebView) {
+        let Some(i) = self.tab_index(webview.id()) else {
+            return;
+        };
+        let (url, no_history) = {
+            let tab = &self.tabs.borrow()[i];
+            (tab.state.page_url.clone(), tab.no_history)
+        };
+        if no_history {
+            return;
+        }
+        let Some(icon) = webview.favicon().and_then(|image| favicon_rgba(&image)) else {
+            return;
+        };
+        if !url.is_empty() {
+            self.favicons.borrow_mut().push((url, icon));
+            self.event_sender.send(UserEvent::BrowserWakeup);
+        }
+    }
+
     /// `HeadParsed` is dropped: Servo sends it from `HTMLBodyElement::bind_to_tree`
     /// (measured on 0.5), so a script inserting a `<body>` after the load emits one
     /// with no `Complete` to follow — that left the tab busy forever on wikipedia.
@@ -62,16 +129,39 @@ impl servo::WebViewDelegate for AppBrowserInner {
         let loading = match status {
             servo::LoadStatus::Started => true,
             servo::LoadStatus::Complete => false,
-            servo::LoadStatus::HeadParsed => return,
+            servo::LoadStatus::HeadParsed => {
+                // Invert as early as there is a document (see `super::forced_dark`).
+                if let Some(i) = self.tab_index(webview.id()) {
+                    let page_url = self.tabs.borrow()[i].state.page_url.clone();
+                    self.apply_forced_dark(&webview, &page_url);
+                }
+                return;
+            }
         };
         if let Some(i) = self.tab_index(webview.id()) {
             self.tabs.borrow_mut()[i].state.loading = loading;
+            // The loaded page's element hiding (see `super::cosmetic`).
+            let page_url = self.tabs.borrow()[i].state.page_url.clone();
+            let (_, exempt) = self.filters_for(&page_url);
+            if !loading && !exempt && page_url != super::HOME_URL {
+                self.hide_elements(&webview, &page_url);
+            }
+            // Servo's certificate and network error pages (see
+            // `super::cert_error` and `super::https_only`).
+            if !loading {
+                self.apply_forced_dark(&webview, &page_url);
+                self.check_cert_error(&webview, &page_url);
+                let upgrade = self.tabs.borrow()[i].https_upgrade.take();
+                if let Some((from, _)) = upgrade {
+                    self.check_upgrade(&webview, from);
+                }
+            }
         }
     }
 
     /// Servo can't download: navigating to a file URL would just fail to render.
     /// Deny those navigations and queue the URL for our own fetch instead (see
-    /// [`crate::data::downloads`]). Everything else proceeds normally.
+    /// [`crate::data::downloads`]). Everything else proceeds.
     fn request_navigation(&self, webview: WebView, request: servo::NavigationRequest) {
         if !self.is_download_url(&request.url) {
             request.allow();
@@ -139,6 +229,11 @@ impl servo::WebViewDelegate for AppBrowserInner {
             webview.set_page_zoom(self.default_zoom);
         }
 
+        // A popup from a no-history tab keeps no history either.
+        let no_history = self
+            .tab_index(parent_webview.id())
+            .is_some_and(|i| self.tabs.borrow()[i].no_history);
+
         // Only one tab may be shown (all share one rendering context), so hide
         // the current one before showing the new tab — matching `open_tab`.
         if let Some(cur) = self.active_webview() {
@@ -152,6 +247,12 @@ impl servo::WebViewDelegate for AppBrowserInner {
             webview,
             state: BrowserState::default(),
             page_images: RefCell::default(),
+            blocked: RefCell::default(),
+            requests: RefCell::default(),
+            https_upgrade: RefCell::default(),
+            saved_title: None,
+            game_mode: false,
+            no_history,
         });
         self.active.set(tabs.len() - 1);
         drop(tabs);
@@ -164,7 +265,15 @@ impl servo::WebViewDelegate for AppBrowserInner {
     /// [`super::home`]); otherwise loads run through the ad blocker, where a
     /// blocked load gets an empty 200 response so scripts/images fail soft
     /// instead of raising network errors. Everything else proceeds untouched
-    /// (dropping the load means "do not intercept").
+    /// (dropping the load means "do not intercept"). Subresources are
+    /// filtered with the overrides of the page they belong to, and each block
+    /// is logged on the tab (see [`super::block_log`]). In HTTPS-only mode an
+    /// `http://` load is redirected to `https://` before any of that (see
+    /// [`super::https_only`]), and a top-level `GET` carrying tracking
+    /// parameters is redirected to its cleaned URL (see
+    /// [`crate::data::clean_url`]); a redirect keeps the Referer, and only
+    /// `GET`s are touched, so no form body is lost. Each load and what became
+    /// of it goes in the tab's request log (see [`super::net_log`]).
     fn load_web_resource(&self, webview: WebView, load: servo::WebResourceLoad) {
         let req = load.request();
         let url = req.url.clone();
@@ -178,15 +287,71 @@ impl servo::WebViewDelegate for AppBrowserInner {
             finish_intercepted(load, servo::WebResourceResponse::new(url), Vec::new());
             return;
         }
+        let logged = net_log::Request::new(req);
+
+        // HTTPS-only mode: send an `http://` load to its `https://` twin (see
+        // `super::https_only`), remembering a page's original to offer it back
+        // if the upgrade fails.
+        if let Some(upgraded) = self.https_upgrade(&url) {
+            // The twin redirected back here: upgrading again would loop, so
+            // the upgrade has failed. Stop on a blank page and ask at once.
+            if req.is_for_main_frame && req.is_redirect && self.upgrade_looped(&webview, &upgraded)
+            {
+                let reason = "https-only: redirected back to http".to_string();
+                self.log_request(&webview, logged, Outcome::Blocked(reason));
+                finish_intercepted(load, servo::WebResourceResponse::new(url), Vec::new());
+                return;
+            }
+            log::debug!("https-only: {url} -> {upgraded}");
+            if req.is_for_main_frame {
+                if let Some(i) = self.tab_index(webview.id()) {
+                    *self.tabs.borrow()[i].https_upgrade.borrow_mut() =
+                        Some((url.clone(), upgraded.clone()));
+                }
+            }
+            let response = redirect_response(url, &upgraded);
+            self.log_request(&webview, logged, Outcome::Redirected(upgraded.into()));
+            finish_intercepted(load, response, Vec::new());
+            return;
+        }
+
+        // Tracking parameters off a page load: redirect to the cleaned URL.
+        let cleaned = match crate::data::clean_url::clean(url.as_str()) {
+            Cow::Owned(cleaned) if req.is_for_main_frame && req.method == http::Method::GET => {
+                Url::parse(&cleaned).ok()
+            }
+            _ => None,
+        };
+        if let Some(cleaned) = cleaned {
+            log::debug!("url cleaning: {url} -> {cleaned}");
+            let response = redirect_response(url, &cleaned);
+            self.log_request(&webview, logged, Outcome::Redirected(cleaned.into()));
+            finish_intercepted(load, response, Vec::new());
+            return;
+        }
 
         let is_home = req.is_for_main_frame && super::home::is_home(&url);
 
-        let filter = self.content_filter.get();
         let is_subresource = !is_home && !req.is_for_main_frame;
+        let (filter, adblock_exempt) = match self.tab_index(webview.id()) {
+            Some(i) if is_subresource => self.filters_for(&self.tabs.borrow()[i].state.page_url),
+            _ => (self.content_filter.get(), false),
+        };
         // Block ads and any lightweight-mode content categories (images / media
         // / fonts). Never the main document itself — only its subresources.
-        let mut block =
-            is_subresource && (self.adblock.should_block(req) || filter.blocks(req.destination));
+        let mut block = None;
+        if is_subresource {
+            if !adblock_exempt {
+                block = self
+                    .adblock
+                    .blocking_rule(req)
+                    .map(|rule| (BlockKind::filtered(req.destination), rule));
+            }
+            if block.is_none() && filter.blocks(req.destination) {
+                let kind = BlockKind::content(req.destination);
+                block = Some((kind, kind.setting().to_string()));
+            }
+        }
 
         // Per-page image cap: soft-block images past the limit so a huge grid
         // doesn't freeze the device (Servo loads them all eagerly, no lazy-load).
@@ -197,14 +362,21 @@ impl servo::WebViewDelegate for AppBrowserInner {
             let images = &tabs[i].page_images;
             if req.is_for_main_frame {
                 images.borrow_mut().clear();
-            } else if is_subresource && !block && req.destination == Destination::Image {
+                tabs[i].blocked.borrow_mut().clear();
+                // Another page than the upgraded one's twin: not its load.
+                let mut upgrade = tabs[i].https_upgrade.borrow_mut();
+                if upgrade.as_ref().is_some_and(|(_, to)| *to != url) {
+                    *upgrade = None;
+                }
+            } else if is_subresource && block.is_none() && req.destination == Destination::Image {
                 if let Some(cap) = filter.image_cap() {
                     let mut images = images.borrow_mut();
                     let key = image_key(&url);
                     if !images.contains(&key) {
                         if images.len() >= cap {
                             log::debug!("image cap: blocked {url}");
-                            block = true;
+                            let kind = BlockKind::ImageCap;
+                            block = Some((kind, kind.setting().to_string()));
                         } else {
                             images.insert(key);
                         }
@@ -221,11 +393,22 @@ impl servo::WebViewDelegate for AppBrowserInner {
                 http::HeaderValue::from_static("text/html; charset=utf-8"),
             );
             let response = servo::WebResourceResponse::new(url).headers(headers);
+            self.log_request(&webview, logged, Outcome::Answered(200));
             finish_intercepted(load, response, html);
-        } else if block {
-            log::debug!("adblock: blocked {url}");
+        } else if let Some((kind, rule)) = block {
+            log::debug!("blocked {url} ({rule})");
+            let reason = format!("{}: {rule}", kind.label());
+            if let Some(i) = self.tab_index(webview.id()) {
+                self.tabs.borrow()[i]
+                    .blocked
+                    .borrow_mut()
+                    .record(url.as_str(), kind, rule);
+            }
+            self.log_request(&webview, logged, Outcome::Blocked(reason));
             let response = servo::WebResourceResponse::new(url);
             finish_intercepted(load, response, Vec::new());
+        } else {
+            self.log_request(&webview, logged, Outcome::Passed);
         }
     }
 }
@@ -239,6 +422,35 @@ fn image_key(url: &Url) -> u64 {
     hasher.finish()
 }
 
+/// A favicon as straight RGBA8, whatever layout Servo decoded it to. `None` for
+/// an empty image or one whose buffer doesn't match its size.
+fn favicon_rgba(image: &servo::Image) -> Option<Icon> {
+    let (width, height) = (image.width, image.height);
+    let pixels = width as usize * height as usize;
+    let data = image.data();
+    let rgba: Vec<u8> = match image.format {
+        servo::PixelFormat::RGBA8 => data.to_vec(),
+        servo::PixelFormat::BGRA8 => data
+            .chunks_exact(4)
+            .flat_map(|p| [p[2], p[1], p[0], p[3]])
+            .collect(),
+        servo::PixelFormat::RGB8 => data
+            .chunks_exact(3)
+            .flat_map(|p| [p[0], p[1], p[2], 255])
+            .collect(),
+        servo::PixelFormat::KA8 => data
+            .chunks_exact(2)
+            .flat_map(|p| [p[0], p[0], p[0], p[1]])
+            .collect(),
+        servo::PixelFormat::K8 => data.iter().flat_map(|&k| [k, k, k, 255]).collect(),
+    };
+    (pixels != 0 && rgba.len() == pixels * 4).then_some(Icon {
+        width,
+        height,
+        rgba,
+    })
+}
+
 /// The linking page as a Referer: http(s) only, fragment and credentials stripped.
 pub(super) fn referer_for(location: &str) -> Option<String> {
     let mut url = Url::parse(location).ok()?;
@@ -251,6 +463,17 @@ pub(super) fn referer_for(location: &str) -> Option<String> {
     Some(url.to_string())
 }
 
+/// A `307` sending the load of `url` on to `to`, method and body kept.
+fn redirect_response(url: Url, to: &Url) -> servo::WebResourceResponse {
+    let mut headers = http::HeaderMap::new();
+    if let Ok(location) = http::HeaderValue::from_str(to.as_str()) {
+        headers.insert(http::header::LOCATION, location);
+    }
+    servo::WebResourceResponse::new(url)
+        .status_code(http::StatusCode::TEMPORARY_REDIRECT)
+        .headers(headers)
+}
+
 /// Answer an intercepted load with `body`, always sending a chunk — even an empty
 /// one. Servo's request interceptor only marks the response body `Done` once at
 /// least one chunk arrived, and net's subresource-integrity check panics on a body
@@ -13,6 +13,8 @@ use crate::{
 /// then load cookies / HSTS / localStorage from it at startup and write them
 /// back on a clean shutdown (see [`AppBrowser::shutdown`]), so logins survive
 /// restarts. The subfolder keeps Servo's files apart from retsurf's own.
+/// `certificate_path` names the roots bundle when `[network] extra_ca_certs`
+/// adds CAs (see `net::tls`).
 ///
 /// [`AppBrowser::shutdown`]: super::AppBrowser::shutdown
 pub(super) fn build_opts(config: &BrowserConfig) -> servo::Opts {
@@ -20,6 +22,7 @@ pub(super) fn build_opts(config: &BrowserConfig) -> servo::Opts {
     if config.persist_site_data {
         opts.config_dir = Some(std::path::PathBuf::from(crate::config::servo_data_dir()));
     }
+    opts.certificate_path = crate::net::tls::bundle_path().map(str::to_string);
     opts
 }
 
@@ -73,6 +76,18 @@ pub(super) fn build_preferences(
         apply_http_disk_cache(&mut prefs, perf.http_disk_cache_mb, &config::cache_dir());
     }
 
+    // Game mode forwards the controller as Gamepad API events; without the
+    // pref `navigator.getGamepads()` isn't there to read them.
+    prefs.dom_gamepad_enabled = true;
+
+    // The `[network]` proxy for page loads (see `net::proxy`); a SOCKS one or
+    // DoH is already behind its local HTTP bridge by now.
+    if let Some(proxy) = crate::net::proxy::current() {
+        prefs.network_http_proxy_uri = proxy.http_uri().to_string();
+        prefs.network_https_proxy_uri = proxy.http_uri().to_string();
+        prefs.network_http_no_proxy = proxy.no_proxy();
+    }
+
     if let Some(ua) = resolve_user_agent(&config.user_agent) {
         log::info!("user agent: {ua}");
         prefs.user_agent = ua;
@@ -0,0 +1,120 @@
+//! Servo's own error pages — `badcert.html` for a certificate it rejects and
+//! `neterror.html` for a load that failed — told apart from pages made to look
+//! like them. Servo fills them in from the resources we embed (see
+//! `platform::resources`), so as each is read it gets a `<meta>` naming it and
+//! carrying a token made up per process. A site can copy the text and the
+//! buttons, but not the token. [`super::cert_error`] and [`super::https_only`]
+//! check for it once a page has loaded.
+
+use servo::WebView;
+use std::sync::OnceLock;
+
+/// The marker's `name`; its `content` is `<file>:<token>`.
+const MARKER: &str = "retsurf-error";
+
+/// The page's marker and its first paragraph (Servo's reason line), or `null`
+/// on a page without one.
+const DETECT_JS: &str = r#"
+(function () {
+    const marker = document.querySelector('meta[name="retsurf-error"]');
+    if (!marker) return null;
+    const reason = document.querySelector('p');
+    return [marker.content, reason ? reason.textContent.trim() : ''];
+})()
+"#;
+
+/// Which of Servo's error pages.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum ErrorPage {
+    BadCert,
+    NetError,
+}
+
+impl ErrorPage {
+    fn file(self) -> &'static str {
+        match self {
+            ErrorPage::BadCert => "badcert.html",
+            ErrorPage::NetError => "neterror.html",
+        }
+    }
+
+    fn from_file(name: &str) -> Option<Self> {
+        [ErrorPage::BadCert, ErrorPage::NetError]
+            .into_iter()
+            .find(|page| page.file() == name)
+    }
+}
+
+fn token() -> &'static str {
+    static TOKEN: OnceLock<String> = OnceLock::new();
+    TOKEN.get_or_init(crate::net::secret)
+}
+
+/// The Servo resource `name` as it is handed over: an error page gets its
+/// marker at the top of its `<head>`, anything else is left as it is.
+pub fn mark_error_page(name: &str, html: Vec<u8>) -> Vec<u8> {
+    let Some(page) = ErrorPage::from_file(name) else {
+        return html;
+    };
+    let marker = format!(
+        "<meta name=\"{MARKER}\" content=\"{}:{}\">",
+        page.file(),
+        token()
+    );
+    let at = html
+        .windows(6)
+        .position(|w| w.eq_ignore_ascii_case(b"<head>"))
+        .map_or(0, |i| i + 6);
+    let mut out = html;
+    out.splice(at..at, marker.into_bytes());
+    out
+}
+
+/// The error page a marker's `content` names, if its token is this process's.
+fn parse_marker(content: &str) -> Option<ErrorPage> {
+    let (file, marker_token) = content.split_once(':')?;
+    (marker_token == token())
+        .then(|| ErrorPage::from_file(file))
+        .flatten()
+}
+
+/// Check the page `webview` has loaded for one of Servo's error pages, and
+/// hand `found` which one it is and its reason line.
+pub(super) fn detect(webview: &WebView, found: impl FnOnce(ErrorPage, String) + 'static) {
+    webview.evaluate_javascript(DETECT_JS, move |result| {
+        let Ok(servo::JSValue::Array(values)) = result else {
+            return;
+        };
+        let [servo::JSValue::String(content), servo::JSValue::String(reason)] = &values[..] else {
+            return;
+        };
+        match parse_marker(content) {
+            Some(page) => found(page, reason.clone()),
+            None => log::warn!("error page: a page carries a marker without the token"),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The error pages get a marker with this process's token, which reads
+    /// back as the page; other resources and forged tokens don't.
+    #[test]
+    fn marks_servos_error_pages() {
+        let html = b"<html>\n<head>\n<title>Certificate error</title>".to_vec();
+        let marked = String::from_utf8(mark_error_page("badcert.html", html)).unwrap();
+        let content = format!("badcert.html:{}", token());
+        assert!(marked.starts_with(&format!(
+            "<html>\n<head><meta name=\"retsurf-error\" content=\"{content}\">\n<title>"
+        )));
+        assert_eq!(parse_marker(&content), Some(ErrorPage::BadCert));
+        assert_eq!(
+            parse_marker(&format!("neterror.html:{}", token())),
+            Some(ErrorPage::NetError)
+        );
+        assert_eq!(parse_marker("badcert.html:0123"), None);
+        assert_eq!(mark_error_page("crash.html", b"<head>".to_vec()), b"<head>");
+    }
+}
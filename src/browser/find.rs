@@ -0,0 +1,166 @@
+//! Find-in-page in the document itself: a script wraps every case-insensitive
+//! match of the query in a `<mark>`, tints the current one, and scrolls it into
+//! view. Its state rides on `window`, so a step re-uses the marks — and a page
+//! that navigated or re-rendered them away is simply searched again. The answer
+//! comes back through `evaluate_javascript` like the link-hint collection.
+
+use super::{AppBrowser, UserEvent};
+
+/// Search for `QUERY` (a JS string, or `null` to clear) and move `STEP` matches
+/// from the current one, returning `[current, total]` (current is 1-based, 0 with
+/// no matches). Text in scripts, styles, form fields and hidden elements is
+/// skipped, and the marks are capped so a one-letter query can't bury the page.
+/// Offsets come from the lowercased text, so the rare letter whose lowercase
+/// form changes length (`İ`) can shift a mark within its node.
+const FIND_JS: &str = r#"
+(function (query, step) {
+    const CLS = 'retsurf-find', CUR = 'retsurf-find-current';
+    let st = window.__retsurfFind;
+    const clear = () => {
+        if (!st) return;
+        for (const m of st.marks) {
+            const p = m.parentNode;
+            if (!p) continue;
+            p.replaceChild(document.createTextNode(m.textContent), m);
+            p.normalize();
+        }
+        if (st.style) st.style.remove();
+        window.__retsurfFind = st = null;
+    };
+    if (query === null) { clear(); return [0, 0]; }
+    if (!st || st.query !== query || !st.marks.every(m => m.isConnected)) {
+        clear();
+        st = window.__retsurfFind = { query, marks: [], index: 0, style: null };
+        step = 0;
+        const needle = query.toLowerCase();
+        const root = document.body || document.documentElement;
+        if (!needle || !root) return [0, 0];
+        st.style = document.createElement('style');
+        st.style.textContent =
+            'mark.' + CLS + '{background:#ffe066;color:#000}' +
+            'mark.' + CUR + '{background:#3fb8a0;color:#000}';
+        document.documentElement.appendChild(st.style);
+        const walker = document.createTreeWalker(root, NodeFilter.SHOW_TEXT, {
+            acceptNode(n) {
+                const p = n.parentElement;
+                if (!p || /^(SCRIPT|STYLE|NOSCRIPT|TEXTAREA|TITLE)$/.test(p.tagName)) {
+                    return NodeFilter.FILTER_SKIP;
+                }
+                if (!n.data.toLowerCase().includes(needle)) return NodeFilter.FILTER_SKIP;
+                return p.getClientRects().length ? NodeFilter.FILTER_ACCEPT : NodeFilter.FILTER_SKIP;
+            }
+        });
+        const nodes = [];
+        while (walker.nextNode()) nodes.push(walker.currentNode);
+        for (let node of nodes) {
+            let at;
+            while (st.marks.length < 1000 && (at = node.data.toLowerCase().indexOf(needle)) >= 0) {
+                const hit = node.splitText(at);
+                node = hit.splitText(needle.length);
+                const mark = document.createElement('mark');
+                mark.className = CLS;
+                hit.parentNode.replaceChild(mark, hit);
+                mark.appendChild(hit);
+                st.marks.push(mark);
+            }
+        }
+    }
+    const total = st.marks.length;
+    if (!total) return [0, 0];
+    st.marks[st.index].classList.remove(CUR);
+    st.index = ((st.index + step) % total + total) % total;
+    const cur = st.marks[st.index];
+    cur.classList.add(CUR);
+    cur.scrollIntoView({ block: 'center', inline: 'nearest' });
+    return [st.index + 1, total];
+})(QUERY, STEP)
+"#;
+
+impl AppBrowser {
+    /// Search the active page for `query`, or step `step` matches from the
+    /// current one when it's the query already marked. The answer lands in
+    /// `find_result` (drained via [`AppBrowser::take_find_result`]) and a
+    /// wake-up event is sent; an evaluation error reports no matches.
+    pub fn find(&self, query: &str, step: i32) {
+        let Some(webview) = self.inner.active_webview() else {
+            return;
+        };
+        // STEP first: the spliced-in query may itself contain the placeholder.
+        let js = FIND_JS
+            .replace("STEP", &step.to_string())
+            .replace("QUERY", &js_string(query));
+        let inner = self.inner.clone();
+        webview.evaluate_javascript(js, move |result| {
+            let answer = match result {
+                Ok(servo::JSValue::Array(values)) => match values.as_slice() {
+                    [servo::JSValue::Number(current), servo::JSValue::Number(total)] => {
+                        (*current as usize, *total as usize)
+                    }
+                    other => {
+                        log::warn!("find returned unexpected value: {other:?}");
+                        (0, 0)
+                    }
+                },
+                Ok(other) => {
+                    log::warn!("find returned unexpected value: {other:?}");
+                    (0, 0)
+                }
+                Err(e) => {
+                    log::warn!("find failed: {e:?}");
+                    (0, 0)
+                }
+            };
+            inner.find_result.set(Some(answer));
+            inner.event_sender.send(UserEvent::FindReady);
+        });
+    }
+
+    /// Remove the active page's find marks.
+    pub fn find_clear(&self) {
+        let Some(webview) = self.inner.active_webview() else {
+            return;
+        };
+        let js = FIND_JS.replace("QUERY", "null").replace("STEP", "0");
+        webview.evaluate_javascript(js, |_| {});
+    }
+
+    /// Take the page's answer to the last find, if it arrived since the previous
+    /// call. Drained once per frame by the main loop.
+    #[inline]
+    pub fn take_find_result(&self) -> Option<(usize, usize)> {
+        self.inner.find_result.take()
+    }
+}
+
+/// `s` as a double-quoted JS string literal, safe to splice into a script.
+pub(super) fn js_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            // Line terminators in older JS, and anything else unprintable.
+            '\u{2028}' | '\u{2029}' => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Quotes, backslashes and line breaks can't end the literal early.
+    #[test]
+    fn js_string_escapes() {
+        assert_eq!(js_string("plain"), "\"plain\"");
+        assert_eq!(js_string("a\"b\\c"), "\"a\\\"b\\\\c\"");
+        assert_eq!(js_string("x\ny\u{2028}"), "\"x\\ny\\u2028\"");
+    }
+}
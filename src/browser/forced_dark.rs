@@ -1,19 +1,47 @@
-//! Forced dark mode: a user-origin stylesheet that inverts the page, for sites
-//! that ship no dark theme (`[browser] page_theme = "forced-dark"`). Attached to
-//! the shared `UserContentManager` in [`super`], so it lands on the next load.
-//! Costs a full-page compositor pass per frame — wants an on-device frame-time
-//! check before being recommended on a handheld.
+//! Forced dark mode: a stylesheet that inverts the page, for sites that ship no
+//! dark theme — every site under `[browser] page_theme = "forced-dark"`, or one
+//! whose site settings turn it on or off (see [`crate::data::site_settings`]).
+//!
+//! Per document, like the cosmetic filter (see [`super::cosmetic`]), not a sheet
+//! on the `UserContentManager`: that one is shared by every tab, so a site's
+//! choice there would land on whichever pages load next. The `<style>` goes in
+//! once the body starts (Servo's `HeadParsed`) and again on `Complete`, so a
+//! page shows light until then. Costs a full-page compositor pass per frame —
+//! wants an on-device frame-time check before being recommended on a handheld.
 
-use servo::user_contents::UserStyleSheet;
-use std::rc::Rc;
+use super::find::js_string;
+use super::AppBrowserInner;
+use servo::WebView;
 
-/// Base URL for the sheet. Nothing is fetched; it only names the source.
-const SHEET_URL: &str = "retsurf://forced-dark.css";
+/// Put `CSS` (a JS string) in the page's own forced-dark `<style>`, once.
+const INJECT_JS: &str = r#"
+(function (css) {
+    if (document.getElementById('retsurf-forced-dark')) return;
+    const style = document.createElement('style');
+    style.id = 'retsurf-forced-dark';
+    style.textContent = css;
+    (document.documentElement || document).appendChild(style);
+})(CSS)
+"#;
 
-pub(super) fn stylesheet() -> Rc<UserStyleSheet> {
-    let url = ::url::Url::parse(SHEET_URL).expect("SHEET_URL is a valid literal URL");
-    Rc::new(UserStyleSheet::new(
-        include_str!("forced_dark.css").to_string(),
-        url,
-    ))
+impl AppBrowserInner {
+    /// Whether the page at `page_url` is inverted: its site's choice, else the
+    /// page theme's.
+    fn forced_dark_for(&self, page_url: &str) -> bool {
+        self.site_settings
+            .borrow()
+            .for_url(page_url)
+            .and_then(|site| site.forced_dark)
+            .unwrap_or_else(|| self.page_theme.get().is_forced_dark())
+    }
+
+    /// Invert the document in `webview`, showing the page at `page_url`, if it
+    /// should be. Safe to call more than once per document.
+    pub(super) fn apply_forced_dark(&self, webview: &WebView, page_url: &str) {
+        if !self.forced_dark_for(page_url) {
+            return;
+        }
+        let js = INJECT_JS.replace("CSS", &js_string(include_str!("forced_dark.css")));
+        webview.evaluate_javascript(js, |_| {});
+    }
 }
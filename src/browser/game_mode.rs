@@ -0,0 +1,60 @@
+//! Game mode: a tab that hands the controller to its page. While the active tab
+//! is in it, the event handler forwards SDL controller input to Servo as
+//! Gamepad API events (see [`crate::event::handler`]). A tab enters it by the
+//! `game_mode` action or by loading a host from `[input] game_sites`.
+
+use ::url::Url;
+
+/// A tab's game mode after it commits `to`, coming from `from` (its previous
+/// page URL, possibly empty). A move to another host re-decides it from the
+/// allowlist; a same-host navigation (including a history push) keeps whatever
+/// the user toggled.
+pub(super) fn after_navigation(current: bool, from: &str, to: &Url, sites: &[String]) -> bool {
+    let from_host = Url::parse(from)
+        .ok()
+        .and_then(|u| u.host_str().map(str::to_owned));
+    if from_host.as_deref() == to.host_str() {
+        return current;
+    }
+    to.host_str().is_some_and(|host| is_listed(host, sites))
+}
+
+/// Whether `host` is a listed site or a subdomain of one.
+fn is_listed(host: &str, sites: &[String]) -> bool {
+    let host = host.to_ascii_lowercase();
+    sites.iter().any(|site| {
+        let site = site.trim().trim_end_matches('/').to_ascii_lowercase();
+        !site.is_empty()
+            && (host == site
+                || host
+                    .strip_suffix(site.as_str())
+                    .is_some_and(|rest| rest.ends_with('.')))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Game mode after moving from `from` to `to` with `itch.io` listed.
+    fn after(current: bool, from: &str, to: &str) -> bool {
+        let sites = vec!["itch.io".to_string()];
+        after_navigation(current, from, &Url::parse(to).unwrap(), &sites)
+    }
+
+    /// A listed host and its subdomains switch it on; a look-alike doesn't.
+    #[test]
+    fn listed_hosts_and_subdomains() {
+        assert!(after(false, "", "https://itch.io/"));
+        assert!(after(false, "", "https://html.itch.io/game"));
+        assert!(!after(false, "", "https://notitch.io/"));
+    }
+
+    /// A manual toggle survives navigation within the site, not leaving it.
+    #[test]
+    fn same_host_keeps_the_toggle() {
+        let from = "https://example.com/a";
+        assert!(after(true, from, "https://example.com/b"));
+        assert!(!after(true, from, "https://example.org/"));
+    }
+}
@@ -0,0 +1,174 @@
+//! HTTPS-only mode (`[browser] https_only`): `http://` loads, top-level and
+//! subresource alike, are answered in the network hook in [`super::delegate`]
+//! with a redirect to their `https://` twin. Local addresses (loopback, LAN
+//! ranges, `.local` names) are left alone, as are hosts the user chose to
+//! reach over HTTP this session.
+//!
+//! A subresource whose upgrade fails just fails. A page does too, but once
+//! Servo's network error page has loaded over it (recognised by its marker,
+//! see [`super::error_page`]) the failure goes to the app, which offers to
+//! continue over HTTP for the host (an [`crate::overlay::prompt::AppDialog`]).
+//! So does a page whose `https://` twin redirects back to it, as sites without
+//! HTTPS often do: upgrading that redirect again would loop.
+
+use super::error_page::{self, ErrorPage};
+use super::{AppBrowser, AppBrowserInner};
+use crate::event::user::{UserEvent, UserEventSender};
+use ::url::{Host, Url};
+use servo::WebView;
+use std::cell::RefCell;
+
+/// A page whose upgrade to HTTPS failed, waiting for the user's answer.
+pub struct UpgradeFailed {
+    pub webview: servo::WebViewId,
+    /// The `http://` URL that was upgraded.
+    pub url: Url,
+}
+
+/// The `https://` URL to load instead of `url`; `None` to load it as it is
+/// (not `http://`, or a local address).
+fn upgrade(url: &Url) -> Option<Url> {
+    if url.scheme() != "http" || is_local(url.host()?) {
+        return None;
+    }
+    let mut upgraded = url.clone();
+    upgraded.set_scheme("https").ok()?;
+    Some(upgraded)
+}
+
+/// Loopback, private and link-local addresses and names: devices on the
+/// LAN rarely have a certificate to offer.
+fn is_local(host: Host<&str>) -> bool {
+    match host {
+        Host::Domain(name) => {
+            let name = name.trim_end_matches('.').to_ascii_lowercase();
+            name == "localhost" || name.ends_with(".localhost") || name.ends_with(".local")
+        }
+        Host::Ipv4(ip) => ip.is_loopback() || ip.is_private() || ip.is_link_local(),
+        Host::Ipv6(ip) => ip.is_loopback() || ip.is_unicast_link_local() || ip.is_unique_local(),
+    }
+}
+
+impl AppBrowserInner {
+    /// The upgrade for a load of `url`, unless HTTPS-only is off or the host
+    /// was let through over HTTP.
+    pub(super) fn https_upgrade(&self, url: &Url) -> Option<Url> {
+        if !self.https_only.get() {
+            return None;
+        }
+        let upgraded = upgrade(url)?;
+        let host = url.host_str()?.to_ascii_lowercase();
+        (!self.http_allowed.borrow().contains(&host)).then_some(upgraded)
+    }
+
+    /// Check the page `webview` finished loading after its upgrade from
+    /// `from` for Servo's error page, and queue the failure for the app.
+    pub(super) fn check_upgrade(&self, webview: &WebView, from: Url) {
+        let failures = self.upgrade_failures.clone();
+        let sender = self.event_sender.clone();
+        let id = webview.id();
+        error_page::detect(webview, move |page, _| {
+            if page == ErrorPage::NetError {
+                queue_failure(&failures, &sender, id, from);
+            }
+        });
+    }
+
+    /// Whether a redirected page load in `webview`, about to be upgraded to
+    /// `upgraded`, came from that same `https://` URL — the tab's own upgrade
+    /// being sent back. If so, the upgrade is dropped and its failure queued.
+    pub(super) fn upgrade_looped(&self, webview: &WebView, upgraded: &Url) -> bool {
+        let Some(i) = self.tab_index(webview.id()) else {
+            return false;
+        };
+        let tabs = self.tabs.borrow();
+        let mut upgrade = tabs[i].https_upgrade.borrow_mut();
+        if upgrade.as_ref().is_none_or(|(_, to)| to != upgraded) {
+            return false;
+        }
+        let (from, _) = upgrade.take().expect("checked above");
+        queue_failure(
+            &self.upgrade_failures,
+            &self.event_sender,
+            webview.id(),
+            from,
+        );
+        true
+    }
+}
+
+/// Hand the app a failed upgrade of `url` in `webview`.
+fn queue_failure(
+    failures: &RefCell<Vec<UpgradeFailed>>,
+    sender: &UserEventSender,
+    webview: servo::WebViewId,
+    url: Url,
+) {
+    log::warn!("https-only: {url} has no working HTTPS version");
+    failures.borrow_mut().push(UpgradeFailed { webview, url });
+    // Wake the main loop so the question shows even when idle.
+    sender.send(UserEvent::ControlPending);
+}
+
+impl AppBrowser {
+    /// Turn HTTPS-only mode on or off (settings overlay); effective on the
+    /// next load.
+    #[inline]
+    pub fn set_https_only(&self, on: bool) {
+        self.inner.https_only.set(on);
+    }
+
+    /// Take the failed upgrades found since the last call, for the app to
+    /// ask about. Drained once per frame.
+    #[inline]
+    pub fn take_upgrade_failures(&self) -> Vec<UpgradeFailed> {
+        std::mem::take(&mut self.inner.upgrade_failures.borrow_mut())
+    }
+
+    /// Let `url`'s host through over HTTP for the session and load `url` in
+    /// the tab that failed to upgrade it.
+    pub fn continue_over_http(&self, webview: servo::WebViewId, url: &Url) {
+        let Some(host) = url.host_str() else {
+            return;
+        };
+        log::info!("https-only: {host} allowed over HTTP (this session)");
+        self.inner
+            .http_allowed
+            .borrow_mut()
+            .insert(host.to_ascii_lowercase());
+        if let Some(i) = self.inner.tab_index(webview) {
+            let webview = self.inner.tabs.borrow()[i].webview.clone();
+            webview.load(url.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Remote `http://` URLs become `https://` ones, keeping any explicit
+    /// port; local addresses and other schemes stay as they are.
+    #[test]
+    fn upgrades_remote_http_only() {
+        let up = |url: &str| upgrade(&Url::parse(url).unwrap()).map(String::from);
+        assert_eq!(
+            up("http://example.com/a?b=1#c").as_deref(),
+            Some("https://example.com/a?b=1#c")
+        );
+        assert_eq!(
+            up("http://example.com:8080/").as_deref(),
+            Some("https://example.com:8080/")
+        );
+        assert_eq!(
+            up("http://example.com:80/").as_deref(),
+            Some("https://example.com/")
+        );
+        assert_eq!(up("https://example.com/"), None);
+        assert_eq!(up("http://localhost:3000/"), None);
+        assert_eq!(up("http://192.168.1.20/"), None);
+        assert_eq!(up("http://printer.local/"), None);
+        assert_eq!(up("http://[::1]/"), None);
+        assert_eq!(up("data:text/plain,hi"), None);
+    }
+}
@@ -5,24 +5,40 @@
 
 pub mod adblock;
 mod blob_download;
+pub mod block_log;
+mod cert_error;
 pub mod content_filter;
+mod cosmetic;
 
 mod delegate;
 mod engine;
+mod error_page;
+mod find;
 mod forced_dark;
+mod game_mode;
 mod home;
+mod https_only;
 pub mod memory;
+pub mod net_log;
 mod reader;
+pub mod search_suggest;
 mod url;
 
 pub use blob_download::BlobDownload;
+pub use cert_error::CertError;
 pub use engine::effective_user_agent;
+pub use error_page::mark_error_page;
 pub use home::HOME_URL;
-pub use url::try_into_url;
+pub use https_only::UpgradeFailed;
+pub use url::{search_url, try_into_url};
 
 use crate::{
-    browser::{adblock::Adblock, content_filter::ContentFilter},
+    browser::{
+        adblock::Adblock, block_log::BlockLog, content_filter::ContentFilter, net_log::NetLog,
+    },
     config::{AppConfig, BrowserConfig, ExperimentalConfig, PageTheme},
+    data::favicons::Icon,
+    data::site_settings::{Site, SiteSettings},
     event::user::{UserEvent, UserEventSender},
     overlay::hints::Hint,
 };
@@ -50,10 +66,13 @@ pub enum BrowserCommand {
     /// Step the active tab's page zoom along [`ZOOM_LADDER`] (+1 in, -1 out);
     /// `0` resets to the config default.
     Zoom(i32),
+    /// Toggle game mode on the active tab (see [`game_mode`]).
+    GameMode,
 }
 
-/// The page-zoom steps (Firefox's ladder), walked by [`BrowserCommand::Zoom`].
-const ZOOM_LADDER: &[f32] = &[
+/// The page-zoom steps (Firefox's ladder), walked by [`BrowserCommand::Zoom`]
+/// and the site settings sheet's zoom row.
+pub const ZOOM_LADDER: &[f32] = &[
     0.5, 0.67, 0.8, 0.9, 1.0, 1.1, 1.25, 1.5, 1.75, 2.0, 2.5, 3.0,
 ];
 
@@ -119,6 +138,20 @@ struct Tab {
     /// `delegate::image_key`); cleared on its own top-level navigations. Per tab so
     /// a background load can't spend the visible page's budget.
     page_images: RefCell<HashSet<u64>>,
+    /// What was blocked on this tab's current page (see [`block_log`]).
+    blocked: RefCell<BlockLog>,
+    /// Every load the tab made, across its pages (see [`net_log`]).
+    requests: RefCell<NetLog>,
+    /// The page load HTTPS-only mode upgraded (see [`https_only`]): the
+    /// `http://` URL and its `https://` twin, until the load completes.
+    https_upgrade: RefCell<Option<(Url, Url)>>,
+    /// Title from the saved session (see [`AppBrowser::restore_tabs`]), shown
+    /// until the tab's first page commits; `None` for tabs opened this run.
+    saved_title: Option<String>,
+    /// Whether the controller goes to this tab's page (see [`game_mode`]).
+    game_mode: bool,
+    /// A no-history tab (see [`AppBrowser::open_no_history_tab`]).
+    no_history: bool,
 }
 
 /// A denied download navigation or an `a[download]` link, for
@@ -140,6 +173,10 @@ pub struct TabInfo {
     pub url: String,
     /// Whether this is the currently shown tab.
     pub active: bool,
+    /// The tab's page zoom (1.0 = 100%), for the saved session.
+    pub zoom: f32,
+    /// A no-history tab: marked in the menu, left out of the saved session.
+    pub no_history: bool,
 }
 
 /// Shared state behind the [`AppBrowser`] handle. Servo calls back into it as
@@ -156,6 +193,12 @@ struct AppBrowserInner {
     /// the history log. Sourced from `notify_url_changed` (a real navigation), *not*
     /// the address-bar text — so typing a URL doesn't pollute history.
     visited: RefCell<Vec<String>>,
+    /// `(page URL, title)` pairs reported by any tab since the last drain, for
+    /// the stores that keep titles (see [`AppBrowser::take_titles`]).
+    titles: RefCell<Vec<(String, String)>>,
+    /// `(page URL, icon)` pairs reported by any tab since the last drain, for
+    /// the favicon cache (see [`AppBrowser::take_favicons`]).
+    favicons: RefCell<Vec<(String, Icon)>>,
     /// Download navigations denied by [`delegate`], drained once per frame.
     download_requests: RefCell<Vec<DownloadRequest>>,
     /// Webviews whose page signalled a captured blob download (see
@@ -165,8 +208,10 @@ struct AppBrowserInner {
     blob_downloads: RefCell<Vec<BlobDownload>>,
     /// Lowercased URL path extensions treated as downloads (from `[downloads]`).
     download_exts: Vec<String>,
-    /// Network-level ad blocking, consulted for every resource load.
-    adblock: Adblock,
+    /// Network-level ad blocking, consulted for every resource load, and the
+    /// cosmetic rules (see [`cosmetic`]). Shared with the cosmetic pass's
+    /// script callback.
+    adblock: Rc<Adblock>,
     /// Lightweight-mode content filter (block images/media/fonts), consulted
     /// for every resource load alongside the ad blocker. Behind a `Cell` so a
     /// settings save can swap in new flags live (see [`AppBrowser::set_content_filter`]).
@@ -174,6 +219,9 @@ struct AppBrowserInner {
     /// Clickable-element rects reported by the page for hint mode (see
     /// [`AppBrowser::collect_hints`]), drained once by the main loop.
     hint_rects: RefCell<Option<Vec<Hint>>>,
+    /// The page's answer to the last find-in-page search or step — the current
+    /// match (1-based) and the count (see [`find`]), drained by the main loop.
+    find_result: Cell<Option<(usize, usize)>>,
     /// The live IME request, present while an editable element on the page
     /// holds focus (see [`delegate`]). Plain-key keyboard shortcuts are
     /// suppressed while it's set so they can't hijack typing.
@@ -184,6 +232,21 @@ struct AppBrowserInner {
     /// Controls Servo retracted before they were answered, drained alongside
     /// `embedder_controls` so the overlay drops them.
     dismissed_controls: RefCell<Vec<servo::EmbedderControlId>>,
+    /// Certificate error pages waiting for the user's answer (see
+    /// [`cert_error`]), drained once per frame by the main loop. Shared with
+    /// the page check's script callback.
+    cert_errors: Rc<RefCell<Vec<CertError>>>,
+    /// Hosts the user chose to proceed to despite a certificate error, for the
+    /// session.
+    cert_exceptions: RefCell<HashSet<String>>,
+    /// `[browser] https_only`. Behind a `Cell` so a settings save applies to
+    /// the next load (see [`https_only`]).
+    https_only: Cell<bool>,
+    /// Hosts the user chose to reach over HTTP this session despite it.
+    http_allowed: RefCell<HashSet<String>>,
+    /// Pages whose upgrade failed, waiting for the user's answer; drained once
+    /// per frame by the main loop. Shared with the page check's callback.
+    upgrade_failures: Rc<RefCell<Vec<UpgradeFailed>>>,
     /// Injects the download-capture shim (see [`blob_download`]) into every
     /// document before its own scripts run; attached to each webview at build.
     user_content: Rc<servo::UserContentManager>,
@@ -191,11 +254,15 @@ struct AppBrowserInner {
     /// target (also hides the toolbar zoom chip when a tab is back at it).
     default_zoom: f32,
     /// `[browser] page_theme`. Behind a `Cell` so a settings save can retheme
-    /// the open tabs and still be inherited by tabs opened later.
+    /// the open tabs and still be inherited by tabs opened later. Forced dark
+    /// is applied per document (see [`forced_dark`]).
     page_theme: Cell<PageTheme>,
-    /// The forced-dark sheet, attached to `user_content` while the theme asks
-    /// for it. Kept so it can be detached again.
-    forced_dark: Rc<servo::user_contents::UserStyleSheet>,
+    /// Per-site overrides (see [`crate::data::site_settings`]), consulted as
+    /// pages load and edited through [`AppBrowser::set_site_settings`].
+    site_settings: RefCell<SiteSettings>,
+    /// `[input] game_sites`: hosts whose tabs start in game mode. Behind a
+    /// `RefCell` so a settings save applies to the next navigation.
+    game_sites: RefCell<Vec<String>>,
     /// Latest memory report from Servo (see [`AppBrowser::request_memory_report`]).
     /// `Arc<Mutex>` because the report arrives on an IPC router thread, not the
     /// main loop. Drained by [`AppBrowser::take_memory_report`].
@@ -211,6 +278,7 @@ impl AppBrowserInner {
         adblock: Adblock,
         content_filter: ContentFilter,
         browser: &BrowserConfig,
+        game_sites: Vec<String>,
     ) -> Self {
         // Sanitize the configured zoom: Servo clamps it to [0.1, 10.0] anyway,
         // and a zero/negative/NaN default would make every tab unusable.
@@ -228,10 +296,6 @@ impl AppBrowserInner {
             blob_download::capture_js().to_string(),
             None,
         )));
-        let forced_dark = forced_dark::stylesheet();
-        if browser.page_theme.is_forced_dark() {
-            user_content.add_stylesheet(forced_dark.clone());
-        }
         Self {
             tabs: RefCell::new(vec![]),
             active: Cell::new(0),
@@ -240,6 +304,8 @@ impl AppBrowserInner {
             rendering_ctx,
             repaint_pending: Cell::new(false),
             visited: RefCell::new(vec![]),
+            titles: RefCell::new(vec![]),
+            favicons: RefCell::new(vec![]),
             download_requests: RefCell::new(vec![]),
             blob_pings: RefCell::new(vec![]),
             blob_downloads: RefCell::new(vec![]),
@@ -247,29 +313,45 @@ impl AppBrowserInner {
                 .into_iter()
                 .map(|e| e.trim_start_matches('.').to_ascii_lowercase())
                 .collect(),
-            adblock,
+            adblock: Rc::new(adblock),
             content_filter: Cell::new(content_filter),
             hint_rects: RefCell::new(None),
+            find_result: Cell::new(None),
             ime_control: Cell::new(None),
             embedder_controls: RefCell::new(vec![]),
             dismissed_controls: RefCell::new(vec![]),
+            cert_errors: Rc::default(),
+            cert_exceptions: RefCell::default(),
+            https_only: Cell::new(browser.https_only),
+            http_allowed: RefCell::default(),
+            upgrade_failures: Rc::default(),
             user_content,
             default_zoom,
             page_theme: Cell::new(browser.page_theme),
-            forced_dark,
+            site_settings: RefCell::new(SiteSettings::load()),
+            game_sites: RefCell::new(game_sites),
             mem_report: Arc::new(Mutex::new(None)),
         }
     }
 
-    /// Attach or detach the forced-dark sheet. Only ever called on a real theme
-    /// change: `add_stylesheet` pushes, so attaching twice would double it.
-    fn sync_forced_dark(&self, theme: PageTheme) {
-        if theme.is_forced_dark() {
-            self.user_content.add_stylesheet(self.forced_dark.clone());
-        } else {
-            self.user_content
-                .remove_stylesheet(self.forced_dark.clone());
-        }
+    /// The zoom a tab arriving at `url` starts at: its site's, else the default.
+    fn site_zoom(&self, url: &str) -> f32 {
+        self.site_settings
+            .borrow()
+            .for_url(url)
+            .and_then(|s| s.zoom)
+            .unwrap_or(self.default_zoom)
+    }
+
+    /// The content filter and ad-block exemption (the allowlist) for loads on
+    /// the page at `page_url`, its site's overrides applied.
+    fn filters_for(&self, page_url: &str) -> (ContentFilter, bool) {
+        let filter = self.content_filter.get();
+        let filter = match self.site_settings.borrow().for_url(page_url) {
+            Some(site) => filter.for_site(site),
+            None => filter,
+        };
+        (filter, self.adblock.is_allowlisted(page_url))
     }
 
     /// The currently shown tab's webview, if any.
@@ -311,6 +393,7 @@ impl AppBrowser {
             Adblock::new(&config.adblock),
             ContentFilter::from_config(&config.data_saving),
             &config.browser,
+            config.input.game_sites.clone(),
         );
 
         Ok(Self {
@@ -352,6 +435,18 @@ impl AppBrowser {
         std::mem::take(&mut self.inner.visited.borrow_mut())
     }
 
+    /// Take and clear the page titles reported since the last call, oldest first.
+    #[inline]
+    pub fn take_titles(&self) -> Vec<(String, String)> {
+        std::mem::take(&mut self.inner.titles.borrow_mut())
+    }
+
+    /// Take and clear the favicons reported since the last call, oldest first.
+    #[inline]
+    pub fn take_favicons(&self) -> Vec<(String, Icon)> {
+        std::mem::take(&mut self.inner.favicons.borrow_mut())
+    }
+
     /// Take and clear the download navigations denied since the last call.
     #[inline]
     pub fn take_download_requests(&self) -> Vec<DownloadRequest> {
@@ -435,6 +530,44 @@ impl AppBrowser {
         self.inner.content_filter.set(filter);
     }
 
+    /// Swap in an edited `[input] game_sites` (settings save). Open tabs keep
+    /// their mode; the list is consulted when a tab moves to another host.
+    pub fn set_game_sites(&self, sites: Vec<String>) {
+        *self.inner.game_sites.borrow_mut() = sites;
+    }
+
+    /// Whether the active tab is in game mode — the controller then goes to the
+    /// page instead of the browser (see [`crate::event::handler`]).
+    pub fn game_mode(&self) -> bool {
+        let tabs = self.inner.tabs.borrow();
+        tabs.get(self.inner.active.get())
+            .is_some_and(|t| t.game_mode)
+    }
+
+    /// Whether the active tab keeps no history (the toolbar marks it).
+    pub fn no_history(&self) -> bool {
+        let tabs = self.inner.tabs.borrow();
+        tabs.get(self.inner.active.get())
+            .is_some_and(|t| t.no_history)
+    }
+
+    /// What was blocked on the active tab's page, for the blocked-requests
+    /// panel.
+    pub fn block_log(&self) -> BlockLog {
+        let tabs = self.inner.tabs.borrow();
+        tabs.get(self.inner.active.get())
+            .map(|t| t.blocked.borrow().clone())
+            .unwrap_or_default()
+    }
+
+    /// Loads blocked on the active tab's page — feeds the toolbar's shield
+    /// chip (hidden at zero).
+    pub fn blocked_count(&self) -> usize {
+        let tabs = self.inner.tabs.borrow();
+        tabs.get(self.inner.active.get())
+            .map_or(0, |t| t.blocked.borrow().total())
+    }
+
     /// Re-apply the experimental prefs live (settings overlay). Like
     /// [`Self::set_content_filter`], effective on the next page load.
     #[inline]
@@ -445,14 +578,13 @@ impl AppBrowser {
     /// Retheme every open tab and inherit the choice into later ones.
     ///
     /// Reloads them: measured on Servo 0.4, notifying a loaded page flips
-    /// `matchMedia` but does not restyle it, and user stylesheets need a load
-    /// either way. Guarded on an actual change so an unrelated settings save
+    /// `matchMedia` but does not restyle it, and the forced-dark style goes in
+    /// as a page loads either way. Guarded on an actual change so an unrelated settings save
     /// can't discard scroll and form state.
     pub fn set_page_theme(&self, theme: PageTheme) {
         if self.inner.page_theme.replace(theme) == theme {
             return;
         }
-        self.inner.sync_forced_dark(theme);
         for tab in self.inner.tabs.borrow_mut().iter_mut() {
             tab.webview.notify_theme_change(engine::theme(theme));
             tab.state.loading = true;
@@ -548,12 +680,15 @@ impl AppBrowser {
                     .webview
                     .page_title()
                     .filter(|t| !t.is_empty())
+                    .or_else(|| tab.saved_title.clone().filter(|t| !t.is_empty()))
                     .or_else(|| Some(tab.state.page_url.clone()).filter(|l| !l.is_empty()))
                     .unwrap_or_else(|| "New tab".to_string());
                 TabInfo {
                     title,
                     url: tab.state.page_url.clone(),
                     active: i == active,
+                    zoom: tab.webview.page_zoom(),
+                    no_history: tab.no_history,
                 }
             })
             .collect()
@@ -583,6 +718,20 @@ impl AppBrowser {
 
     /// Open a new tab at `url` and make it the active (shown) one.
     pub fn open_tab(&mut self, url: &str) {
+        self.open_shown_tab(url, false);
+    }
+
+    /// Open a new no-history tab at `url` and show it. Its visits stay out of
+    /// history, the favicon cache and the saved session, and its zoom isn't
+    /// remembered for the site. Cookies and storage are not isolated: Servo has
+    /// one jar for every webview, and clearing a visited site's data afterwards
+    /// would log the normal tabs out of it too, so a no-history tab shares (and
+    /// keeps) site data like any other.
+    pub fn open_no_history_tab(&mut self, url: &str) {
+        self.open_shown_tab(url, true);
+    }
+
+    fn open_shown_tab(&mut self, url: &str, no_history: bool) {
         let Some(webview) = self.build_tab(url) else {
             return;
         };
@@ -600,6 +749,12 @@ impl AppBrowser {
             webview,
             state: BrowserState::loading(),
             page_images: RefCell::default(),
+            blocked: RefCell::default(),
+            requests: RefCell::default(),
+            https_upgrade: RefCell::default(),
+            saved_title: None,
+            game_mode: false,
+            no_history,
         });
         self.inner.active.set(tabs.len() - 1);
         drop(tabs);
@@ -609,18 +764,77 @@ impl AppBrowser {
     /// Open `url` in a new background tab: built and loading, but left unshown
     /// and unfocused so the current tab stays in view (the link-hints "open in
     /// new tab" gesture). Loading is independent of `show()`, so it fetches in
-    /// the background; switch to it later via the tab cycle or menu.
+    /// the background; switch to it later via the tab cycle or menu. Opened
+    /// from a no-history tab, it keeps none either.
     pub fn open_tab_background(&mut self, url: &str) {
         let Some(webview) = self.build_tab(url) else {
             return;
         };
+        let no_history = self.no_history();
         self.inner.tabs.borrow_mut().push(Tab {
             webview,
             state: BrowserState::loading(),
             page_images: RefCell::default(),
+            blocked: RefCell::default(),
+            requests: RefCell::default(),
+            https_upgrade: RefCell::default(),
+            saved_title: None,
+            game_mode: false,
+            no_history,
         });
     }
 
+    /// Replace the open tabs with a saved session: each entry's URL reopened at
+    /// its zoom, with its title shown until the page reports one. Only the
+    /// `active` entry is shown; the rest load in the background. Entries whose URL
+    /// won't parse are dropped. Returns false (tabs untouched) when none will.
+    pub fn restore_tabs(&mut self, saved: &[TabInfo]) -> bool {
+        let mut active = 0;
+        let mut restored = Vec::with_capacity(saved.len());
+        for info in saved {
+            let Some(webview) = self.build_tab(&info.url) else {
+                continue;
+            };
+            if info.zoom != self.inner.default_zoom {
+                webview.set_page_zoom(info.zoom);
+            }
+            if info.active {
+                active = restored.len();
+            }
+            // The first commit is same-host, so an allowlisted site is decided
+            // here rather than in the delegate.
+            let game_mode = Url::parse(&info.url).is_ok_and(|url| {
+                game_mode::after_navigation(false, "", &url, &self.inner.game_sites.borrow())
+            });
+            let mut state = BrowserState::loading();
+            state.location = info.url.clone();
+            state.page_url = info.url.clone();
+            restored.push(Tab {
+                webview,
+                state,
+                page_images: RefCell::default(),
+                blocked: RefCell::default(),
+                requests: RefCell::default(),
+                https_upgrade: RefCell::default(),
+                saved_title: Some(info.title.clone()),
+                game_mode,
+                no_history: false,
+            });
+        }
+        if restored.is_empty() {
+            return false;
+        }
+
+        let shown = &restored[active].webview;
+        shown.show();
+        shown.focus();
+        // Dropping the old tabs closes their webviews (see `close_tab`).
+        *self.inner.tabs.borrow_mut() = restored;
+        self.inner.active.set(active);
+        self.inner.repaint_pending.set(true);
+        true
+    }
+
     /// Switch the shown tab to `index` (no-op if out of range or already active).
     pub fn switch_to(&self, index: usize) {
         let tabs = self.inner.tabs.borrow();
@@ -755,7 +969,7 @@ impl AppBrowser {
         tab.notify_scroll_event(delta, point);
     }
 
-    pub fn execute_command(&mut self, command: &BrowserCommand, config: &BrowserConfig) {
+    pub fn execute_command(&mut self, command: &BrowserCommand, config: &AppConfig) {
         match command {
             BrowserCommand::Back => _ = self.inner.active_webview().map(|x| x.go_back(1)),
             BrowserCommand::Forward => _ = self.inner.active_webview().map(|x| x.go_forward(1)),
@@ -767,13 +981,19 @@ impl AppBrowser {
             }
             BrowserCommand::Reader => self.toggle_reader(),
             BrowserCommand::Zoom(delta) => self.zoom(*delta),
+            BrowserCommand::GameMode => {
+                let active = self.inner.active.get();
+                if let Some(tab) = self.inner.tabs.borrow_mut().get_mut(active) {
+                    tab.game_mode = !tab.game_mode;
+                }
+            }
             BrowserCommand::Load => {
                 let active = self.inner.active.get();
                 let tabs = self.inner.tabs.borrow();
                 let Some(tab) = tabs.get(active) else {
                     return;
                 };
-                let Some(url) = try_into_url(&tab.state.location, &config.search_page) else {
+                let Some(url) = try_into_url(&tab.state.location, config) else {
                     log::warn!("failed to parse location");
                     return;
                 };
@@ -786,8 +1006,8 @@ impl AppBrowser {
                 let Some(webview) = self.inner.active_webview() else {
                     return;
                 };
-                let Some(url) = try_into_url(&config.home_page, &config.search_page) else {
-                    log::warn!("failed to parse home_page `{}`", config.home_page);
+                let Some(url) = try_into_url(&config.browser.home_page, config) else {
+                    log::warn!("failed to parse home_page `{}`", config.browser.home_page);
                     return;
                 };
                 self.mark_loading();
@@ -821,7 +1041,8 @@ impl AppBrowser {
     /// Step the active tab's page zoom to the next [`ZOOM_LADDER`] entry in
     /// the given direction (so an off-ladder config default still steps
     /// sensibly); `0` resets to the config default. Page zoom reflows the
-    /// layout and is per-WebView, so each tab keeps its own level.
+    /// layout and is per-WebView, so each tab keeps its own level; the site
+    /// keeps it too, for its next visit (see [`crate::data::site_settings`]).
     fn zoom(&self, delta: i32) {
         let Some(webview) = self.inner.active_webview() else {
             return;
@@ -840,6 +1061,62 @@ impl AppBrowser {
                 .unwrap_or(&ZOOM_LADDER[0]),
         };
         webview.set_page_zoom(target);
+        self.remember_zoom(target);
+    }
+
+    /// Keep the active tab's zoom as its site's, forgotten back at the default.
+    /// Written on the next [`Self::flush_site_settings`], so stepping through
+    /// the ladder costs one write. A no-history tab's zoom isn't kept.
+    fn remember_zoom(&self, zoom: f32) {
+        if self.no_history() {
+            return;
+        }
+        let url = self.get_state_mut().page_url.clone();
+        let mut sites = self.inner.site_settings.borrow_mut();
+        let Some((key, mut site)) = sites.entry_for(&url) else {
+            return;
+        };
+        site.zoom = ((zoom - self.inner.default_zoom).abs() > 0.005).then_some(zoom);
+        sites.set_zoom(&key, site);
+    }
+
+    /// Persist deferred site-zoom changes. Called on the app's history-flush
+    /// throttle and at shutdown.
+    pub fn flush_site_settings(&self) {
+        self.inner.site_settings.borrow_mut().flush();
+    }
+
+    /// The active page's site settings, for the sheet that edits them: the key
+    /// they are stored under, the overrides and whether the ad blocker runs
+    /// there (off when allowlisted). `None` on a page without a host.
+    pub fn site_settings(&self) -> Option<(String, Site, bool)> {
+        let url = self.get_state_mut().page_url.clone();
+        let (key, site) = self.inner.site_settings.borrow().entry_for(&url)?;
+        Some((key, site, !self.inner.adblock.is_allowlisted(&url)))
+    }
+
+    /// Store a site's edited overrides and ad-block allowlisting, and bring the
+    /// active tab in line: its zoom at once, and a reload when something only a
+    /// load applies (forced dark, content or ad blocking) changed.
+    pub fn set_site_settings(&self, key: &str, site: Site, blocking: bool) {
+        let url = self.get_state_mut().page_url.clone();
+        let was_blocking = !self.inner.adblock.is_allowlisted(&url);
+        self.inner.adblock.set_allowlisted(&url, !blocking);
+        let current = |sites: &SiteSettings| sites.for_url(&url).cloned().unwrap_or_default();
+        let before = current(&self.inner.site_settings.borrow());
+        self.inner.site_settings.borrow_mut().set(key, site);
+        let after = current(&self.inner.site_settings.borrow());
+        let Some(webview) = self.inner.active_webview() else {
+            return;
+        };
+        if before.zoom != after.zoom {
+            webview.set_page_zoom(after.zoom.unwrap_or(self.inner.default_zoom));
+        }
+        let on_load = |site: Site| Site { zoom: None, ..site };
+        if on_load(before) != on_load(after) || was_blocking != blocking {
+            self.mark_loading();
+            webview.reload();
+        }
     }
 
     /// The active tab's page zoom as a percentage, when it differs from the
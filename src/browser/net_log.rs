@@ -0,0 +1,388 @@
+//! Every load the network hook in [`super::delegate`] saw on a tab — URL,
+//! method, destination, what became of it, and when — for telling why a site
+//! misbehaves. Unlike [`super::block_log`] it outlives navigations: each
+//! top-level load starts a new page, and the oldest loads (and the pages left
+//! without any) drop off past [`MAX_ENTRIES`]. The blocked-requests panel's
+//! Requests section ([`crate::overlay::blocked`]) lists it, and it exports as
+//! HAR 1.2 for a desktop's dev tools.
+//!
+//! The hook sees requests, not responses: a status is known only where the
+//! hook answered itself (the start page, an HTTPS-only redirect), and a
+//! load's timing is when it started, relative to its page.
+
+use super::{AppBrowser, AppBrowserInner};
+use content_security_policy::Destination;
+use serde_json::{json, Value};
+use servo::WebView;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Loads kept per tab.
+const MAX_ENTRIES: usize = 500;
+
+/// A request as the hook received it.
+#[derive(Clone)]
+pub struct Request {
+    pub url: String,
+    pub method: String,
+    pub destination: Destination,
+    pub headers: Vec<(String, String)>,
+    pub main_frame: bool,
+    /// A redirect's follow-up (still the same page when top-level).
+    pub redirect: bool,
+}
+
+impl Request {
+    pub(super) fn new(req: &servo::WebResourceRequest) -> Self {
+        Self {
+            url: req.url.to_string(),
+            method: req.method.to_string(),
+            destination: req.destination,
+            headers: req
+                .headers
+                .iter()
+                .map(|(name, value)| {
+                    let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+                    (name.to_string(), value)
+                })
+                .collect(),
+            main_frame: req.is_for_main_frame,
+            redirect: req.is_redirect,
+        }
+    }
+
+    /// The destination, in the words dev tools use for a resource type.
+    pub fn kind(&self) -> &'static str {
+        match self.destination {
+            Destination::Document => "document",
+            Destination::Frame | Destination::IFrame => "frame",
+            Destination::Script | Destination::Worker => "script",
+            Destination::Style => "style",
+            Destination::Image => "image",
+            Destination::Font => "font",
+            Destination::Audio | Destination::Video | Destination::Track => "media",
+            Destination::None | Destination::Json => "fetch",
+            Destination::Report => "ping",
+            _ => "other",
+        }
+    }
+}
+
+/// What became of a request.
+#[derive(Clone)]
+pub enum Outcome {
+    /// Let through to the network; its response isn't seen here.
+    Passed,
+    /// Answered by the hook itself, with this status.
+    Answered(u16),
+    /// Redirected by the hook (HTTPS-only, URL cleaning) to this URL.
+    Redirected(String),
+    /// Blocked, by this reason and rule.
+    Blocked(String),
+}
+
+impl Outcome {
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            Outcome::Answered(status) => Some(*status),
+            Outcome::Redirected(_) => Some(307),
+            Outcome::Passed | Outcome::Blocked(_) => None,
+        }
+    }
+
+    /// Short, for a list row.
+    pub fn label(&self) -> String {
+        match self {
+            Outcome::Blocked(_) => "blocked".to_string(),
+            _ => self.status().map_or_else(String::new, |s| s.to_string()),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Entry {
+    pub request: Request,
+    pub outcome: Outcome,
+    pub started: SystemTime,
+    /// Since its page's top-level load started.
+    pub offset: Duration,
+    page: u32,
+}
+
+#[derive(Clone)]
+struct Page {
+    id: u32,
+    url: String,
+    started: SystemTime,
+}
+
+#[derive(Clone, Default)]
+pub struct NetLog {
+    /// Oldest first, like the entries.
+    pages: VecDeque<Page>,
+    entries: VecDeque<Entry>,
+    /// When the newest page started, for the offsets.
+    page_start: Option<Instant>,
+}
+
+impl NetLog {
+    pub fn record(&mut self, request: Request, outcome: Outcome) {
+        let now = Instant::now();
+        if (request.main_frame && !request.redirect) || self.pages.is_empty() {
+            let id = self.pages.back().map_or(0, |p| p.id.wrapping_add(1));
+            self.pages.push_back(Page {
+                id,
+                url: request.url.clone(),
+                started: SystemTime::now(),
+            });
+            self.page_start = Some(now);
+        }
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+            // A page whose loads are all gone goes too (the newest stays).
+            while self.pages.len() > 1
+                && self
+                    .entries
+                    .front()
+                    .is_some_and(|e| e.page != self.pages[0].id)
+            {
+                self.pages.pop_front();
+            }
+        }
+        self.entries.push_back(Entry {
+            request,
+            outcome,
+            started: SystemTime::now(),
+            offset: self.page_start.map_or_else(Duration::default, |t| now - t),
+            page: self.pages.back().map_or(0, |p| p.id),
+        });
+    }
+
+    /// The kept loads, newest first.
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.iter().rev()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// A file name for the export: the newest page's host and the time.
+    pub fn har_name(&self) -> String {
+        let host = self
+            .pages
+            .back()
+            .and_then(|p| url::Url::parse(&p.url).ok())
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_else(|| "retsurf".to_string());
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        format!("{host}-{secs}.har")
+    }
+
+    /// The log as a HAR 1.2 document. What the hook can't know is filled as
+    /// the spec says for "not available": `-1` sizes, status `0`, zero times.
+    /// Blocked loads carry an `_error`, as Chrome exports them.
+    pub fn to_har(&self) -> Value {
+        let pages: Vec<Value> = self
+            .pages
+            .iter()
+            .map(|p| {
+                json!({
+                    "startedDateTime": iso8601(p.started),
+                    "id": page_ref(p.id),
+                    "title": p.url,
+                    "pageTimings": { "onContentLoad": -1, "onLoad": -1 },
+                })
+            })
+            .collect();
+        let entries: Vec<Value> = self.entries.iter().map(har_entry).collect();
+        json!({
+            "log": {
+                "version": "1.2",
+                "creator": { "name": "retsurf", "version": env!("CARGO_PKG_VERSION") },
+                "pages": pages,
+                "entries": entries,
+            }
+        })
+    }
+}
+
+fn har_entry(entry: &Entry) -> Value {
+    let request = &entry.request;
+    let pairs = |pairs: &[(String, String)]| -> Vec<Value> {
+        pairs
+            .iter()
+            .map(|(name, value)| json!({ "name": name, "value": value }))
+            .collect()
+    };
+    let query: Vec<(String, String)> = url::Url::parse(&request.url)
+        .map(|url| url.query_pairs().into_owned().collect())
+        .unwrap_or_default();
+    let (location, headers) = match &entry.outcome {
+        Outcome::Redirected(to) => (to.as_str(), vec![("Location".to_string(), to.clone())]),
+        _ => ("", Vec::new()),
+    };
+    let mut har = json!({
+        "pageref": page_ref(entry.page),
+        "startedDateTime": iso8601(entry.started),
+        "time": 0,
+        "request": {
+            "method": request.method,
+            "url": request.url,
+            "httpVersion": "",
+            "cookies": [],
+            "headers": pairs(&request.headers),
+            "queryString": pairs(&query),
+            "headersSize": -1,
+            "bodySize": -1,
+        },
+        "response": {
+            "status": entry.outcome.status().unwrap_or(0),
+            "statusText": "",
+            "httpVersion": "",
+            "cookies": [],
+            "headers": pairs(&headers),
+            "content": { "size": -1, "mimeType": "" },
+            "redirectURL": location,
+            "headersSize": -1,
+            "bodySize": -1,
+        },
+        "cache": {},
+        "timings": { "send": 0, "wait": 0, "receive": 0 },
+        "_resourceType": request.kind(),
+    });
+    if let Outcome::Blocked(reason) = &entry.outcome {
+        har["_error"] = json!(format!("blocked: {reason}"));
+    }
+    har
+}
+
+fn page_ref(id: u32) -> String {
+    format!("page_{id}")
+}
+
+/// `t` as an ISO 8601 UTC timestamp with milliseconds.
+fn iso8601(t: SystemTime) -> String {
+    let since = t.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let (secs, ms) = (since.as_secs(), since.subsec_millis());
+    // Days to a civil date (Howard Hinnant's `civil_from_days`).
+    let z = (secs / 86_400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    let rem = secs % 86_400;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{ms:03}Z",
+        rem / 3600,
+        rem / 60 % 60,
+        rem % 60
+    )
+}
+
+impl AppBrowserInner {
+    /// Note a load the hook handled in `webview`'s log.
+    pub(super) fn log_request(&self, webview: &WebView, request: Request, outcome: Outcome) {
+        if let Some(i) = self.tab_index(webview.id()) {
+            self.tabs.borrow()[i]
+                .requests
+                .borrow_mut()
+                .record(request, outcome);
+        }
+    }
+}
+
+impl AppBrowser {
+    /// The active tab's loads, for the blocked-requests panel.
+    pub fn net_log(&self) -> NetLog {
+        let tabs = self.inner.tabs.borrow();
+        tabs.get(self.inner.active.get())
+            .map(|t| t.requests.borrow().clone())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(url: &str, main_frame: bool) -> Request {
+        Request {
+            url: url.to_string(),
+            method: "GET".to_string(),
+            destination: if main_frame {
+                Destination::Document
+            } else {
+                Destination::Script
+            },
+            headers: vec![("Accept".to_string(), "*/*".to_string())],
+            main_frame,
+            redirect: false,
+        }
+    }
+
+    /// The buffer keeps the newest loads, and the pages they belong to.
+    #[test]
+    fn keeps_the_newest_loads_and_their_pages() {
+        let mut log = NetLog::default();
+        log.record(request("https://a.example/", true), Outcome::Passed);
+        log.record(request("https://b.example/", true), Outcome::Passed);
+        for i in 0..MAX_ENTRIES - 1 {
+            let url = format!("https://cdn.example/{i}.js");
+            log.record(request(&url, false), Outcome::Passed);
+        }
+        assert_eq!(log.len(), MAX_ENTRIES);
+        assert_eq!(log.pages.len(), 1);
+        assert_eq!(log.pages[0].url, "https://b.example/");
+        assert_eq!(
+            log.entries().next().unwrap().request.url,
+            format!("https://cdn.example/{}.js", MAX_ENTRIES - 2)
+        );
+    }
+
+    /// The export has HAR's required fields, with a blocked load's reason
+    /// and a redirect's target.
+    #[test]
+    fn exports_har() {
+        let mut log = NetLog::default();
+        log.record(
+            request("http://a.example/?q=1", true),
+            Outcome::Redirected("https://a.example/?q=1".to_string()),
+        );
+        log.record(
+            request("https://ads.example/x.js", false),
+            Outcome::Blocked("Ads: ||ads.example^".to_string()),
+        );
+        let har = log.to_har();
+        let entries = har["log"]["entries"].as_array().unwrap();
+        assert_eq!(har["log"]["version"], "1.2");
+        assert_eq!(har["log"]["pages"][0]["id"], "page_0");
+        assert_eq!(entries[0]["pageref"], "page_0");
+        assert_eq!(entries[0]["response"]["status"], 307);
+        assert_eq!(
+            entries[0]["response"]["redirectURL"],
+            "https://a.example/?q=1"
+        );
+        assert_eq!(
+            entries[0]["request"]["queryString"][0],
+            json!({ "name": "q", "value": "1" })
+        );
+        assert_eq!(entries[1]["response"]["status"], 0);
+        assert_eq!(entries[1]["_error"], "blocked: Ads: ||ads.example^");
+        assert_eq!(entries[1]["_resourceType"], "script");
+        assert_eq!(
+            iso8601(UNIX_EPOCH + Duration::from_millis(1_700_000_000_500)),
+            "2023-11-14T22:13:20.500Z"
+        );
+    }
+}
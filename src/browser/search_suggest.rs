@@ -0,0 +1,106 @@
+//! Search suggestions from the default engine's `suggest_url` (see
+//! [`crate::config::SearchEngine::suggest_url`]) for the address-bar list in
+//! [`crate::overlay::suggest`]. Fetched off the main thread, one request at a
+//! time: text typed while one is out is fetched when it returns, and only the
+//! newest. The answer is handed back through a shared slot the UI polls each
+//! frame, with a [`UserEvent::SuggestReady`] to wake the loop.
+
+use crate::event::user::{UserEvent, UserEventSender};
+use std::sync::{Arc, Mutex};
+
+/// Suggestions kept from one answer — the list has only a few rows anyway.
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Largest answer read; a real one is a few hundred bytes.
+const MAX_ANSWER: u64 = 64 * 1024;
+
+#[derive(Default)]
+struct Slot {
+    /// A request is out.
+    busy: bool,
+    /// The newest query typed meanwhile, as `(endpoint, query)`.
+    pending: Option<(String, String)>,
+    /// The latest answer: the query it was for and its suggestions.
+    done: Option<(String, Vec<String>)>,
+}
+
+pub struct SearchSuggest {
+    slot: Arc<Mutex<Slot>>,
+    sender: UserEventSender,
+}
+
+impl SearchSuggest {
+    pub fn new(sender: UserEventSender) -> Self {
+        Self {
+            slot: Arc::new(Mutex::new(Slot::default())),
+            sender,
+        }
+    }
+
+    /// Ask `endpoint` (`%s` for the query) what it suggests for `query`.
+    pub fn request(&self, endpoint: &str, query: &str) {
+        let job = (endpoint.to_string(), query.to_string());
+        let mut slot = self.slot.lock().unwrap();
+        if slot.busy {
+            slot.pending = Some(job);
+            return;
+        }
+        slot.busy = true;
+        drop(slot);
+        let slot = self.slot.clone();
+        let sender = self.sender.clone();
+        std::thread::spawn(move || worker(job, slot, sender));
+    }
+
+    /// Take the latest answer, if one came in since the last call.
+    pub fn take(&self) -> Option<(String, Vec<String>)> {
+        self.slot.lock().unwrap().done.take()
+    }
+}
+
+/// Fetch `job`, then whatever was queued behind it, until nothing is.
+fn worker(mut job: (String, String), slot: Arc<Mutex<Slot>>, sender: UserEventSender) {
+    loop {
+        let (endpoint, query) = &job;
+        let suggestions = fetch(endpoint, query).unwrap_or_else(|e| {
+            log::warn!("search suggestions: `{endpoint}`: {e}");
+            Vec::new()
+        });
+        let mut slot = slot.lock().unwrap();
+        slot.done = Some((job.1.clone(), suggestions));
+        sender.send(UserEvent::SuggestReady);
+        match slot.pending.take() {
+            Some(next) => job = next,
+            None => {
+                slot.busy = false;
+                return;
+            }
+        }
+    }
+}
+
+/// One OpenSearch suggestions request: the answer is `["query", ["a", "b", …],
+/// …]`; only the second element matters.
+fn fetch(endpoint: &str, query: &str) -> Result<Vec<String>, String> {
+    let url = super::url::search_url(endpoint, query).ok_or("not a URL")?;
+    let text = crate::net::agent(url.as_str())
+        .get(url.as_str())
+        .call()
+        .map_err(|e| e.to_string())?
+        .body_mut()
+        .with_config()
+        .limit(MAX_ANSWER)
+        .read_to_string()
+        .map_err(|e| e.to_string())?;
+    let value: serde_json::Value = serde_json::from_str(&text).map_err(|e| e.to_string())?;
+    let list = value
+        .get(1)
+        .and_then(serde_json::Value::as_array)
+        .ok_or("not an OpenSearch suggestions answer")?;
+    Ok(list
+        .iter()
+        .filter_map(serde_json::Value::as_str)
+        .take(MAX_SUGGESTIONS)
+        .map(str::to_string)
+        .collect())
+}
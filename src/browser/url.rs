@@ -1,21 +1,25 @@
-//! Address-bar input interpretation: try the text as a URL, then as a bare
-//! domain or absolute file path, and finally fall back to the configured search
-//! page.
+//! Address-bar input interpretation: try the text as a URL, then as an
+//! absolute file path, a keyword search (`w retro handheld`) or a bare domain,
+//! and finally fall back to the default search engine.
 
+use crate::config::{AppConfig, SearchEngines};
 use url::Url;
 
 /// Interpret an input URL.
 ///
 /// If this is not a valid URL, try to "fix" it by adding a scheme or if all else fails,
-/// interpret the string as a search term.
-pub fn try_into_url<S: AsRef<str>>(request: S, searchpage: &str) -> Option<Url> {
+/// interpret the string as a search term. A leading search keyword (see
+/// [`SearchEngines::by_keyword`]) is checked before the domain guess, so
+/// `w example.com` searches rather than loads.
+pub fn try_into_url<S: AsRef<str>>(request: S, config: &AppConfig) -> Option<Url> {
     let request = request.as_ref().trim();
 
     Url::parse(request)
         .ok()
         .or_else(|| try_as_file(request))
+        .or_else(|| try_as_keyword(request, &config.search_engines))
         .or_else(|| try_as_domain(request))
-        .or_else(|| try_as_search_page(request, searchpage))
+        .or_else(|| try_as_search_page(request, config.search_page()))
 }
 
 fn try_as_file(request: &str) -> Option<Url> {
@@ -25,6 +29,11 @@ fn try_as_file(request: &str) -> Option<Url> {
     None
 }
 
+fn try_as_keyword(request: &str, engines: &SearchEngines) -> Option<Url> {
+    let (engine, query) = engines.by_keyword(request)?;
+    search_url(&engine.url, query)
+}
+
 fn try_as_domain(request: &str) -> Option<Url> {
     fn is_domain_like(s: &str) -> bool {
         !s.starts_with('/') && s.contains('/')
@@ -43,5 +52,12 @@ fn try_as_search_page(request: &str, searchpage: &str) -> Option<Url> {
         return None;
     }
 
-    Url::parse(&searchpage.replace("%s", request)).ok()
+    search_url(searchpage, request)
+}
+
+/// A search `template` with its `%s` replaced by `query`, form-encoded so a
+/// `&`, `#` or `+` in the query stays part of it.
+pub fn search_url(template: &str, query: &str) -> Option<Url> {
+    let query: String = url::form_urlencoded::byte_serialize(query.as_bytes()).collect();
+    Url::parse(&template.replace("%s", &query)).ok()
 }
@@ -1,14 +1,23 @@
 use serde::{Deserialize, Serialize};
 
-/// Ad-blocker settings (`[adblock]` in the config): network-level filtering via
-/// Brave's adblock-rust engine — see [`crate::browser::adblock`].
+/// Ad-blocker settings (`[adblock]` in the config): network-level filtering and
+/// element hiding via Brave's adblock-rust engine — see
+/// [`crate::browser::adblock`].
 #[derive(Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct AdblockConfig {
     /// Master switch. When off, no lists are fetched and nothing is filtered.
     pub enabled: bool,
-    /// Filter lists (EasyList syntax) downloaded into the engine.
+    /// Element hiding: the lists' `##` rules hide ad boxes and cookie banners
+    /// left behind on the page (see `browser::cosmetic`).
+    pub cosmetic: bool,
+    /// Filter lists (EasyList syntax) compiled into the engine: URLs to
+    /// download, or local files (`file://` URLs, or paths — relative ones under
+    /// the data dir).
     pub lists: Vec<String>,
+    /// Block with the engine bundled into the binary until the lists have been
+    /// read once (a device that hasn't been online yet).
+    pub bundled: bool,
     /// Re-download the lists once the cached engine is older than this many
     /// days; `0` never refreshes (keeps using whatever cache exists).
     pub update_days: u64,
@@ -18,10 +27,12 @@ impl Default for AdblockConfig {
     fn default() -> Self {
         Self {
             enabled: true,
+            cosmetic: true,
             lists: vec![
                 "https://easylist.to/easylist/easylist.txt".to_string(),
                 "https://easylist.to/easylist/easyprivacy.txt".to_string(),
             ],
+            bundled: true,
             update_days: 7,
         }
     }
@@ -49,6 +49,9 @@ pub const HISTORY_MAX: IntBounds = IntBounds { min: 0, max: 1000 };
 pub const ADBLOCK_UPDATE_DAYS: IntBounds = IntBounds { min: 0, max: 90 };
 pub const IMAGES_PER_PAGE: IntBounds = IntBounds { min: 0, max: 500 };
 
+// Downloads
+pub const DOWNLOADS_CONCURRENT: IntBounds = IntBounds { min: 0, max: 8 };
+
 // Audio
 pub const DECODE_SECONDS: IntBounds = IntBounds { min: 0, max: 3600 };
 
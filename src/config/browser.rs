@@ -5,7 +5,12 @@ use serde::{Deserialize, Serialize};
 #[serde(default)]
 pub struct BrowserConfig {
     pub home_page: String,
+    /// The results page for typed text that isn't an address, `%s` standing
+    /// for the query. Used while `search_engine` names no engine.
     pub search_page: String,
+    /// The default search engine, by its `[[search_engines]]` name. Empty (or
+    /// an unknown name) searches `search_page` instead.
+    pub search_engine: String,
     /// The User-Agent header sites see. Empty keeps Servo's platform default;
     /// the keywords `desktop`, `mobile` (or `android`), and `ios` pick the
     /// matching stock UA — `mobile` makes sites serve their phone layouts,
@@ -24,6 +29,14 @@ pub struct BrowserConfig {
     /// `forced-dark` inverts every page. Changing it reloads the open tabs (see
     /// [`crate::browser::AppBrowser::set_page_theme`]).
     pub page_theme: PageTheme,
+    /// Reopen the previous run's tabs on start (kept in `session.toml` in the
+    /// data dir). After a crash retsurf asks first either way.
+    pub restore_session: bool,
+    /// Load `http://` pages and resources over HTTPS instead, offering to
+    /// continue over HTTP for a host whose upgrade fails (see
+    /// [`crate::browser::AppBrowser::set_https_only`]). Local addresses are
+    /// left alone.
+    pub https_only: bool,
 }
 
 impl Default for BrowserConfig {
@@ -32,10 +45,13 @@ impl Default for BrowserConfig {
             // The built-in start page (crate::browser::home::HOME_URL).
             home_page: "retsurf:home".to_string(),
             search_page: "https://duckduckgo.com/?q=%s".to_string(),
+            search_engine: String::new(),
             user_agent: String::new(),
             persist_site_data: true,
             page_zoom: 1.0,
             page_theme: PageTheme::Light,
+            restore_session: true,
+            https_only: false,
         }
     }
 }
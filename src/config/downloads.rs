@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 use super::data_dir;
+use crate::config::token_enum::token_enum;
 
 /// File-download settings (`[downloads]` in the config). Servo has no download
 /// support, so retsurf intercepts navigations to file-like URLs and fetches them
@@ -18,6 +20,21 @@ pub struct DownloadsConfig {
     /// URLs without one of these extensions (e.g. dynamic `download.php?id=5`
     /// links) load in the browser as usual.
     pub extensions: Vec<String>,
+    /// How many downloads run at once; further ones wait in a queue. `0` lifts
+    /// the limit.
+    pub max_concurrent: usize,
+    /// Unpack finished `.zip` downloads, and where to (see [`ExtractMode`]).
+    pub extract: ExtractMode,
+    /// Delete an archive once it unpacked without errors.
+    pub delete_archive: bool,
+    /// When a finished download's link carries no hash, look for one published
+    /// beside it (`<file>.sha256`, else the folder's `SHA256SUMS`), after the
+    /// row shows as done: up to two extra requests per file.
+    pub checksum_lookup: bool,
+    /// `[downloads.routes]`: extension (`gba`) or glob (`*.pbp`) → folder the
+    /// matching files are saved in instead of `dir`; relative folders are taken
+    /// inside it. See [`crate::data::downloads`].
+    pub routes: BTreeMap<String, String>,
 }
 
 impl Default for DownloadsConfig {
@@ -37,10 +54,28 @@ impl Default for DownloadsConfig {
             ]
             .map(str::to_string)
             .to_vec(),
+            max_concurrent: 2,
+            extract: ExtractMode::Off,
+            delete_archive: false,
+            checksum_lookup: true,
+            routes: BTreeMap::new(),
         }
     }
 }
 
+token_enum! {
+    /// What happens to a finished `.zip` download (`[downloads] extract`).
+    pub enum ExtractMode {
+        default Off;
+        /// Leave archives as they are (the default).
+        Off => "off", "Off",
+        /// Unpack into a new folder beside the archive, named after it.
+        Folder => "folder", "Into a new folder",
+        /// Unpack into the folder the archive is in (its routed destination).
+        Here => "here", "Next to the archive",
+    }
+}
+
 impl DownloadsConfig {
     /// Resolve the save directory (with a trailing `/`): the configured one, else
     /// the system download folder, else `downloads/` in the user data dir.
@@ -33,6 +33,10 @@ pub struct InputConfig {
     /// (and stick) hop the selection again and the buttons keep their normal
     /// meaning. See [`crate::overlay::hints`].
     pub hint_badges: bool,
+    /// Hosts whose tabs enter game mode on their own when they load (the
+    /// controller goes to the page's Gamepad API; see the `game_mode` action).
+    /// A host also covers its subdomains.
+    pub game_sites: Vec<String>,
 }
 
 impl Default for InputConfig {
@@ -48,6 +52,7 @@ impl Default for InputConfig {
             hold_ms: 400,
             cursor_mode: CursorMode::Mouse,
             hint_badges: true,
+            game_sites: Vec::new(),
         }
     }
 }
@@ -19,11 +19,14 @@ mod downloads;
 mod experimental;
 mod history;
 mod input;
+mod network;
 mod osk;
 mod paths;
 mod performance;
+mod search;
 mod token_enum;
 mod update;
+mod url_cleaning;
 mod video;
 
 pub use adblock::AdblockConfig;
@@ -32,14 +35,17 @@ pub use browser::{BrowserConfig, PageTheme};
 pub use data_saving::DataSavingConfig;
 pub use debug::DebugConfig;
 pub use display::{DisplayConfig, ToolbarPosition};
-pub use downloads::DownloadsConfig;
+pub use downloads::{DownloadsConfig, ExtractMode};
 pub use experimental::{ExperimentalConfig, ExperimentalPreset};
 pub use history::HistoryConfig;
 pub use input::{CursorMode, InputConfig};
+pub use network::NetworkConfig;
 pub use osk::OskConfig;
 pub use paths::{cache_dir, data_dir, device_scale, servo_data_dir};
 pub use performance::{MemoryProfile, PerformanceConfig};
+pub use search::{SearchEngine, SearchEngines};
 pub use update::{Channel, UpdateConfig};
+pub use url_cleaning::UrlCleaningConfig;
 pub use video::VideoConfig;
 
 #[derive(Clone, Default, Serialize, Deserialize)]
@@ -52,6 +58,8 @@ pub struct AppConfig {
     pub history: HistoryConfig,
     pub downloads: DownloadsConfig,
     pub adblock: AdblockConfig,
+    pub network: NetworkConfig,
+    pub url_cleaning: UrlCleaningConfig,
     pub performance: PerformanceConfig,
     pub data_saving: DataSavingConfig,
     pub audio: AudioConfig,
@@ -59,6 +67,8 @@ pub struct AppConfig {
     pub osk: OskConfig,
     pub debug: DebugConfig,
     pub update: UpdateConfig,
+    /// `[[search_engines]]`: keyword searches and the default-engine choices.
+    pub search_engines: SearchEngines,
 }
 
 impl AppConfig {
@@ -91,6 +101,18 @@ impl AppConfig {
         }
     }
 
+    /// The default search engine `browser.search_engine` names, if any.
+    pub fn search_engine(&self) -> Option<&SearchEngine> {
+        self.search_engines.get(&self.browser.search_engine)
+    }
+
+    /// The results page (`%s` for the query) for typed text that isn't an
+    /// address: the default engine's, else `browser.search_page`.
+    pub fn search_page(&self) -> &str {
+        self.search_engine()
+            .map_or(&self.browser.search_page, |e| &e.url)
+    }
+
     /// Best-effort write of the default config so the user has a file to edit.
     /// Failures (e.g. a read-only filesystem on the handheld) are non-fatal.
     fn write_template(&self, path: &str) {
@@ -123,6 +145,7 @@ impl AppConfig {
         use bounds as b;
 
         fix_f32("browser.page_zoom", &mut self.browser.page_zoom, b::PAGE_ZOOM);
+        self.search_engines.sanitize();
 
         fix_u32("display.width", &mut self.display.width, b::WIDTH);
         fix_u32("display.height", &mut self.display.height, b::HEIGHT);
@@ -152,6 +175,11 @@ impl AppConfig {
             &mut self.data_saving.max_images_per_page,
             b::IMAGES_PER_PAGE,
         );
+        fix_usize(
+            "downloads.max_concurrent",
+            &mut self.downloads.max_concurrent,
+            b::DOWNLOADS_CONCURRENT,
+        );
         fix_u64("adblock.update_days", &mut self.adblock.update_days, b::ADBLOCK_UPDATE_DAYS);
         fix_u32(
             "audio.max_decode_seconds",
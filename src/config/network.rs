@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// Connection settings (`[network]` in the config), shared by every path that
+/// reaches the network: Servo's page loads, downloads, the ad-block list fetch
+/// and the updater — see [`crate::net::proxy`] and [`crate::net::tls`].
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// Proxy URL: `http://host:port` or `socks5://host:port`, with optional
+    /// `user:password@` credentials. Empty connects directly.
+    pub proxy: String,
+    /// Hosts reached directly despite the proxy or DoH, resolved by the
+    /// system: a name covers its subdomains (a leading `*.` or `.` is
+    /// optional), or an exact IP address.
+    pub proxy_bypass: Vec<String>,
+    /// DNS-over-HTTPS endpoint (`https://…/dns-query`) to resolve names
+    /// through instead of the system's DNS, which stays the fallback. Unused
+    /// with a proxy, which resolves. Empty uses system DNS.
+    pub doh_url: String,
+    /// PEM files of CAs to trust on top of the public roots (an internal CA),
+    /// relative to the data dir or absolute.
+    pub extra_ca_certs: Vec<String>,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            proxy: String::new(),
+            proxy_bypass: vec!["localhost".to_string(), "127.0.0.1".to_string()],
+            doh_url: String::new(),
+            extra_ca_certs: Vec::new(),
+        }
+    }
+}
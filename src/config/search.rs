@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+/// One `[[search_engines]]` entry: a results page typed text can be sent to,
+/// either by its keyword (`w retro handheld`) or as the default engine
+/// ([`crate::config::BrowserConfig::search_engine`]).
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SearchEngine {
+    /// Shown in the settings overlay's engine picker, and what
+    /// `browser.search_engine` names.
+    pub name: String,
+    /// Typed before the query, separated by a space, to search this engine
+    /// whatever the default is. Empty: reachable only as the default.
+    #[serde(default)]
+    pub keyword: String,
+    /// The results page; `%s` is replaced with the (URL-encoded) query.
+    pub url: String,
+    /// An OpenSearch suggestions endpoint (`%s` for the query) answering with
+    /// `["query", ["suggestion", …]]`. When the default engine has one, the
+    /// address bar lists its suggestions too — which sends what's typed to the
+    /// engine as it's typed, hence off unless set.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub suggest_url: String,
+}
+
+/// The `[[search_engines]]` list. A config that has the key replaces the
+/// built-in list wholesale.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SearchEngines(pub Vec<SearchEngine>);
+
+impl Default for SearchEngines {
+    fn default() -> Self {
+        let engine = |name: &str, keyword: &str, url: &str| SearchEngine {
+            name: name.to_string(),
+            keyword: keyword.to_string(),
+            url: url.to_string(),
+            suggest_url: String::new(),
+        };
+        Self(vec![
+            engine("DuckDuckGo", "d", "https://duckduckgo.com/?q=%s"),
+            engine("Google", "g", "https://www.google.com/search?q=%s"),
+            engine(
+                "Wikipedia",
+                "w",
+                "https://en.wikipedia.org/wiki/Special:Search?search=%s",
+            ),
+            engine("GitHub", "gh", "https://github.com/search?q=%s"),
+            engine(
+                "YouTube",
+                "yt",
+                "https://www.youtube.com/results?search_query=%s",
+            ),
+        ])
+    }
+}
+
+impl SearchEngines {
+    /// The engine called `name` (case-insensitive).
+    pub fn get(&self, name: &str) -> Option<&SearchEngine> {
+        self.0.iter().find(|e| e.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Split keyword-led `text` (`gh servo`) into its engine and the query
+    /// after the keyword. `None` when the first word is no engine's keyword or
+    /// nothing follows it.
+    pub fn by_keyword<'a>(&self, text: &'a str) -> Option<(&SearchEngine, &'a str)> {
+        let (word, query) = text.trim().split_once(char::is_whitespace)?;
+        let query = query.trim();
+        if query.is_empty() {
+            return None;
+        }
+        self.0
+            .iter()
+            .find(|e| !e.keyword.is_empty() && e.keyword.eq_ignore_ascii_case(word))
+            .map(|e| (e, query))
+    }
+
+    /// Drop entries that can't search — no name or a URL without `%s` — and
+    /// keyword clashes (the first entry keeps it). Logs what it drops.
+    pub(super) fn sanitize(&mut self) {
+        let mut keywords: Vec<String> = Vec::new();
+        self.0.retain_mut(|e| {
+            if e.name.trim().is_empty() || !e.url.contains("%s") {
+                log::warn!(
+                    "config: search engine `{}` needs a name and a `%s` in its url; skipping",
+                    e.name
+                );
+                return false;
+            }
+            let keyword = e.keyword.to_lowercase();
+            if !keyword.is_empty() {
+                if keywords.contains(&keyword) {
+                    log::warn!(
+                        "config: search keyword `{}` is taken; `{}` keeps no keyword",
+                        e.keyword,
+                        e.name
+                    );
+                    e.keyword.clear();
+                } else {
+                    keywords.push(keyword);
+                }
+            }
+            true
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Only a known keyword with a query after it picks an engine; the query
+    /// keeps its own spacing.
+    #[test]
+    fn splits_keyword_queries() {
+        let engines = SearchEngines::default();
+        let (engine, query) = engines.by_keyword("w  retro handheld ").unwrap();
+        assert_eq!(engine.name, "Wikipedia");
+        assert_eq!(query, "retro handheld");
+        assert_eq!(engines.by_keyword("GH servo").unwrap().0.name, "GitHub");
+        assert!(engines.by_keyword("w").is_none());
+        assert!(engines.by_keyword("w ").is_none());
+        assert!(engines.by_keyword("wiki servo").is_none());
+    }
+}
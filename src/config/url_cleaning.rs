@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+/// Tracking-parameter removal (`[url_cleaning]` in the config): applied to
+/// top-level navigations and to the URLs history, bookmarks and the dial
+/// store. See [`crate::data::clean_url`].
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UrlCleaningConfig {
+    pub enabled: bool,
+    /// Query parameters to drop, by name (case-insensitive); a trailing `*`
+    /// matches a prefix (`utm_*`).
+    pub params: Vec<String>,
+    /// A rule list in the ClearURLs JSON format (`data.min.json`), relative to
+    /// the data dir or absolute; its per-site rules and redirections apply on
+    /// top of `params`. Empty for none.
+    pub rules: String,
+}
+
+impl Default for UrlCleaningConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            params: [
+                "utm_*", "fbclid", "gclid", "dclid", "gbraid", "wbraid", "msclkid", "yclid",
+                "mc_eid", "igshid", "_hsenc", "_hsmi",
+            ]
+            .map(String::from)
+            .to_vec(),
+            rules: String::new(),
+        }
+    }
+}
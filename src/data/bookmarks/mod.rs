@@ -0,0 +1,714 @@
+//! Saved bookmarks: a tree of folders holding pages (URL, title, tags) persisted
+//! to `bookmarks.toml` in the user data dir, plus where the menu's Bookmarks
+//! section stands in it — the open folder and its highlighted row. The menu (see
+//! [`crate::overlay::menu`]) owns whether the overlay is shown and the edit
+//! sheet; this owns the tree, the drill-down and the selection. Rendered by
+//! [`crate::ui`], driven by the central router. Other browsers' bookmarks come
+//! and go as `bookmarks.html` files (see [`netscape`]).
+
+pub mod netscape;
+
+use super::clean_url;
+use super::history;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// A saved page. The title starts as the one the page reported and is the
+/// user's to edit from then on; tags are free-form labels shown on its row.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// When it was saved (unix seconds); `0` if unknown (files from before
+    /// this was kept).
+    #[serde(default)]
+    pub added: u64,
+}
+
+/// A folder of pages and subfolders, each in the user's order. The menu lists
+/// the subfolders first.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Folder {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub pages: Vec<Bookmark>,
+    #[serde(default)]
+    pub folders: Vec<Folder>,
+}
+
+impl Folder {
+    /// Subfolders plus pages — the entry rows the menu lists for this folder.
+    pub fn len(&self) -> usize {
+        self.folders.len() + self.pages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The entry at `index` in menu order: subfolders, then pages.
+    pub fn entry(&self, index: usize) -> Option<Entry<'_>> {
+        match index.checked_sub(self.folders.len()) {
+            None => self.folders.get(index).map(Entry::Folder),
+            Some(i) => self.pages.get(i).map(Entry::Page),
+        }
+    }
+
+    /// Pages in this folder and every folder below it.
+    pub fn page_count(&self) -> usize {
+        self.pages.len() + self.folders.iter().map(Folder::page_count).sum::<usize>()
+    }
+
+    fn contains(&self, url: &str) -> bool {
+        self.pages.iter().any(|p| p.url == url) || self.folders.iter().any(|f| f.contains(url))
+    }
+
+    /// Drop every page saved at `url`, here and below.
+    fn remove_url(&mut self, url: &str) {
+        self.pages.retain(|p| p.url != url);
+        for folder in &mut self.folders {
+            folder.remove_url(url);
+        }
+    }
+
+    fn find(&self, url: &str) -> Option<&Bookmark> {
+        self.pages
+            .iter()
+            .find(|p| p.url == url)
+            .or_else(|| self.folders.iter().find_map(|f| f.find(url)))
+    }
+
+    fn pages_mut<'a>(&'a mut self, out: &mut Vec<&'a mut Bookmark>) {
+        out.extend(self.pages.iter_mut());
+        for folder in &mut self.folders {
+            folder.pages_mut(out);
+        }
+    }
+
+    fn collect_pages<'a>(&'a self, out: &mut Vec<&'a Bookmark>) {
+        out.extend(self.pages.iter());
+        for folder in &self.folders {
+            folder.collect_pages(out);
+        }
+    }
+
+    fn collect_urls(&self, out: &mut HashSet<String>) {
+        out.extend(self.pages.iter().map(|p| p.url.clone()));
+        for folder in &self.folders {
+            folder.collect_urls(out);
+        }
+    }
+
+    /// The folder `path` (subfolder indices) leads to from here.
+    fn at(&self, path: &[usize]) -> Option<&Folder> {
+        path.iter().try_fold(self, |f, &i| f.folders.get(i))
+    }
+
+    fn at_mut(&mut self, path: &[usize]) -> Option<&mut Folder> {
+        path.iter().try_fold(self, |f, &i| f.folders.get_mut(i))
+    }
+
+    /// Every folder here and below as `(path, "A › B")`, depth-first.
+    fn collect_paths(
+        &self,
+        path: &mut Vec<usize>,
+        label: &str,
+        out: &mut Vec<(Vec<usize>, String)>,
+    ) {
+        for (i, folder) in self.folders.iter().enumerate() {
+            path.push(i);
+            let label = if label.is_empty() {
+                folder.name.clone()
+            } else {
+                format!("{label} › {}", folder.name)
+            };
+            out.push((path.clone(), label.clone()));
+            folder.collect_paths(path, &label, out);
+            path.pop();
+        }
+    }
+}
+
+/// One entry row of a folder.
+#[derive(Clone, Copy)]
+pub enum Entry<'a> {
+    Folder(&'a Folder),
+    Page(&'a Bookmark),
+}
+
+/// On-disk shape: the top-level folder's contents (a TOML table can't be a bare
+/// array). Files from before folders and titles hold a bare `urls` array
+/// instead; it's read, never written, so the next save upgrades the file with
+/// every entry kept.
+#[derive(Default, Serialize, Deserialize)]
+struct Store {
+    #[serde(default)]
+    pages: Vec<Bookmark>,
+    #[serde(default)]
+    folders: Vec<Folder>,
+    #[serde(default, skip_serializing)]
+    urls: Vec<String>,
+}
+
+impl Store {
+    /// The tree this file holds, legacy URLs appended as untitled top-level pages.
+    fn into_root(self) -> Folder {
+        let mut root = Folder {
+            name: String::new(),
+            pages: self.pages,
+            folders: self.folders,
+        };
+        for url in self.urls {
+            if !root.contains(&url) {
+                root.pages.push(Bookmark {
+                    url,
+                    title: String::new(),
+                    tags: Vec::new(),
+                    added: 0,
+                });
+            }
+        }
+        root
+    }
+}
+
+pub struct Bookmarks {
+    root: Folder,
+    /// Subfolder indices from the top down to the folder the menu has open.
+    path: Vec<usize>,
+    /// Highlighted row in the open folder. Row 0 is the "+ New folder" reserved
+    /// row, so the cursor carries a reserve of 1.
+    cursor: super::ListCursor,
+    /// Unsaved title changes, for [`Self::flush`].
+    dirty: bool,
+}
+
+impl Bookmarks {
+    /// Load the saved tree (missing/invalid file → empty).
+    pub fn load() -> Self {
+        Self {
+            root: super::load_toml::<Store>("bookmarks.toml").into_root(),
+            path: Vec::new(),
+            cursor: super::ListCursor::new(1),
+            dirty: false,
+        }
+    }
+
+    /// Best-effort persist; failures are logged, not fatal, and leave `dirty`
+    /// set for the next [`Self::flush`].
+    fn save(&mut self) {
+        let store = Store {
+            pages: self.root.pages.clone(),
+            folders: self.root.folders.clone(),
+            urls: Vec::new(),
+        };
+        if super::save_toml("bookmarks.toml", &store, "bookmarks") {
+            self.dirty = false;
+        }
+    }
+
+    /// Write a deferred title change, if any (see [`crate::app`]'s flush
+    /// throttle).
+    pub fn flush(&mut self) {
+        if self.dirty {
+            self.save();
+        }
+    }
+
+    /// Whether nothing at all is saved.
+    pub fn is_empty(&self) -> bool {
+        self.root.is_empty()
+    }
+
+    /// The folder the menu has open.
+    pub fn folder(&self) -> &Folder {
+        // `path` only ever holds indices that resolved when it was walked, and
+        // every removal re-validates it (see `fix_path`).
+        self.root.at(&self.path).unwrap_or(&self.root)
+    }
+
+    fn folder_mut(&mut self) -> &mut Folder {
+        if self.root.at(&self.path).is_none() {
+            self.path.clear();
+        }
+        self.root.at_mut(&self.path).expect("path resolves")
+    }
+
+    /// Names from the top folder down to the open one (empty at the top).
+    pub fn path_names(&self) -> Vec<&str> {
+        let mut folder = &self.root;
+        let mut names = Vec::with_capacity(self.path.len());
+        for &i in &self.path {
+            folder = &folder.folders[i];
+            names.push(folder.name.as_str());
+        }
+        names
+    }
+
+    /// Whether a subfolder (not the top level) is open.
+    pub fn in_folder(&self) -> bool {
+        !self.path.is_empty()
+    }
+
+    /// The highlighted row: 0 is "+ New folder", the open folder's entries
+    /// follow at `1..=len`.
+    pub fn selected(&self) -> usize {
+        self.cursor.selected()
+    }
+
+    /// Whether the "+ New folder" row is highlighted.
+    pub fn new_folder_selected(&self) -> bool {
+        self.cursor.selected() == 0
+    }
+
+    /// Index of the highlighted entry in the open folder, if an entry is.
+    pub fn selected_index(&self) -> Option<usize> {
+        self.cursor
+            .entry_index()
+            .filter(|&i| i < self.folder().len())
+    }
+
+    pub fn selected_entry(&self) -> Option<Entry<'_>> {
+        self.selected_index().and_then(|i| self.folder().entry(i))
+    }
+
+    /// Every saved page, whatever folder it's in.
+    pub fn pages(&self) -> Vec<&Bookmark> {
+        let mut out = Vec::new();
+        self.root.collect_pages(&mut out);
+        out
+    }
+
+    /// Whether `url` is saved in any folder.
+    pub fn contains(&self, url: &str) -> bool {
+        self.root.contains(url)
+    }
+
+    /// Add `url` (titled `title`, possibly empty) to the top folder if it isn't
+    /// saved anywhere, otherwise remove it from everywhere; persists either way.
+    /// Saved without its tracking parameters (see [`clean_url`]), unless that
+    /// URL is saved already.
+    pub fn toggle(&mut self, url: &str, title: &str) {
+        if self.root.contains(url) {
+            self.root.remove_url(url);
+            self.clamp_selected();
+        } else {
+            let url = clean_url::clean(url);
+            if self.root.contains(&url) {
+                return;
+            }
+            self.root.pages.push(Bookmark {
+                url: url.into_owned(),
+                title: title.to_string(),
+                tags: Vec::new(),
+                added: history::now_unix(),
+            });
+        }
+        self.save();
+    }
+
+    /// The saved title of `url`, if bookmarked with one.
+    pub fn title_of(&self, url: &str) -> Option<&str> {
+        self.root
+            .find(url)
+            .map(|p| p.title.as_str())
+            .filter(|t| !t.is_empty())
+    }
+
+    /// Adopt the title a bookmarked page reported, where it has none yet — a
+    /// title the user edited (or the one it was saved with) stays. A change marks
+    /// the bookmarks dirty (see [`Self::flush`]).
+    pub fn set_title(&mut self, url: &str, title: &str) {
+        let mut pages = Vec::new();
+        self.root.pages_mut(&mut pages);
+        let mut changed = false;
+        for page in pages {
+            if page.url == url && page.title.is_empty() {
+                page.title = title.to_string();
+                changed = true;
+            }
+        }
+        if changed {
+            self.dirty = true;
+        }
+    }
+
+    /// Back to the top folder with the first entry highlighted (called when the
+    /// menu opens).
+    pub fn reset(&mut self) {
+        self.path.clear();
+        self.cursor.reset(self.root.len());
+    }
+
+    /// Move the highlight by `dy` rows, clamped to the open folder.
+    pub fn move_sel(&mut self, dy: i32) {
+        let len = self.folder().len();
+        // An empty folder still has its "+ New folder" row; nothing to move to.
+        self.cursor.move_sel(dy, len);
+    }
+
+    pub fn selected_url(&self) -> Option<String> {
+        match self.selected_entry()? {
+            Entry::Page(page) => Some(page.url.clone()),
+            Entry::Folder(_) => None,
+        }
+    }
+
+    /// Open the subfolder at entry `index` of the open folder; returns whether
+    /// it was one.
+    pub fn enter(&mut self, index: usize) -> bool {
+        if index >= self.folder().folders.len() {
+            return false;
+        }
+        self.path.push(index);
+        self.cursor.reset(self.folder().len());
+        true
+    }
+
+    /// Go up to the parent folder, highlighting the one just left; returns
+    /// false at the top.
+    pub fn leave(&mut self) -> bool {
+        let Some(index) = self.path.pop() else {
+            return false;
+        };
+        self.cursor.reset(self.folder().len());
+        self.cursor.move_sel(index as i32, self.folder().len());
+        true
+    }
+
+    /// Add a folder named `name` after the open folder's subfolders and
+    /// highlight it; persists. Returns its entry index.
+    pub fn create_folder(&mut self, name: &str) -> usize {
+        let folder = self.folder_mut();
+        folder.folders.push(Folder {
+            name: name.to_string(),
+            ..Folder::default()
+        });
+        let index = folder.folders.len() - 1;
+        self.select(index);
+        self.save();
+        index
+    }
+
+    /// Highlight entry `index` of the open folder.
+    fn select(&mut self, index: usize) {
+        let len = self.folder().len();
+        self.cursor.reset(len);
+        self.cursor.move_sel(index as i32, len);
+    }
+
+    /// Move the highlighted entry `delta` places among its own kind (folders
+    /// among folders, pages among pages), the highlight following; persists.
+    pub fn move_selected(&mut self, delta: i32) {
+        let Some(index) = self.selected_index() else {
+            return;
+        };
+        let folder = self.folder_mut();
+        let subfolders = folder.folders.len();
+        let (start, len) = if index < subfolders {
+            (0, subfolders)
+        } else {
+            (subfolders, folder.pages.len())
+        };
+        let at = index - start;
+        let to = at as i32 + delta;
+        if to < 0 || to >= len as i32 {
+            return;
+        }
+        let to = to as usize;
+        if index < subfolders {
+            folder.folders.swap(at, to);
+        } else {
+            folder.pages.swap(at, to);
+        }
+        self.select(start + to);
+        self.save();
+    }
+
+    /// Rename the folder at entry `index` of the open folder; persists.
+    pub fn rename_folder(&mut self, index: usize, name: &str) {
+        if let Some(folder) = self.folder_mut().folders.get_mut(index) {
+            folder.name = name.to_string();
+            self.save();
+        }
+    }
+
+    /// Replace the title and tags of the page at entry `index` of the open
+    /// folder; persists.
+    pub fn edit_page(&mut self, index: usize, title: &str, tags: Vec<String>) {
+        let folder = self.folder_mut();
+        let Some(page) = index
+            .checked_sub(folder.folders.len())
+            .and_then(|i| folder.pages.get_mut(i))
+        else {
+            return;
+        };
+        page.title = title.to_string();
+        page.tags = tags;
+        self.save();
+    }
+
+    /// Every folder as `(path, "A › B")`, the top level first as `(/, "")`:
+    /// the destinations a page can move to.
+    pub fn folder_paths(&self) -> Vec<(Vec<usize>, String)> {
+        let mut out = vec![(Vec::new(), String::new())];
+        self.root.collect_paths(&mut Vec::new(), "", &mut out);
+        out
+    }
+
+    /// The open folder's path, as listed by [`Self::folder_paths`].
+    pub fn path(&self) -> &[usize] {
+        &self.path
+    }
+
+    /// Move the page at entry `index` of the open folder to the end of the
+    /// folder at `dest`; persists. The open folder stays open.
+    pub fn move_page(&mut self, index: usize, dest: &[usize]) {
+        if dest == self.path.as_slice() || self.root.at(dest).is_none() {
+            return;
+        }
+        let folder = self.folder_mut();
+        let Some(i) = index
+            .checked_sub(folder.folders.len())
+            .filter(|&i| i < folder.pages.len())
+        else {
+            return;
+        };
+        let page = folder.pages.remove(i);
+        self.root
+            .at_mut(dest)
+            .expect("checked above")
+            .pages
+            .push(page);
+        self.clamp_selected();
+        self.save();
+    }
+
+    /// Remove entry `index` of the open folder (if in range), a folder with
+    /// everything in it; persists.
+    pub fn remove(&mut self, index: usize) {
+        let folder = self.folder_mut();
+        let subfolders = folder.folders.len();
+        if index < subfolders {
+            folder.folders.remove(index);
+        } else if index - subfolders < folder.pages.len() {
+            folder.pages.remove(index - subfolders);
+        } else {
+            return;
+        }
+        self.clamp_selected();
+        self.save();
+    }
+
+    /// Merge an imported tree into the saved one: at each level folders match
+    /// by name (unmatched ones are added), and pages whose URL is already saved
+    /// anywhere are skipped. Persists; returns how many pages were added.
+    pub fn merge(&mut self, imported: Folder) -> usize {
+        let mut seen = HashSet::new();
+        self.root.collect_urls(&mut seen);
+        let added = merge_folder(&mut self.root, imported, &mut seen);
+        self.clamp_selected();
+        self.save();
+        added
+    }
+
+    /// Merge the `bookmarks.html` at `path` (see [`Self::merge`]); returns how
+    /// many pages were added, or a message for the user.
+    pub fn import_html(&mut self, path: &str) -> Result<usize, String> {
+        let bytes = std::fs::read(path).map_err(|e| format!("{path}: {e}"))?;
+        let tree = netscape::parse(&String::from_utf8_lossy(&bytes));
+        if tree.page_count() == 0 {
+            return Err(format!("{path}: no bookmarks found"));
+        }
+        Ok(self.merge(tree))
+    }
+
+    /// Write everything to `path` as a `bookmarks.html`; returns how many pages
+    /// were written, or a message for the user.
+    pub fn export_html(&self, path: &str) -> Result<usize, String> {
+        std::fs::write(path, netscape::export(&self.root)).map_err(|e| format!("{path}: {e}"))?;
+        Ok(self.root.page_count())
+    }
+
+    fn clamp_selected(&mut self) {
+        // A removal elsewhere in the tree can't shorten `path` itself, but keep
+        // it honest: fall back up to the deepest folder that still exists.
+        while self.root.at(&self.path).is_none() {
+            self.path.pop();
+        }
+        self.cursor.clamp(self.folder().len());
+    }
+}
+
+/// Add `from`'s pages not in `seen` (recording them there) and its folders to
+/// `into`, recursing into same-named folders; returns how many pages were added.
+fn merge_folder(into: &mut Folder, from: Folder, seen: &mut HashSet<String>) -> usize {
+    let mut added = 0;
+    for page in from.pages {
+        if seen.insert(page.url.clone()) {
+            into.pages.push(page);
+            added += 1;
+        }
+    }
+    for folder in from.folders {
+        let i = match into.folders.iter().position(|f| f.name == folder.name) {
+            Some(i) => i,
+            None => {
+                into.folders.push(Folder {
+                    name: folder.name.clone(),
+                    ..Folder::default()
+                });
+                into.folders.len() - 1
+            }
+        };
+        added += merge_folder(&mut into.folders[i], folder, seen);
+    }
+    added
+}
+
+/// Where the Settings rows import from and export to: `bookmarks.html` in the
+/// user data dir.
+pub fn html_path() -> String {
+    super::data_path("bookmarks.html")
+}
+
+/// Split a comma-separated tag field into tags: trimmed, empties and repeats
+/// dropped, in the order typed.
+pub fn parse_tags(text: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in text.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bookmarks(root: Folder) -> Bookmarks {
+        let mut bookmarks = Bookmarks {
+            root,
+            path: Vec::new(),
+            cursor: super::super::ListCursor::new(1),
+            dirty: false,
+        };
+        bookmarks.reset();
+        bookmarks
+    }
+
+    /// A file from before folders and titles loads with every URL kept, and
+    /// the rewrite drops the old array.
+    #[test]
+    fn legacy_urls_upgrade() {
+        let text = "urls = [\"https://a.test/\", \"https://b.test/\"]\n";
+        let root = toml::from_str::<Store>(text).unwrap().into_root();
+        assert_eq!(root.pages.len(), 2);
+        assert_eq!(root.pages[1].url, "https://b.test/");
+        assert!(root.pages[1].title.is_empty());
+
+        let store = Store {
+            pages: root.pages,
+            folders: vec![Folder {
+                name: "Docs".to_string(),
+                ..Folder::default()
+            }],
+            urls: Vec::new(),
+        };
+        let text = toml::to_string_pretty(&store).unwrap();
+        assert!(!text.contains("urls"));
+        let back = toml::from_str::<Store>(&text).unwrap().into_root();
+        assert_eq!(back.pages.len(), 2);
+        assert_eq!(back.folders[0].name, "Docs");
+    }
+
+    /// Entering a folder and reordering inside it stays among the same kind;
+    /// leaving lands back on the folder's row.
+    #[test]
+    fn drill_down_and_reorder() {
+        let page = |url: &str| Bookmark {
+            url: url.to_string(),
+            title: String::new(),
+            tags: Vec::new(),
+            added: 0,
+        };
+        let mut b = bookmarks(Folder {
+            name: String::new(),
+            pages: vec![page("https://top.test/")],
+            folders: vec![Folder {
+                name: "Work".to_string(),
+                pages: vec![page("https://a.test/"), page("https://b.test/")],
+                folders: Vec::new(),
+            }],
+        });
+        assert!(b.enter(0));
+        assert_eq!(b.path_names(), ["Work"]);
+        b.move_sel(1); // the second page
+        b.move_selected(-1);
+        assert_eq!(b.folder().pages[0].url, "https://b.test/");
+        assert_eq!(b.selected_url().as_deref(), Some("https://b.test/"));
+        b.move_selected(-1); // already first: no-op
+        assert_eq!(b.folder().pages[0].url, "https://b.test/");
+        assert!(b.leave());
+        assert!(matches!(b.selected_entry(), Some(Entry::Folder(_))));
+        assert!(!b.leave());
+    }
+
+    /// An import adds only pages not saved anywhere yet, filing them into the
+    /// same-named folder.
+    #[test]
+    fn merge_skips_saved_urls() {
+        let page = |url: &str| Bookmark {
+            url: url.to_string(),
+            title: String::new(),
+            tags: Vec::new(),
+            added: 0,
+        };
+        let folder = |name: &str, pages| Folder {
+            name: name.to_string(),
+            pages,
+            folders: Vec::new(),
+        };
+        let mut b = bookmarks(Folder {
+            name: String::new(),
+            pages: vec![page("https://a.test/")],
+            folders: vec![folder("Work", vec![page("https://b.test/")])],
+        });
+        let imported = Folder {
+            name: String::new(),
+            pages: vec![page("https://b.test/")],
+            folders: vec![
+                folder(
+                    "Work",
+                    vec![page("https://a.test/"), page("https://c.test/")],
+                ),
+                folder(
+                    "Fun",
+                    vec![page("https://c.test/"), page("https://d.test/")],
+                ),
+            ],
+        };
+        let mut seen = HashSet::new();
+        b.root.collect_urls(&mut seen);
+        assert_eq!(merge_folder(&mut b.root, imported, &mut seen), 2);
+        assert_eq!(b.root.pages.len(), 1);
+        assert_eq!(b.root.folders[0].pages[1].url, "https://c.test/");
+        assert_eq!(b.root.folders[1].name, "Fun");
+        assert_eq!(b.root.folders[1].pages[0].url, "https://d.test/");
+    }
+
+    #[test]
+    fn tags_are_trimmed_and_deduplicated() {
+        assert_eq!(
+            parse_tags(" rust, docs ,,Rust,ref"),
+            ["rust", "docs", "ref"]
+        );
+        assert!(parse_tags(" , ").is_empty());
+    }
+}
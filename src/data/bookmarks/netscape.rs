@@ -0,0 +1,310 @@
+//! The Netscape bookmark file format — the `bookmarks.html` every desktop
+//! browser imports and exports. [`export`] writes a bookmark tree as one;
+//! [`parse`] reads one back, tolerantly: it's a loose HTML dialect (unclosed
+//! `<DT>`/`<p>`, any tag case, attributes in any order), so this scans for the
+//! few tags that carry data rather than parsing HTML properly.
+//!
+//! Folders are `<DT><H3>name</H3>` followed by their own `<DL>` list; pages are
+//! `<DT><A HREF=… ADD_DATE=… TAGS=…>title</A>`. Everything else (icons,
+//! descriptions, toolbar markers) is ignored.
+
+use super::{parse_tags, Bookmark, Folder};
+use std::fmt::Write;
+
+const HEADER: &str = "<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<!-- This is an automatically generated file.
+     It will be read and overwritten.
+     DO NOT EDIT! -->
+<META HTTP-EQUIV=\"Content-Type\" CONTENT=\"text/html; charset=UTF-8\">
+<TITLE>Bookmarks</TITLE>
+<H1>Bookmarks</H1>
+";
+
+/// `root` as a complete `bookmarks.html` document.
+pub fn export(root: &Folder) -> String {
+    let mut out = String::from(HEADER);
+    write_list(&mut out, root, 0);
+    out
+}
+
+fn write_list(out: &mut String, folder: &Folder, depth: usize) {
+    let pad = "    ".repeat(depth);
+    let _ = writeln!(out, "{pad}<DL><p>");
+    for sub in &folder.folders {
+        let _ = writeln!(out, "{pad}    <DT><H3>{}</H3>", escape(&sub.name));
+        write_list(out, sub, depth + 1);
+    }
+    for page in &folder.pages {
+        let _ = write!(out, "{pad}    <DT><A HREF=\"{}\"", escape(&page.url));
+        if page.added != 0 {
+            let _ = write!(out, " ADD_DATE=\"{}\"", page.added);
+        }
+        if !page.tags.is_empty() {
+            let _ = write!(out, " TAGS=\"{}\"", escape(&page.tags.join(",")));
+        }
+        let _ = writeln!(out, ">{}</A>", escape(&page.title));
+    }
+    let _ = writeln!(out, "{pad}</DL><p>");
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Read a `bookmarks.html` into a tree. The file's top-level list becomes the
+/// returned (unnamed) folder; anything malformed is skipped, never an error —
+/// an unrecognised file just yields an empty tree.
+pub fn parse(html: &str) -> Folder {
+    // ASCII lowercasing keeps byte offsets, so positions found in `lower` index
+    // `html` too.
+    let lower = html.to_ascii_lowercase();
+    // Folders being filled, innermost last; the bottom one is the result.
+    let mut open = vec![Folder::default()];
+    // One flag per `<DL>` seen and not yet closed: whether it opened a folder
+    // (the top-level list and stray lists don't).
+    let mut lists: Vec<bool> = Vec::new();
+    // A folder name waiting for its `<DL>`.
+    let mut heading: Option<String> = None;
+    let mut pos = 0;
+    while let Some(i) = lower[pos..].find('<') {
+        let start = pos + i;
+        let Some(len) = lower[start..].find('>') else {
+            break;
+        };
+        let end = start + len + 1;
+        let tag = &html[start..end];
+        pos = end;
+        match tag_name(&lower[start..end]) {
+            "h3" => {
+                flush_heading(&mut open, &mut heading);
+                let (text, next) = text_until(html, &lower, end, "</h3");
+                heading = Some(text);
+                pos = next;
+            }
+            "a" => {
+                flush_heading(&mut open, &mut heading);
+                let (title, next) = text_until(html, &lower, end, "</a");
+                pos = next;
+                let Some(url) = attr(tag, "href").filter(|u| importable(u)) else {
+                    continue;
+                };
+                let page = Bookmark {
+                    url,
+                    title,
+                    tags: attr(tag, "tags").map_or_else(Vec::new, |t| parse_tags(&t)),
+                    added: attr(tag, "add_date")
+                        .and_then(|d| d.trim().parse().ok())
+                        .unwrap_or(0),
+                };
+                open.last_mut().expect("root").pages.push(page);
+            }
+            "dl" => {
+                let name = heading.take();
+                lists.push(name.is_some());
+                if let Some(name) = name {
+                    open.push(Folder {
+                        name,
+                        ..Folder::default()
+                    });
+                }
+            }
+            "/dl" => {
+                flush_heading(&mut open, &mut heading);
+                if lists.pop() == Some(true) {
+                    close_folder(&mut open);
+                }
+            }
+            _ => {}
+        }
+    }
+    flush_heading(&mut open, &mut heading);
+    while open.len() > 1 {
+        close_folder(&mut open);
+    }
+    open.pop().expect("root")
+}
+
+/// A heading that never got its `<DL>` is an empty folder.
+fn flush_heading(open: &mut [Folder], heading: &mut Option<String>) {
+    if let Some(name) = heading.take() {
+        open.last_mut().expect("root").folders.push(Folder {
+            name,
+            ..Folder::default()
+        });
+    }
+}
+
+/// Pop the innermost open folder into its parent.
+fn close_folder(open: &mut Vec<Folder>) {
+    let folder = open.pop().expect("a subfolder");
+    open.last_mut().expect("root").folders.push(folder);
+}
+
+/// The lowercase name of the tag source `tag` (`<a href…>` → `a`, `</DL>` →
+/// `/dl`).
+fn tag_name(tag: &str) -> &str {
+    tag[1..]
+        .split(|c: char| c.is_ascii_whitespace() || c == '>')
+        .next()
+        .unwrap_or("")
+}
+
+/// The decoded, trimmed text from `from` up to the closing tag `close` (or the
+/// next tag, if it's missing), and where scanning resumes.
+fn text_until(html: &str, lower: &str, from: usize, close: &str) -> (String, usize) {
+    let end = lower[from..]
+        .find(close)
+        .or_else(|| lower[from..].find('<'))
+        .map_or(html.len(), |i| from + i);
+    (decode(html[from..end].trim()), end)
+}
+
+/// The entity-decoded value of attribute `name` (lowercase) in the tag source
+/// `tag`, quoted or not.
+fn attr(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut from = 0;
+    while let Some(i) = lower[from..].find(name) {
+        let start = from + i;
+        from = start + name.len();
+        // A whole attribute name (`HREF`, not the tail of `DATAHREF`) and an `=`.
+        let rest = lower[from..].trim_start();
+        if !lower[..start].ends_with(|c: char| c.is_ascii_whitespace()) || !rest.starts_with('=') {
+            continue;
+        }
+        let value = tag[tag.len() - rest.len() + 1..].trim_start();
+        let raw = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or(""),
+            _ => value
+                .split(|c: char| c.is_ascii_whitespace() || c == '>')
+                .next()
+                .unwrap_or(""),
+        };
+        return Some(decode(raw));
+    }
+    None
+}
+
+/// Whether a bookmarked URL is worth keeping: not bookmarklets or Firefox's
+/// `place:` smart-folder queries, which mean nothing here.
+fn importable(url: &str) -> bool {
+    let lower = url.trim_start().to_ascii_lowercase();
+    !lower.is_empty() && !lower.starts_with("javascript:") && !lower.starts_with("place:")
+}
+
+/// Decode the character references browsers write: the named XML five (plus
+/// `&nbsp;`) and numeric ones. Anything else is kept verbatim.
+fn decode(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(i) = rest.find('&') {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+        let decoded = rest
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| Some((end, entity(&rest[1..end])?)));
+        match decoded {
+            Some((end, c)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn entity(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        _ => {
+            let num = name.strip_prefix('#')?;
+            let code = match num.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => num.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// What's exported reads back as the same tree.
+    #[test]
+    fn export_roundtrips() {
+        let root = Folder {
+            name: String::new(),
+            pages: vec![Bookmark {
+                url: "https://a.test/?q=1&r=2".to_string(),
+                title: "Fish & \"chips\" <deluxe>".to_string(),
+                tags: vec!["food".to_string(), "uk".to_string()],
+                added: 1_700_000_000,
+            }],
+            folders: vec![Folder {
+                name: "Work".to_string(),
+                pages: vec![Bookmark {
+                    url: "https://b.test/".to_string(),
+                    title: String::new(),
+                    tags: Vec::new(),
+                    added: 0,
+                }],
+                folders: vec![Folder {
+                    name: "Empty".to_string(),
+                    ..Folder::default()
+                }],
+            }],
+        };
+        assert!(parse(&export(&root)) == root);
+    }
+
+    /// A typical browser export: mixed tag case, unclosed `<DT>`s, icons,
+    /// toolbar markers, a bookmarklet and numeric entities.
+    #[test]
+    fn parses_browser_export() {
+        let html = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<META HTTP-EQUIV="Content-Type" CONTENT="text/html; charset=UTF-8">
+<TITLE>Bookmarks</TITLE>
+<H1>Bookmarks</H1>
+<DL><p>
+    <DT><H3 ADD_DATE="1600000000" PERSONAL_TOOLBAR_FOLDER="true">Bookmarks bar</H3>
+    <DL><p>
+        <dt><a href="https://news.test/" add_date="1650000000" icon="data:image/png;base64,AAAA">News &#8211; daily</a>
+        <DT><A HREF="javascript:alert(1)">Bookmarklet</A>
+        <DT><H3>Nested</H3>
+        <DL><p>
+            <DT><A HREF='https://deep.test/' TAGS="a, b">Deep</A>
+        </DL><p>
+    </DL><p>
+    <DT><A HREF="https://top.test/">Top</A>
+    <DD>A description
+</DL><p>
+"#;
+        let root = parse(html);
+        assert_eq!(root.pages.len(), 1);
+        assert_eq!(root.pages[0].title, "Top");
+        let bar = &root.folders[0];
+        assert_eq!(bar.name, "Bookmarks bar");
+        assert_eq!(bar.pages.len(), 1);
+        assert_eq!(bar.pages[0].title, "News – daily");
+        assert_eq!(bar.pages[0].added, 1_650_000_000);
+        let nested = &bar.folders[0];
+        assert_eq!(nested.pages[0].url, "https://deep.test/");
+        assert_eq!(nested.pages[0].tags, ["a", "b"]);
+    }
+}
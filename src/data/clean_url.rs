@@ -0,0 +1,294 @@
+//! Tracking-parameter removal (`[url_cleaning]`): `utm_*`, `fbclid` and the
+//! other configured query parameters come off a URL before a top-level `GET`
+//! load (see [`crate::browser`]'s network hook) and as history, bookmarks or
+//! the dial store it, so the saved links stay short on a small screen. Only
+//! writes clean: the stores look URLs up as they are, so a link saved before
+//! cleaning (or under other rules) still matches itself.
+//!
+//! A rule list in the ClearURLs JSON format adds per-site rules on top: for
+//! each provider whose `urlPattern` matches (and no `exceptions` entry), its
+//! `redirections` unwrap a tracking redirect to its target, its `rawRules` are
+//! cut out of the URL, and its `rules` / `referralMarketing` name more query
+//! parameters to drop. The patterns are JavaScript regexes; the few the
+//! `regex` crate can't compile are skipped.
+//!
+//! Only the query is rebuilt, from its raw pieces, so whatever it keeps stays
+//! encoded as it was.
+
+use crate::config::{self, UrlCleaningConfig};
+use regex::Regex;
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::RwLock;
+
+/// Set by [`configure`] at startup and on a settings change; `None` while
+/// cleaning is off.
+static CLEANER: RwLock<Option<Cleaner>> = RwLock::new(None);
+
+/// (Re)build the cleaner from the config, reading its rule list if it names
+/// one. A list that can't be read is logged and the parameter list alone
+/// applies.
+pub fn configure(cfg: &UrlCleaningConfig) {
+    let cleaner = cfg.enabled.then(|| {
+        let mut cleaner = Cleaner::new(&cfg.params);
+        if !cfg.rules.is_empty() {
+            let path = Path::new(&config::data_dir()).join(&cfg.rules);
+            match std::fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|json| cleaner.add_rules(&json))
+            {
+                Ok(n) => log::info!("url cleaning: {n} provider(s) from {}", cfg.rules),
+                Err(e) => log::error!("url cleaning: `{}`: {e}; skipped", cfg.rules),
+            }
+        }
+        cleaner
+    });
+    *CLEANER.write().unwrap() = cleaner;
+}
+
+/// `url` without its tracking parameters; borrowed when there was nothing to
+/// remove (or cleaning is off, or it isn't an `http(s)` URL).
+pub fn clean(url: &str) -> Cow<'_, str> {
+    let lower = url.get(..8).unwrap_or(url).to_ascii_lowercase();
+    if !lower.starts_with("http://") && !lower.starts_with("https://") {
+        return Cow::Borrowed(url);
+    }
+    match CLEANER.read().unwrap().as_ref().and_then(|c| c.apply(url)) {
+        Some(cleaned) => Cow::Owned(cleaned),
+        None => Cow::Borrowed(url),
+    }
+}
+
+/// A parameter name to drop, lowercased.
+enum Param {
+    Exact(String),
+    /// `utm_*`: any name starting with this.
+    Prefix(String),
+}
+
+impl Param {
+    fn matches(&self, name: &str) -> bool {
+        match self {
+            Param::Exact(exact) => name == exact,
+            Param::Prefix(prefix) => name.starts_with(prefix.as_str()),
+        }
+    }
+}
+
+/// One site's rules from a ClearURLs list.
+struct Provider {
+    pattern: Regex,
+    exceptions: Vec<Regex>,
+    /// Whole parameter names, case-insensitive.
+    params: Vec<Regex>,
+    raw: Vec<Regex>,
+    /// Capture group 1 is the (percent-encoded) target.
+    redirections: Vec<Regex>,
+}
+
+impl Provider {
+    fn applies_to(&self, url: &str) -> bool {
+        self.pattern.is_match(url) && !self.exceptions.iter().any(|e| e.is_match(url))
+    }
+}
+
+/// A provider as ClearURLs writes it. `completeProvider` (block the site
+/// outright) is the ad blocker's job and isn't read.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProviderJson {
+    url_pattern: String,
+    #[serde(default)]
+    rules: Vec<String>,
+    #[serde(default)]
+    referral_marketing: Vec<String>,
+    #[serde(default)]
+    raw_rules: Vec<String>,
+    #[serde(default)]
+    exceptions: Vec<String>,
+    #[serde(default)]
+    redirections: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct RulesJson {
+    providers: HashMap<String, ProviderJson>,
+}
+
+struct Cleaner {
+    params: Vec<Param>,
+    providers: Vec<Provider>,
+}
+
+impl Cleaner {
+    fn new(params: &[String]) -> Self {
+        let params = params
+            .iter()
+            .map(|p| p.trim().to_ascii_lowercase())
+            .filter(|p| !p.is_empty())
+            .map(|p| match p.strip_suffix('*') {
+                Some(prefix) => Param::Prefix(prefix.to_string()),
+                None => Param::Exact(p),
+            })
+            .collect();
+        Self {
+            params,
+            providers: Vec::new(),
+        }
+    }
+
+    /// Add the providers of a ClearURLs list; returns how many.
+    fn add_rules(&mut self, json: &str) -> Result<usize, String> {
+        let list: RulesJson = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let before = self.providers.len();
+        for (name, p) in list.providers {
+            let Some(pattern) = compile(&name, &p.url_pattern) else {
+                continue;
+            };
+            let all = |list: &[String], wrap: fn(&str) -> String| {
+                list.iter()
+                    .filter_map(|r| compile(&name, &wrap(r)))
+                    .collect::<Vec<_>>()
+            };
+            let mut params = all(&p.rules, |r| format!("(?i)^(?:{r})$"));
+            params.extend(all(&p.referral_marketing, |r| format!("(?i)^(?:{r})$")));
+            self.providers.push(Provider {
+                pattern,
+                exceptions: all(&p.exceptions, str::to_string),
+                params,
+                raw: all(&p.raw_rules, str::to_string),
+                redirections: all(&p.redirections, str::to_string),
+            });
+        }
+        Ok(self.providers.len() - before)
+    }
+
+    /// The cleaned URL, or `None` if nothing came off.
+    fn apply(&self, url: &str) -> Option<String> {
+        let mut out = url.to_string();
+        // A tracking redirect: go straight to where it leads.
+        let target = self
+            .providers
+            .iter()
+            .filter(|p| p.applies_to(url))
+            .flat_map(|p| &p.redirections)
+            .find_map(|r| r.captures(url)?.get(1))
+            .map(|m| percent_encoding::percent_decode_str(m.as_str()).decode_utf8_lossy())
+            .filter(|t| t.starts_with("http://") || t.starts_with("https://"));
+        if let Some(target) = target {
+            out = target.into_owned();
+        }
+        let providers: Vec<&Provider> = self
+            .providers
+            .iter()
+            .filter(|p| p.applies_to(&out))
+            .collect();
+        for raw in providers.iter().flat_map(|p| &p.raw) {
+            if let Cow::Owned(cut) = raw.replace_all(&out, "") {
+                out = cut;
+            }
+        }
+        out = self.strip_query(&out, &providers);
+        (out != url).then_some(out)
+    }
+
+    /// `url` with the matching query parameters dropped; the query goes
+    /// entirely if none are left.
+    fn strip_query(&self, url: &str, providers: &[&Provider]) -> String {
+        let (rest, fragment) = match url.find('#') {
+            Some(i) => url.split_at(i),
+            None => (url, ""),
+        };
+        let Some((base, query)) = rest.split_once('?') else {
+            return url.to_string();
+        };
+        let kept: Vec<&str> = query
+            .split('&')
+            .filter(|part| {
+                let name = part.split('=').next().unwrap_or_default();
+                let name = percent_encoding::percent_decode_str(name).decode_utf8_lossy();
+                !part.is_empty() && !self.drops(&name, providers)
+            })
+            .collect();
+        if kept.is_empty() {
+            format!("{base}{fragment}")
+        } else {
+            format!("{base}?{}{fragment}", kept.join("&"))
+        }
+    }
+
+    fn drops(&self, name: &str, providers: &[&Provider]) -> bool {
+        let lower = name.to_ascii_lowercase();
+        self.params.iter().any(|p| p.matches(&lower))
+            || providers
+                .iter()
+                .flat_map(|p| &p.params)
+                .any(|r| r.is_match(name))
+    }
+}
+
+fn compile(provider: &str, pattern: &str) -> Option<Regex> {
+    Regex::new(pattern)
+        .inspect_err(|e| log::debug!("url cleaning: {provider}: skipped rule: {e}"))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cleaner() -> Cleaner {
+        Cleaner::new(&UrlCleaningConfig::default().params)
+    }
+
+    /// The built-in list drops `utm_*` and the click ids whatever their case,
+    /// keeps the other parameters as they were encoded, and leaves the
+    /// fragment alone.
+    #[test]
+    fn strips_configured_params() {
+        let c = cleaner();
+        assert_eq!(
+            c.apply("https://example.com/a?id=7&utm_source=x&UTM_Medium=y&q=a%20b#top")
+                .as_deref(),
+            Some("https://example.com/a?id=7&q=a%20b#top")
+        );
+        assert_eq!(
+            c.apply("https://example.com/?fbclid=abc&gclid=def")
+                .as_deref(),
+            Some("https://example.com/")
+        );
+        assert_eq!(c.apply("https://example.com/?utm=1&q=2"), None);
+        assert_eq!(c.apply("https://example.com/#utm_source=x"), None);
+    }
+
+    /// A ClearURLs provider's rules apply only on its site, and its
+    /// redirection unwraps to the (then cleaned) target.
+    #[test]
+    fn applies_clearurls_providers() {
+        let mut c = cleaner();
+        let json = r#"{"providers": {"shop": {
+            "urlPattern": "^https?://(?:[a-z0-9-]+\\.)*?shop\\.example",
+            "rules": ["ref", "pf_rd_[a-z]"],
+            "rawRules": ["/ref=[^/?]*"],
+            "exceptions": ["^https?://shop\\.example/keep"],
+            "redirections": ["^https?://shop\\.example/out\\?to=([^&]+)"]
+        }}}"#;
+        assert_eq!(c.add_rules(json), Ok(1));
+        assert_eq!(
+            c.apply("https://www.shop.example/item/ref=sr_1?pf_rd_p=1&ref=x&size=2")
+                .as_deref(),
+            Some("https://www.shop.example/item?size=2")
+        );
+        assert_eq!(c.apply("https://other.example/?ref=x"), None);
+        assert_eq!(c.apply("https://shop.example/keep?ref=x"), None);
+        assert_eq!(
+            c.apply(
+                "https://shop.example/out?to=https%3A%2F%2Fnews.example%2F%3Futm_source%3Dshop"
+            )
+            .as_deref(),
+            Some("https://news.example/")
+        );
+    }
+}
@@ -1,11 +1,13 @@
-//! The start page's speed-dial: a flat list of pinned URLs persisted to
+//! The start page's speed-dial: a flat list of pinned pages persisted to
 //! `dial.toml` in the user data dir. Unlike bookmarks (a menu-only list), the
 //! dial is what the built-in start page shows as tiles — curated separately so
-//! the two don't fight over one list. Entries are pinned *from* the menu's
-//! Bookmarks / History sections (Y) and unpinned on the dial itself (X); there
+//! the two don't fight over one list. Entries are pinned *from* a bookmark's
+//! edit sheet in the menu (Y) and unpinned on the dial itself (X); there
 //! is no in-list selection here (the start page owns tile focus, see
 //! [`crate::overlay::home`]). A first run with no file ships [`DEFAULTS`].
 
+use super::clean_url;
+use super::Page;
 use crate::config;
 use serde::{Deserialize, Serialize};
 
@@ -25,15 +27,20 @@ const DEFAULTS: &[&str] = &[
     SETTINGS_PIN,
 ];
 
-/// On-disk shape (a TOML table can't be a bare array, so wrap the list).
+/// On-disk shape (a TOML table can't be a bare array, so wrap the list). The
+/// legacy bare `urls` array is read, never written, like bookmarks'.
 #[derive(Default, Serialize, Deserialize)]
 struct Store {
     #[serde(default)]
+    pages: Vec<Page>,
+    #[serde(default, skip_serializing)]
     urls: Vec<String>,
 }
 
 pub struct Dial {
-    urls: Vec<String>,
+    pins: Vec<Page>,
+    /// Unsaved title changes, for [`Self::flush`].
+    dirty: bool,
 }
 
 impl Dial {
@@ -43,14 +50,17 @@ impl Dial {
     pub fn load() -> Self {
         match std::fs::read_to_string(Self::path()) {
             Ok(text) => match toml::from_str::<Store>(&text) {
-                Ok(store) => Self { urls: store.urls },
+                Ok(store) => Self {
+                    pins: super::upgrade_urls(store.pages, store.urls),
+                    dirty: false,
+                },
                 Err(e) => {
                     log::error!("invalid dial `{}`: {e}; using defaults", Self::path());
                     Self::seeded()
                 }
             },
             Err(_) => {
-                let dial = Self::seeded();
+                let mut dial = Self::seeded();
                 dial.save();
                 dial
             }
@@ -59,7 +69,8 @@ impl Dial {
 
     fn seeded() -> Self {
         Self {
-            urls: DEFAULTS.iter().map(|s| s.to_string()).collect(),
+            pins: DEFAULTS.iter().map(|url| Page::new(url, "")).collect(),
+            dirty: false,
         }
     }
 
@@ -67,36 +78,59 @@ impl Dial {
         format!("{}dial.toml", config::data_dir())
     }
 
-    /// Best-effort persist; failures are logged, not fatal.
-    fn save(&self) {
+    /// Best-effort persist; failures are logged, not fatal, and leave `dirty`
+    /// set for the next [`Self::flush`].
+    fn save(&mut self) {
         let store = Store {
-            urls: self.urls.clone(),
+            pages: self.pins.clone(),
+            urls: Vec::new(),
         };
-        super::save_toml("dial.toml", &store, "dial");
+        if super::save_toml("dial.toml", &store, "dial") {
+            self.dirty = false;
+        }
+    }
+
+    /// Write a deferred title change, if any (see [`crate::app`]'s flush
+    /// throttle).
+    pub fn flush(&mut self) {
+        if self.dirty {
+            self.save();
+        }
     }
 
-    pub fn urls(&self) -> &[String] {
-        &self.urls
+    pub fn pins(&self) -> &[Page] {
+        &self.pins
     }
 
     pub fn contains(&self, url: &str) -> bool {
-        self.urls.iter().any(|u| u == url)
+        self.pins.iter().any(|p| p.url == url)
     }
 
-    /// Pin `url` to the end of the dial if not already present; persists. No-op
-    /// on a duplicate (the start page's "+ Add" tile uses this).
-    pub fn pin(&mut self, url: &str) {
-        if !self.contains(url) {
-            self.urls.push(url.to_string());
+    /// Pin `url` (titled `title`, possibly empty) to the end of the dial if not
+    /// already present; persists. No-op on a duplicate (the start page's "+ Add"
+    /// tile uses this). Pinned without its tracking parameters (see
+    /// [`clean_url`]).
+    pub fn pin(&mut self, url: &str, title: &str) {
+        let cleaned = clean_url::clean(url);
+        if !self.contains(url) && !self.contains(&cleaned) {
+            self.pins.push(Page::new(&cleaned, title));
             self.save();
         }
     }
 
+    /// Adopt the title a pinned page reported; a change marks the dial dirty
+    /// (see [`Self::flush`]).
+    pub fn set_title(&mut self, url: &str, title: &str) {
+        if super::retitle(&mut self.pins, url, title) {
+            self.dirty = true;
+        }
+    }
+
     /// Remove the pin at `index` (if in range); persists. Used by the editor's
     /// per-tile ✖ / X.
     pub fn remove(&mut self, index: usize) {
-        if index < self.urls.len() {
-            self.urls.remove(index);
+        if index < self.pins.len() {
+            self.pins.remove(index);
             self.save();
         }
     }
@@ -104,19 +138,21 @@ impl Dial {
     /// Swap the pins at `a` and `b`; persists. The editor reorders with this, so
     /// the indices may straddle the settings sentinel — it keeps its own slot.
     pub fn swap(&mut self, a: usize, b: usize) {
-        if a != b && a < self.urls.len() && b < self.urls.len() {
-            self.urls.swap(a, b);
+        if a != b && a < self.pins.len() && b < self.pins.len() {
+            self.pins.swap(a, b);
             self.save();
         }
     }
 
-    /// Pin `url` if absent, otherwise unpin it; persists either way.
-    pub fn toggle(&mut self, url: &str) {
-        if let Some(i) = self.urls.iter().position(|u| u == url) {
-            self.urls.remove(i);
-        } else {
-            self.urls.push(url.to_string());
+    /// Pin `url` (titled `title`) if absent, otherwise unpin it; persists either
+    /// way. Pinned like [`Self::pin`].
+    pub fn toggle(&mut self, url: &str, title: &str) {
+        match self.pins.iter().position(|p| p.url == url) {
+            Some(i) => {
+                self.pins.remove(i);
+                self.save();
+            }
+            None => self.pin(url, title),
         }
-        self.save();
     }
 }
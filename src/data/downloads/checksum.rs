@@ -0,0 +1,336 @@
+//! Checksums of downloaded files: SHA-256, plus the MD5 and CRC32 that ROM sets
+//! are catalogued by, computed as the body streams in (see [`super::worker`]; a
+//! resume first re-reads its `.part`). A finished file is then checked against a
+//! published hash, when there is one: in the link's fragment
+//! (`…/game.zip#sha256=<hex>`, also `md5=` / `crc32=`) as it finishes, else,
+//! with `[downloads] checksum_lookup` on, in a `<file>.sha256` next to it on the
+//! server or the directory's `SHA256SUMS`, fetched in the background once the
+//! download shows as done. Lookups are best-effort; no hash found leaves the
+//! file unchecked.
+
+use crate::event::user::{UserEvent, UserEventSender};
+use md5::Md5;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Whole-request deadline for each checksum-file lookup.
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The three hashes, fed together.
+pub(super) struct Hasher {
+    sha256: Sha256,
+    md5: Md5,
+    crc32: crc32fast::Hasher,
+}
+
+impl Hasher {
+    pub fn new() -> Self {
+        Self {
+            sha256: Sha256::new(),
+            md5: Md5::new(),
+            crc32: crc32fast::Hasher::new(),
+        }
+    }
+
+    /// A hasher that has already seen the file at `path` (the `.part` a resume
+    /// appends to).
+    pub fn from_file(path: &str) -> std::io::Result<Self> {
+        let mut hasher = Self::new();
+        let mut file = std::fs::File::open(path)?;
+        let mut buf = vec![0u8; 64 * 1024];
+        loop {
+            let n = file.read(&mut buf)?;
+            if n == 0 {
+                return Ok(hasher);
+            }
+            hasher.update(&buf[..n]);
+        }
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        self.sha256.update(bytes);
+        self.md5.update(bytes);
+        self.crc32.update(bytes);
+    }
+
+    pub fn finish(self) -> Digests {
+        Digests {
+            sha256: hex(&self.sha256.finalize()),
+            md5: hex(&self.md5.finalize()),
+            crc32: format!("{:08x}", self.crc32.finalize()),
+        }
+    }
+}
+
+/// A finished file's hashes, lowercase hex.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Digests {
+    pub sha256: String,
+    pub md5: String,
+    pub crc32: String,
+}
+
+impl Digests {
+    fn get(&self, algo: Algo) -> &str {
+        match algo {
+            Algo::Sha256 => &self.sha256,
+            Algo::Md5 => &self.md5,
+            Algo::Crc32 => &self.crc32,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Algo {
+    Sha256,
+    Md5,
+    Crc32,
+}
+
+impl Algo {
+    /// The algorithm a fragment key names (`sha256`, `sha-256`, `md5`, `crc32`).
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().replace('-', "").as_str() {
+            "sha256" => Some(Algo::Sha256),
+            "md5" => Some(Algo::Md5),
+            "crc32" => Some(Algo::Crc32),
+            _ => None,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Algo::Sha256 => "SHA-256",
+            Algo::Md5 => "MD5",
+            Algo::Crc32 => "CRC32",
+        }
+    }
+
+    /// Hex digits in a digest.
+    fn digits(self) -> usize {
+        match self {
+            Algo::Sha256 => 64,
+            Algo::Md5 => 32,
+            Algo::Crc32 => 8,
+        }
+    }
+}
+
+/// A published hash of the file, and where it was found.
+#[derive(Debug, PartialEq)]
+pub(super) struct Expected {
+    algo: Algo,
+    hex: String,
+    source: String,
+}
+
+/// The outcome of checking a finished file.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verdict {
+    /// No published hash was found.
+    Unchecked,
+    /// The hash matched; names it and its source, e.g. `SHA-256 from SHA256SUMS`.
+    Verified(String),
+    /// The hash differed.
+    Mismatch(String),
+}
+
+impl Verdict {
+    /// Check `digests` against `expected`.
+    pub(super) fn of(digests: &Digests, expected: Option<Expected>) -> Self {
+        let Some(expected) = expected else {
+            return Verdict::Unchecked;
+        };
+        let what = format!("{} from {}", expected.algo.label(), expected.source);
+        if digests.get(expected.algo) == expected.hex {
+            Verdict::Verified(what)
+        } else {
+            Verdict::Mismatch(what)
+        }
+    }
+}
+
+/// The hash published in the fragment of the link `url`, if any.
+pub(super) fn from_link(url: &str) -> Option<Expected> {
+    url::Url::parse(url)
+        .ok()?
+        .fragment()
+        .and_then(from_fragment)
+}
+
+/// A published-hash lookup running after its download finished.
+pub(super) struct Lookup {
+    pub result: Mutex<Option<Verdict>>,
+}
+
+/// Check `digests` against the hash published beside the file `url` was saved
+/// from as `name`, on a background thread (two requests at worst).
+pub(super) fn spawn_lookup(
+    url: &str,
+    name: &str,
+    user_agent: &str,
+    digests: Digests,
+    sender: &UserEventSender,
+) -> Arc<Lookup> {
+    let lookup = Arc::new(Lookup {
+        result: Mutex::new(None),
+    });
+    let (url, name, user_agent) = (url.to_string(), name.to_string(), user_agent.to_string());
+    let (shared, sender) = (lookup.clone(), sender.clone());
+    std::thread::spawn(move || {
+        let verdict = Verdict::of(&digests, published(&url, &name, &user_agent));
+        if let Verdict::Mismatch(what) = &verdict {
+            log::warn!("download `{url}`: {what} doesn't match");
+        }
+        *shared.result.lock().unwrap() = Some(verdict);
+        sender.send(UserEvent::DownloadUpdate);
+    });
+    lookup
+}
+
+/// The hash in a sibling `.sha256` of the file `url` was saved from as `name`,
+/// else in the directory's `SHA256SUMS`.
+fn published(url: &str, name: &str, user_agent: &str) -> Option<Expected> {
+    let parsed = url::Url::parse(url).ok()?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return None;
+    }
+    let mut base = parsed;
+    base.set_fragment(None);
+    base.set_query(None);
+    let linked = super::worker::filename_from_url(base.as_str());
+
+    let mut sidecar = base.clone();
+    sidecar.set_path(&format!("{}.sha256", base.path()));
+    if let Some(hex) = fetch_text(sidecar.as_str(), user_agent)
+        .and_then(|body| find_hash(&body, &[name, &linked], true))
+    {
+        let source = format!("{linked}.sha256");
+        return Some(Expected {
+            algo: Algo::Sha256,
+            hex,
+            source,
+        });
+    }
+    let sums = base.join("SHA256SUMS").ok()?;
+    let hex = fetch_text(sums.as_str(), user_agent)
+        .and_then(|body| find_hash(&body, &[name, &linked], false))?;
+    Some(Expected {
+        algo: Algo::Sha256,
+        hex,
+        source: "SHA256SUMS".to_string(),
+    })
+}
+
+/// A hash in a URL fragment: `sha256=<hex>`, alone or among other `&`-joined
+/// pairs.
+fn from_fragment(fragment: &str) -> Option<Expected> {
+    fragment.split('&').find_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        let algo = Algo::from_name(key.trim())?;
+        let hex = value.trim().to_ascii_lowercase();
+        is_hex(&hex, algo.digits()).then(|| Expected {
+            algo,
+            hex,
+            source: "the link".to_string(),
+        })
+    })
+}
+
+/// The SHA-256 for one of `names` in a `sha256sum`-style list (`<hex>  <name>`,
+/// `*` marking binary mode). With `lone`, a line naming no file counts too,
+/// as a single-file sidecar may just hold the hash.
+fn find_hash(body: &str, names: &[&str], lone: bool) -> Option<String> {
+    body.lines().find_map(|line| {
+        let line = line.trim();
+        let (hex, name) = match line.split_once(char::is_whitespace) {
+            Some((hex, name)) => (hex, name.trim().trim_start_matches('*')),
+            None => (line, ""),
+        };
+        let name = name.trim_start_matches("./");
+        let hex = hex.to_ascii_lowercase();
+        let named = if name.is_empty() {
+            lone
+        } else {
+            names.contains(&name)
+        };
+        (named && is_hex(&hex, 64)).then_some(hex)
+    })
+}
+
+/// A small text file's body, or `None` on any failure.
+fn fetch_text(url: &str, user_agent: &str) -> Option<String> {
+    crate::net::agent_with(url, |config| config.timeout_global(Some(LOOKUP_TIMEOUT)))
+        .get(url)
+        .header("User-Agent", user_agent)
+        .call()
+        .ok()?
+        .body_mut()
+        .read_to_string()
+        .ok()
+}
+
+fn is_hex(s: &str, len: usize) -> bool {
+    s.len() == len && s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+    let mut s = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        let _ = write!(s, "{b:02x}");
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The three digests of a known input, and a verdict against each source.
+    #[test]
+    fn digests_and_verdicts() {
+        let mut hasher = Hasher::new();
+        hasher.update(b"hello ");
+        hasher.update(b"world");
+        let digests = hasher.finish();
+        assert_eq!(
+            digests.sha256,
+            "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+        );
+        assert_eq!(digests.md5, "5eb63bbbe01eeed093cb22bb8f5acdc3");
+        assert_eq!(digests.crc32, "0d4a1185");
+
+        let fragment = from_fragment("x=1&CRC32=0D4A1185");
+        assert_eq!(
+            Verdict::of(&digests, fragment),
+            Verdict::Verified("CRC32 from the link".to_string())
+        );
+        let fragment = from_fragment("md5=00000000000000000000000000000000");
+        assert_eq!(
+            Verdict::of(&digests, fragment),
+            Verdict::Mismatch("MD5 from the link".to_string())
+        );
+        assert_eq!(from_fragment("sha256=abc"), None);
+        assert_eq!(Verdict::of(&digests, None), Verdict::Unchecked);
+    }
+
+    /// A `SHA256SUMS` line is picked by file name (binary-mode `*` and `./`
+    /// allowed); a bare hash only counts in a single-file sidecar.
+    #[test]
+    fn finds_the_hash_in_checksum_files() {
+        let a = "a".repeat(64);
+        let b = "B".repeat(64);
+        let sums = format!("{a}  other.zip\n{b} *./game.zip\n");
+        assert_eq!(find_hash(&sums, &["game.zip"], false), Some("b".repeat(64)));
+        assert_eq!(find_hash(&sums, &["missing.zip"], false), None);
+        assert_eq!(
+            find_hash(&format!("{a}\n"), &["game.zip"], true),
+            Some(a.clone())
+        );
+        assert_eq!(find_hash(&format!("{a}\n"), &["game.zip"], false), None);
+    }
+}
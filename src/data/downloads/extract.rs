@@ -0,0 +1,257 @@
+//! Unpacking finished `.zip` downloads (`[downloads] extract`) on a background
+//! thread: into a new folder beside the archive named after it, or into the
+//! folder the archive is in (its routed destination, see [`super::routes`]),
+//! then optionally deleting the archive. Entry names are untrusted like the
+//! server's save names (see [`super::worker`]): an absolute one, a `..` or a
+//! drive prefix is skipped, so nothing lands outside the destination. Nothing
+//! there is overwritten either: an entry whose name is taken is saved under a
+//! free one, as a download is. And an archive that unpacks past
+//! [`MAX_RATIO`] times its own size is stopped, before a zip bomb fills the SD
+//! card.
+
+use crate::config::ExtractMode;
+use crate::event::user::{UserEvent, UserEventSender};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Minimum gap between progress wake-ups of the main loop.
+const NOTIFY_EVERY: Duration = Duration::from_millis(250);
+
+/// Most bytes an archive may unpack to, per byte of the archive. ROM sets
+/// compress a few times over; a zip bomb, thousands.
+const MAX_RATIO: u64 = 100;
+
+/// Unpacking thread → main-thread progress.
+pub(super) struct Progress {
+    /// Folder the archive unpacks into, with a trailing separator.
+    pub dest: String,
+    /// Entries handled so far, of `total`.
+    pub done: AtomicU64,
+    pub total: AtomicU64,
+    pub result: Mutex<Option<Result<(), String>>>,
+}
+
+/// Start unpacking the archive at `path` per `mode`, deleting it afterwards if
+/// `delete`. `None` when there's nothing to do: unpacking is off, or the file
+/// isn't a zip.
+pub(super) fn spawn(
+    path: &str,
+    mode: ExtractMode,
+    delete: bool,
+    sender: &UserEventSender,
+) -> Option<Arc<Progress>> {
+    if !path.to_ascii_lowercase().ends_with(".zip") {
+        return None;
+    }
+    let progress = Arc::new(Progress {
+        dest: destination(path, mode)?,
+        done: AtomicU64::new(0),
+        total: AtomicU64::new(0),
+        result: Mutex::new(None),
+    });
+    let (path, sender) = (path.to_string(), sender.clone());
+    let shared = progress.clone();
+    std::thread::spawn(move || {
+        let fresh = mode == ExtractMode::Folder;
+        let result = unpack(&path, Path::new(&shared.dest), &shared, &sender);
+        match &result {
+            Ok(()) => {
+                log::info!("unpacked `{path}` -> `{}`", shared.dest);
+                if delete {
+                    if let Err(e) = std::fs::remove_file(&path) {
+                        log::warn!("could not delete `{path}` after unpacking: {e}");
+                    }
+                }
+            }
+            Err(e) => {
+                log::warn!("unpacking `{path}` failed: {e}");
+                // A folder made for this archive goes with the failure.
+                if fresh {
+                    let _ = std::fs::remove_dir_all(&shared.dest);
+                }
+            }
+        }
+        *shared.result.lock().unwrap() = Some(result);
+        sender.send(UserEvent::DownloadUpdate);
+    });
+    Some(progress)
+}
+
+/// Where the archive at `path` unpacks (with a trailing separator): a free
+/// folder beside it named after it, or the folder it's in.
+fn destination(path: &str, mode: ExtractMode) -> Option<String> {
+    let (dir, name) = path.rsplit_once('/')?;
+    let dir = format!("{dir}/");
+    match mode {
+        ExtractMode::Off => None,
+        ExtractMode::Here => Some(dir),
+        ExtractMode::Folder => {
+            let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+            Some(format!("{}/", super::worker::unique_path(&dir, stem)))
+        }
+    }
+}
+
+fn unpack(
+    path: &str,
+    dest: &Path,
+    progress: &Progress,
+    sender: &UserEventSender,
+) -> Result<(), String> {
+    let file = std::fs::File::open(path).map_err(|e| format!("open: {e}"))?;
+    let size = file.metadata().map_err(|e| format!("open: {e}"))?.len();
+    let mut budget = size.saturating_mul(MAX_RATIO);
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+    progress
+        .total
+        .store(archive.len() as u64, Ordering::Relaxed);
+    std::fs::create_dir_all(dest).map_err(|e| format!("create dir: {e}"))?;
+    let mut last_notify = Instant::now();
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| e.to_string())?;
+        match safe_path(entry.name()) {
+            None => log::warn!("unpack: skipped unsafe entry `{}`", entry.name()),
+            Some(rel) if entry.is_dir() => {
+                std::fs::create_dir_all(dest.join(rel)).map_err(|e| format!("create dir: {e}"))?;
+            }
+            Some(rel) => {
+                let out = dest.join(rel);
+                if let Some(parent) = out.parent() {
+                    std::fs::create_dir_all(parent).map_err(|e| format!("create dir: {e}"))?;
+                }
+                let (out, mut writer) = create_free(&out)?;
+                // One byte past the budget tells an archive that overran it.
+                let written = std::io::copy(
+                    &mut (&mut entry).take(budget.saturating_add(1)),
+                    &mut writer,
+                );
+                match written {
+                    Ok(n) if n <= budget => budget -= n,
+                    result => {
+                        drop(writer);
+                        let _ = std::fs::remove_file(&out);
+                        return Err(match result {
+                            Err(e) => format!("write: {e}"),
+                            Ok(_) => format!("unpacks to over {MAX_RATIO}× its size"),
+                        });
+                    }
+                }
+            }
+        }
+        progress.done.store(i as u64 + 1, Ordering::Relaxed);
+        if last_notify.elapsed() >= NOTIFY_EVERY {
+            last_notify = Instant::now();
+            sender.send(UserEvent::DownloadUpdate);
+        }
+    }
+    Ok(())
+}
+
+/// Create the file `out`, or, when that name is taken, a free one beside it
+/// (`rom-1.gba`, …); never an existing file.
+fn create_free(out: &Path) -> Result<(PathBuf, std::fs::File), String> {
+    let create = |path: &Path| {
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+    };
+    match create(out) {
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+        result => {
+            return result
+                .map(|f| (out.to_path_buf(), f))
+                .map_err(|e| format!("create: {e}"))
+        }
+    }
+    let dir = out
+        .parent()
+        .map_or_else(String::new, |d| format!("{}/", d.display()));
+    let name = out
+        .file_name()
+        .map_or_else(String::new, |n| n.to_string_lossy().into_owned());
+    let free = PathBuf::from(super::worker::unique_path(&dir, &name));
+    log::info!(
+        "unpack: `{}` exists, saved as `{}`",
+        out.display(),
+        free.display()
+    );
+    let file = create(&free).map_err(|e| format!("create: {e}"))?;
+    Ok((free, file))
+}
+
+/// The relative path an entry may be written to: its `/`- or `\`-separated
+/// components, minus empty and `.` ones. `None` for an absolute name, a `..`
+/// component, a drive prefix or a control character.
+fn safe_path(name: &str) -> Option<PathBuf> {
+    if name.starts_with(['/', '\\']) || name.chars().any(|c| c.is_control() || c == ':') {
+        return None;
+    }
+    let mut path = PathBuf::new();
+    for part in name.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => return None,
+            part => path.push(part),
+        }
+    }
+    (!path.as_os_str().is_empty()).then_some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Entry names can't climb out of the destination or name a root.
+    #[test]
+    fn entry_paths_stay_inside() {
+        let ok = |name: &str| safe_path(name).map(|p| p.to_string_lossy().into_owned());
+        assert_eq!(ok("game/rom.gba").as_deref(), Some("game/rom.gba"));
+        assert_eq!(ok("./game//rom.gba").as_deref(), Some("game/rom.gba"));
+        assert_eq!(ok("win\\style.txt").as_deref(), Some("win/style.txt"));
+        assert_eq!(ok(".nomedia").as_deref(), Some(".nomedia"));
+        assert_eq!(ok("../evil.sh"), None);
+        assert_eq!(ok("game/../../evil.sh"), None);
+        assert_eq!(ok("/etc/passwd"), None);
+        assert_eq!(ok("\\\\server\\share"), None);
+        assert_eq!(ok("C:\\evil.exe"), None);
+        assert_eq!(ok("bad\nname"), None);
+        assert_eq!(ok("./"), None);
+    }
+
+    /// The folder mode names a new folder after the archive; the other mode
+    /// unpacks where the archive is.
+    #[test]
+    fn destination_per_mode() {
+        let path = "/nonexistent/roms/gba/Golden Sun (USA).zip";
+        assert_eq!(
+            destination(path, ExtractMode::Folder).as_deref(),
+            Some("/nonexistent/roms/gba/Golden Sun (USA)/")
+        );
+        assert_eq!(
+            destination(path, ExtractMode::Here).as_deref(),
+            Some("/nonexistent/roms/gba/")
+        );
+        assert_eq!(destination(path, ExtractMode::Off), None);
+    }
+
+    /// An entry never replaces a file already there; it takes a free name.
+    #[test]
+    fn existing_files_are_kept() {
+        let dir = std::env::temp_dir().join(format!("retsurf-unpack-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        let rom = dir.join("rom.gba");
+        std::fs::write(&rom, b"mine").expect("existing file");
+
+        let (path, mut file) = create_free(&rom).expect("free name");
+        std::io::Write::write_all(&mut file, b"zip").expect("write");
+        assert_eq!(path, dir.join("rom-1.gba"));
+        assert_eq!(std::fs::read(&rom).expect("kept"), b"mine");
+        let (path, _) = create_free(&dir.join("new.gba")).expect("new name");
+        assert_eq!(path, dir.join("new.gba"));
+        std::fs::remove_dir_all(dir).expect("cleanup");
+    }
+}
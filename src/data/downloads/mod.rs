@@ -1,25 +1,52 @@
 //! File downloads. Servo's embedding API has no support for them (no delegate hook, no
 //! `Content-Disposition` handling; still true on `main`), so retsurf does it itself: the
 //! browser denies navigations to file-like URLs (see [`crate::browser`]) and the main loop
-//! hands them here. Fetching runs on background threads (see [`worker`]); finished entries
-//! persist to `downloads.toml` (see [`store`]), active ones don't survive a restart. Owns
+//! hands them here. Fetching runs on background threads (see [`worker`]), which retry a
+//! dropped transfer, pause one that keeps dropping, and continue a paused one from its
+//! `.part`. The list persists to
+//! `downloads.toml` (see [`store`]), unfinished entries included, so a download that was
+//! running at exit resumes on the next launch. At most `[downloads] max_concurrent` run at
+//! once; the rest wait as Queued and start top-first, in list order (which the menu can
+//! rearrange). Each file is hashed as it arrives and checked against a published hash
+//! (see [`checksum`]), one looked up on the server only after the entry shows as done; a
+//! finished zip can be unpacked next to it once it checked out (see [`extract`]). Owns
 //! the entry list and the menu's highlighted row; [`crate::ui`] renders it.
 
+mod checksum;
+mod extract;
+mod routes;
 mod store;
 mod worker;
 
-use crate::config::DownloadsConfig;
+use crate::browser::DownloadRequest;
+use crate::config::{DownloadsConfig, ExtractMode};
 use crate::data::history;
 use crate::event::user::UserEventSender;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
+pub use checksum::{Digests, Verdict};
+
 /// Lifecycle of one download.
 pub enum State {
-    /// A worker thread is still fetching the file.
+    /// Waiting for a free slot (see [`DownloadsConfig::max_concurrent`]).
+    Queued,
+    /// A worker thread is still fetching the file (or waiting to retry).
     Active,
+    /// Stopped by the user, or on its own (with why) once its retries ran out;
+    /// the `.part` is kept for a resume.
+    Paused(Option<String>),
     Done,
-    /// The fetch failed or was cancelled; the partial file was removed.
+    /// The fetch was refused or cancelled. A cancel removed the partial file;
+    /// otherwise it goes when the entry is removed.
+    Failed(String),
+}
+
+/// Unpacking of a finished zip (see [`extract`]).
+enum Unpack {
+    Running(Arc<extract::Progress>),
+    /// Unpacked into this folder.
+    Done(String),
     Failed(String),
 }
 
@@ -35,6 +62,22 @@ pub struct Download {
     /// When the download finished (unix seconds), `0` while active.
     pub time: u64,
     pub state: State,
+    /// Linking page, sent again as Referer when the download resumes.
+    referer: Option<String>,
+    /// Name the page asked for, kept until a queued download starts.
+    suggested_name: Option<String>,
+    /// ETag / Last-Modified of the response, guarding a resume (see [`worker`]).
+    validator: Option<String>,
+    /// Seconds until the worker's next attempt while it waits to retry.
+    retry_in: u64,
+    /// Set once a finished zip is (being) unpacked.
+    unpack: Option<Unpack>,
+    /// Hashes of the finished file; `None` until then.
+    pub checksums: Option<Digests>,
+    /// How the hashes compared with a published one.
+    pub verdict: Verdict,
+    /// Set while a finished file's published hash is looked up.
+    verify: Option<Arc<checksum::Lookup>>,
     /// Progress shared with the worker thread; dropped once it finishes.
     shared: Option<Arc<worker::Shared>>,
 }
@@ -44,38 +87,158 @@ impl Download {
         matches!(self.state, State::Active)
     }
 
-    /// One-line status for the menu row: progress while active, size + date when
-    /// done, the error otherwise.
+    pub fn is_queued(&self) -> bool {
+        matches!(self.state, State::Queued)
+    }
+
+    /// Done or failed: nothing more will happen to it.
+    pub fn is_finished(&self) -> bool {
+        matches!(self.state, State::Done | State::Failed(_))
+    }
+
+    /// Done, with a published hash still being looked up.
+    pub fn is_verifying(&self) -> bool {
+        self.verify.is_some()
+    }
+
+    /// One-line status for the menu row: progress while active, queued or paused,
+    /// size + date when done, the error otherwise.
     pub fn status_text(&self) -> String {
         match &self.state {
-            State::Active if self.total > 0 => format!(
+            State::Queued if self.received > 0 => format!("Queued · {}", self.progress()),
+            State::Queued => "Queued".to_string(),
+            State::Active if self.retry_in > 0 => {
+                format!("Retry in {}s · {}", self.retry_in, self.progress())
+            }
+            State::Active => self.progress(),
+            State::Paused(None) => format!("Paused · {}", self.progress()),
+            State::Paused(Some(e)) => format!("Paused · {e} · {}", self.progress()),
+            State::Done if matches!(self.verdict, Verdict::Mismatch(_)) => {
+                format!("{} checksum mismatch", egui_phosphor::bold::X)
+            }
+            State::Done if self.is_verifying() => format!("{} · verifying", self.summary()),
+            State::Done => match &self.unpack {
+                Some(Unpack::Running(progress)) => {
+                    let done = progress.done.load(Ordering::Relaxed);
+                    match progress.total.load(Ordering::Relaxed) {
+                        0 => "Unpacking".to_string(),
+                        total => format!("Unpacking {}% · {done} / {total}", done * 100 / total),
+                    }
+                }
+                Some(Unpack::Failed(e)) => format!("{} unpack: {e}", egui_phosphor::bold::X),
+                Some(Unpack::Done(_)) => format!("{} · unpacked", self.summary()),
+                None => self.summary(),
+            },
+            // The menu renders this in the Proportional family, which falls back
+            // to the icon font (see `crate::ui::theme`), so the marker resolves.
+            State::Failed(e) => format!("{} {e}", egui_phosphor::bold::X),
+        }
+    }
+
+    /// Pick up the end of a published-hash lookup, then unpack the file if it
+    /// checked out; `true` if the lookup just ended.
+    fn poll_verify(
+        &mut self,
+        extract: ExtractMode,
+        delete_archive: bool,
+        sender: &UserEventSender,
+    ) -> bool {
+        let Some(lookup) = &self.verify else {
+            return false;
+        };
+        let Some(verdict) = lookup.result.lock().unwrap().take() else {
+            return false;
+        };
+        self.verify = None;
+        self.verdict = verdict;
+        self.start_unpack(extract, delete_archive, sender);
+        true
+    }
+
+    /// Unpack the finished file, unless it failed its checksum (a zip that
+    /// does is left packed).
+    fn start_unpack(
+        &mut self,
+        extract: ExtractMode,
+        delete_archive: bool,
+        sender: &UserEventSender,
+    ) {
+        if !matches!(self.verdict, Verdict::Mismatch(_)) {
+            self.unpack =
+                extract::spawn(&self.path, extract, delete_archive, sender).map(Unpack::Running);
+        }
+    }
+
+    /// Delete the `.part` an unfinished or failed entry may have left.
+    fn remove_part(&self) {
+        if !matches!(self.state, State::Done) && !self.path.is_empty() {
+            let _ = std::fs::remove_file(format!("{}.part", self.path));
+        }
+    }
+
+    /// Pick up the end of an unpacking; `true` if it just ended.
+    fn poll_unpack(&mut self) -> bool {
+        let Some(Unpack::Running(progress)) = &self.unpack else {
+            return false;
+        };
+        let Some(result) = progress.result.lock().unwrap().take() else {
+            return false;
+        };
+        self.unpack = Some(match result {
+            Ok(()) => Unpack::Done(progress.dest.clone()),
+            Err(e) => Unpack::Failed(e),
+        });
+        true
+    }
+
+    /// Size and date of a finished download, led by ✔ once its hash checked out.
+    fn summary(&self) -> String {
+        let size_date = format!(
+            "{} · {}",
+            format_size(self.received),
+            history::format_time(self.time)
+        );
+        match self.verdict {
+            Verdict::Verified(_) => format!("{} {size_date}", egui_phosphor::bold::CHECK),
+            _ => size_date,
+        }
+    }
+
+    fn progress(&self) -> String {
+        if self.total > 0 {
+            format!(
                 "{}% · {} / {}",
                 self.received * 100 / self.total,
                 format_size(self.received),
                 format_size(self.total),
-            ),
-            State::Active => format_size(self.received),
-            State::Done => format!(
-                "{} · {}",
-                format_size(self.received),
-                history::format_time(self.time)
-            ),
-            // The menu renders this in the Proportional family, which falls back
-            // to the icon font (see `crate::ui::theme`), so the marker resolves.
-            State::Failed(e) => format!("{} {e}", egui_phosphor::bold::X),
+            )
+        } else {
+            format_size(self.received)
         }
     }
 }
 
 pub struct Downloads {
-    /// Most-recent first; active entries are always from this session.
+    /// Most-recent first.
     items: Vec<Download>,
-    /// Save directory, with a trailing separator (see [`DownloadsConfig`]).
-    dir: String,
+    /// Save directory (with a trailing separator, see [`DownloadsConfig`]) and
+    /// the per-type folders routed off it.
+    routes: Arc<routes::Routes>,
     /// The browser's UA string, sent by the workers' fetches.
     user_agent: String,
+    /// Downloads allowed to run at once; `0` for no limit.
+    max_concurrent: usize,
+    /// Whether and where finished zips unpack, and if the archive goes after.
+    extract: ExtractMode,
+    delete_archive: bool,
+    /// Whether a finished file's hash is looked up on the server when its
+    /// link carries none (see [`checksum`]).
+    checksum_lookup: bool,
     /// Highlighted row in the menu's Downloads section.
     cursor: crate::data::ListCursor,
+    /// Whether the highlighted finished download's details (path, hashes) show
+    /// above the list.
+    details: bool,
 }
 
 impl Downloads {
@@ -83,18 +246,35 @@ impl Downloads {
     pub fn load(cfg: &DownloadsConfig, user_agent: String) -> Self {
         Self {
             items: store::load(),
-            dir: cfg.resolve_dir(),
+            routes: Arc::new(routes::Routes::new(cfg.resolve_dir(), &cfg.routes)),
             user_agent,
+            max_concurrent: cfg.max_concurrent,
+            extract: cfg.extract,
+            delete_archive: cfg.delete_archive,
+            checksum_lookup: cfg.checksum_lookup,
             // Row 0 is the "Clear finished" action (like History's "Clear all"),
             // so it stays reachable without a mouse; entries follow it.
             cursor: crate::data::ListCursor::new(1),
+            details: false,
         }
     }
 
-    /// Begin fetching a denied navigation, adding an Active entry on top.
-    pub fn start(&mut self, request: crate::browser::DownloadRequest, sender: &UserEventSender) {
-        if let Err(e) = std::fs::create_dir_all(&self.dir) {
-            log::warn!("could not create download dir `{}`: {e}", self.dir);
+    /// Adopt changed settings (the concurrency limit, unpacking and checksum
+    /// lookups apply live; the save folder needs a restart), starting queued
+    /// downloads a raised limit allows.
+    pub fn set_config(&mut self, cfg: &DownloadsConfig, sender: &UserEventSender) {
+        self.max_concurrent = cfg.max_concurrent;
+        self.extract = cfg.extract;
+        self.delete_archive = cfg.delete_archive;
+        self.checksum_lookup = cfg.checksum_lookup;
+        self.schedule(sender);
+    }
+
+    /// Begin fetching a denied navigation: a new entry on top, or below the
+    /// already queued ones if it has to wait for a slot.
+    pub fn start(&mut self, request: DownloadRequest, sender: &UserEventSender) {
+        if let Err(e) = std::fs::create_dir_all(self.routes.dir()) {
+            log::warn!("could not create download dir `{}`: {e}", self.routes.dir());
             self.items.insert(
                 0,
                 Download {
@@ -105,6 +285,14 @@ impl Downloads {
                     total: 0,
                     time: history::now_unix(),
                     state: State::Failed(format!("create dir: {e}")),
+                    referer: None,
+                    suggested_name: None,
+                    validator: None,
+                    retry_in: 0,
+                    unpack: None,
+                    checksums: None,
+                    verdict: Verdict::Unchecked,
+                    verify: None,
                     shared: None,
                 },
             );
@@ -112,31 +300,174 @@ impl Downloads {
             return;
         }
 
-        let shared = worker::spawn(&request, &self.user_agent, &self.dir, sender);
+        // Queue order is list order, so a newcomer goes behind those waiting.
         self.items.insert(
-            0,
+            queue_end(&self.items),
             Download {
                 filename: request
                     .suggested_name
+                    .clone()
                     .unwrap_or_else(|| worker::filename_from_url(&request.url)),
                 url: request.url,
                 path: String::new(),
                 received: 0,
                 total: 0,
                 time: 0,
-                state: State::Active,
-                shared: Some(shared),
+                state: State::Queued,
+                referer: request.referer,
+                suggested_name: request.suggested_name,
+                validator: None,
+                retry_in: 0,
+                unpack: None,
+                checksums: None,
+                verdict: Verdict::Unchecked,
+                verify: None,
+                shared: None,
             },
         );
+        // Saved while waiting or running too, so a quit mid-download resumes it.
+        store::save(&self.items);
+        self.schedule(sender);
+    }
+
+    /// Queue the downloads a previous session left running (paused ones wait
+    /// for the user) and start as many as the limit allows. Called once at
+    /// startup.
+    pub fn resume_unfinished(&mut self, sender: &UserEventSender) {
+        for d in &mut self.items {
+            if d.is_active() && d.shared.is_none() {
+                d.state = State::Queued;
+            }
+        }
+        self.schedule(sender);
+    }
+
+    /// Start queued downloads, top-first, while slots are free.
+    fn schedule(&mut self, sender: &UserEventSender) {
+        for i in due(&self.items, self.max_concurrent) {
+            self.launch(i, sender);
+        }
+    }
+
+    /// Y/⏯ on an entry: pause it if active or queued (an active worker stops,
+    /// keeping the `.part`), resume it if paused (by the user or after its
+    /// retries ran out) — straight away when a slot is free, else through the
+    /// queue. Finished entries are left alone.
+    pub fn toggle_pause(&mut self, index: usize, sender: &UserEventSender) {
+        let Some(d) = self.items.get_mut(index) else {
+            return;
+        };
+        if let Some(shared) = &d.shared {
+            shared.pause.store(true, Ordering::Relaxed);
+            return;
+        }
+        match d.state {
+            State::Queued => d.state = State::Paused(None),
+            State::Paused(_) => d.state = State::Queued,
+            _ => return,
+        }
+        store::save(&self.items);
+        self.schedule(sender);
+    }
+
+    pub fn toggle_pause_selected(&mut self, sender: &UserEventSender) {
+        if let Some(i) = self.cursor.entry_index() {
+            self.toggle_pause(i, sender);
+        }
+    }
+
+    /// Show or hide the details of the finished entry at `index`, highlighting
+    /// it (Y, or its ⓘ); `false`, doing nothing, if it isn't finished.
+    pub fn toggle_details(&mut self, index: usize) -> bool {
+        if !self.items.get(index).is_some_and(Download::is_finished) {
+            return false;
+        }
+        let open = self.details && self.cursor.entry_index() == Some(index);
+        self.cursor.select_entry(index);
+        self.details = !open;
+        true
+    }
+
+    pub fn toggle_details_selected(&mut self) -> bool {
+        self.cursor
+            .entry_index()
+            .is_some_and(|i| self.toggle_details(i))
+    }
+
+    /// The download whose details show: the highlighted one, while the view is
+    /// open and it is finished.
+    pub fn details(&self) -> Option<&Download> {
+        if !self.details {
+            return None;
+        }
+        let d = self.items.get(self.cursor.entry_index()?)?;
+        d.is_finished().then_some(d)
+    }
+
+    /// Move the entry at `index` to the top of the list, and so to the front of
+    /// the queue (A on a queued download, or clicking it).
+    pub fn move_to_top(&mut self, index: usize) {
+        if index == 0 || index >= self.items.len() {
+            return;
+        }
+        let d = self.items.remove(index);
+        self.items.insert(0, d);
+        self.cursor.select_entry(0);
+        store::save(&self.items);
+    }
+
+    /// A on the highlighted entry: queued ones jump the queue.
+    pub fn move_selected_to_top(&mut self) {
+        if let Some(i) = self.cursor.entry_index() {
+            if self.items.get(i).is_some_and(Download::is_queued) {
+                self.move_to_top(i);
+            }
+        }
+    }
+
+    /// Swap the highlighted queued entry with the next queued one up (`delta`
+    /// < 0) or down (L2 / R2), reordering the queue. Other entries don't move.
+    pub fn move_selected(&mut self, delta: i32) {
+        let Some(index) = self.cursor.entry_index() else {
+            return;
+        };
+        let Some(to) = queued_neighbour(&self.items, index, delta) else {
+            return;
+        };
+        self.items.swap(index, to);
+        self.cursor.select_entry(to);
+        store::save(&self.items);
+    }
+
+    /// Start a worker on the entry at `index`, continuing its `.part` when the
+    /// save name was already picked.
+    fn launch(&mut self, index: usize, sender: &UserEventSender) {
+        let d = &mut self.items[index];
+        let request = DownloadRequest {
+            url: d.url.clone(),
+            referer: d.referer.clone(),
+            suggested_name: d.suggested_name.clone(),
+        };
+        let resume = (!d.path.is_empty()).then(|| worker::Resume {
+            path: d.path.clone(),
+            validator: d.validator.clone(),
+        });
+        let shared = worker::spawn(&request, resume, &self.user_agent, &self.routes, sender);
+        d.shared = Some(shared);
+        d.state = State::Active;
+        store::save(&self.items);
     }
 
     /// Record a file the page built in JavaScript and handed us whole (see
     /// [`crate::browser::BlobDownload`]). No fetch to run, so the entry is born finished.
     pub fn save_captured(&mut self, item: crate::browser::BlobDownload) {
-        let (filename, path, size, state) = match self.write_captured(&item.filename, item.bytes) {
-            Ok((path, size)) => (file_name_of(&path), path, size, State::Done),
-            Err(e) => (item.filename, String::new(), 0, State::Failed(e)),
-        };
+        let (filename, path, size, state, checksums) =
+            match self.write_captured(&item.filename, item.bytes) {
+                Ok((path, size, digests)) => {
+                    (file_name_of(&path), path, size, State::Done, Some(digests))
+                }
+                Err(e) => (item.filename, String::new(), 0, State::Failed(e), None),
+            };
         self.items.insert(
             0,
             Download {
@@ -148,50 +479,103 @@ impl Downloads {
                 total: size,
                 time: history::now_unix(),
                 state,
+                referer: None,
+                suggested_name: None,
+                validator: None,
+                retry_in: 0,
+                unpack: None,
+                checksums,
+                verdict: Verdict::Unchecked,
+                verify: None,
                 shared: None,
             },
         );
         store::save(&self.items);
     }
 
-    /// Write captured bytes into the download dir under a free name.
+    /// Write captured bytes into the download dir (or the folder routed for the
+    /// name) under a free name, hashing them; there is nothing to verify against.
     fn write_captured(
         &self,
         filename: &str,
         bytes: Result<Vec<u8>, String>,
-    ) -> Result<(String, u64), String> {
+    ) -> Result<(String, u64, Digests), String> {
         let bytes = bytes?;
-        std::fs::create_dir_all(&self.dir).map_err(|e| format!("create dir: {e}"))?;
-        let path = worker::unique_path(&self.dir, filename);
+        let dir = self.routes.dir_for(filename);
+        std::fs::create_dir_all(dir).map_err(|e| format!("create dir: {e}"))?;
+        let path = worker::unique_path(dir, filename);
         std::fs::write(&path, &bytes).map_err(|e| format!("write: {e}"))?;
-        Ok((path, bytes.len() as u64))
+        let mut hasher = checksum::Hasher::new();
+        hasher.update(&bytes);
+        Ok((path, bytes.len() as u64, hasher.finish()))
     }
 
-    /// Pull progress from the worker threads into the entries and record finishes
-    /// (which also persists). Called once per frame; cheap when nothing is active.
-    pub fn poll(&mut self) {
-        let mut finished = false;
+    /// Pull progress from the worker threads into the entries and record finishes,
+    /// pauses and newly picked save names (which also persists), then fill the
+    /// slots that freed up. Called once per frame; cheap when nothing is active.
+    pub fn poll(&mut self, sender: &UserEventSender) {
+        let mut changed = false;
+        let (extract, delete_archive) = (self.extract, self.delete_archive);
+        let lookup_agent = self.checksum_lookup.then_some(self.user_agent.as_str());
         for d in &mut self.items {
+            changed |= d.poll_unpack();
+            changed |= d.poll_verify(extract, delete_archive, sender);
             let Some(shared) = &d.shared else { continue };
             d.received = shared.received.load(Ordering::Relaxed);
             d.total = shared.total.load(Ordering::Relaxed);
-            if let Some(path) = shared.path.lock().unwrap().take() {
-                d.filename = file_name_of(&path);
-                d.path = path;
+            d.retry_in = shared.retry_in.load(Ordering::Relaxed);
+            if let Some(path) = shared.path.lock().unwrap().as_deref() {
+                if d.path != path {
+                    d.filename = file_name_of(path);
+                    d.path = path.to_string();
+                    changed = true;
+                }
+            }
+            if let Some(validator) = shared.validator.lock().unwrap().as_deref() {
+                if d.validator.as_deref() != Some(validator) {
+                    d.validator = Some(validator.to_string());
+                    changed = true;
+                }
             }
             let result = shared.result.lock().unwrap().take();
             if let Some(result) = result {
                 d.state = match result {
                     Ok(()) => State::Done,
-                    Err(e) => State::Failed(e),
+                    Err(_) if shared.paused() => State::Paused(None),
+                    Err(worker::Failure::Retry(e)) => State::Paused(Some(e)),
+                    Err(worker::Failure::Fatal(e)) => State::Failed(e),
                 };
                 d.time = history::now_unix();
+                d.retry_in = 0;
+                if let Some((digests, verdict)) = shared.checksums.lock().unwrap().take() {
+                    d.checksums = Some(digests);
+                    d.verdict = verdict;
+                }
                 d.shared = None;
-                finished = true;
+                if matches!(d.state, State::Done) {
+                    // Shown as done first; a hash published beside the file is
+                    // looked up after, and the unpacking waits for it.
+                    let unchecked = matches!(d.verdict, Verdict::Unchecked);
+                    let digests = d.checksums.clone().filter(|_| unchecked);
+                    match (lookup_agent, digests) {
+                        (Some(agent), Some(digests)) => {
+                            d.verify = Some(checksum::spawn_lookup(
+                                &d.url,
+                                &d.filename,
+                                agent,
+                                digests,
+                                sender,
+                            ));
+                        }
+                        _ => d.start_unpack(extract, delete_archive, sender),
+                    }
+                }
+                changed = true;
             }
         }
-        if finished {
+        if changed {
             store::save(&self.items);
+            self.schedule(sender);
         }
     }
 
@@ -199,17 +583,23 @@ impl Downloads {
         &self.items
     }
 
+    /// The folder `d` is saved in, for its row, when a route sent it somewhere
+    /// other than the download dir.
+    pub fn folder_label(&self, d: &Download) -> Option<String> {
+        self.routes.folder_label(&d.path)
+    }
+
     pub fn selected(&self) -> usize {
         self.cursor.selected()
     }
 
-    /// Number of downloads still in flight (drives the toolbar ⬇ indicator).
+    /// Number of downloads running now (drives the toolbar ⬇ indicator).
     pub fn active_count(&self) -> usize {
         self.items.iter().filter(|d| d.is_active()).count()
     }
 
     pub fn has_finished(&self) -> bool {
-        self.items.iter().any(|d| !d.is_active())
+        self.items.iter().any(Download::is_finished)
     }
 
     /// Whether the "Clear finished" top row (cursor index 0) is highlighted. Only
@@ -218,9 +608,11 @@ impl Downloads {
         self.cursor.on_reserved_row(self.items.len())
     }
 
-    /// Reset the highlight to the top (called when the menu opens).
+    /// Reset the highlight to the top and close the details (called when the
+    /// menu opens).
     pub fn reset(&mut self) {
         self.cursor.reset(self.items.len());
+        self.details = false;
     }
 
     /// Move the highlight by `dy` rows, clamped to the list.
@@ -239,7 +631,8 @@ impl Downloads {
     }
 
     /// X/✖ on an entry: cancel it if still active (it stays, turning Failed once the
-    /// worker stops), otherwise drop it from the list. The file on disk is kept either way.
+    /// worker stops), otherwise drop it from the list. A finished file on disk is
+    /// kept; an unfinished entry's `.part` goes with it.
     pub fn remove(&mut self, index: usize) {
         let Some(d) = self.items.get(index) else {
             return;
@@ -248,6 +641,7 @@ impl Downloads {
             shared.cancel.store(true, Ordering::Relaxed);
             return;
         }
+        d.remove_part();
         self.items.remove(index);
         self.clamp_selected();
         store::save(&self.items);
@@ -259,10 +653,17 @@ impl Downloads {
         }
     }
 
-    /// Drop all finished entries (active ones stay); persists.
+    /// Drop all finished entries (active, queued and paused ones stay), and a
+    /// failed one's `.part`; persists.
     pub fn clear_finished(&mut self) {
         let before = self.items.len();
-        self.items.retain(|d| d.is_active());
+        self.items.retain(|d| {
+            let keep = !d.is_finished();
+            if !keep {
+                d.remove_part();
+            }
+            keep
+        });
         if self.items.len() != before {
             self.clamp_selected();
             store::save(&self.items);
@@ -274,6 +675,47 @@ impl Downloads {
     }
 }
 
+/// Indices of the queued entries to start now, top-first, given at most `max`
+/// running (`0` for no limit).
+fn due(items: &[Download], max: usize) -> Vec<usize> {
+    let running = items.iter().filter(|d| d.is_active()).count();
+    let free = if max == 0 {
+        usize::MAX
+    } else {
+        max.saturating_sub(running)
+    };
+    (0..items.len())
+        .filter(|&i| items[i].is_queued())
+        .take(free)
+        .collect()
+}
+
+/// Where a newcomer joins the queue: queue order is list order, so behind the
+/// last entry waiting, or on top when none is.
+fn queue_end(items: &[Download]) -> usize {
+    items
+        .iter()
+        .rposition(Download::is_queued)
+        .map_or(0, |i| i + 1)
+}
+
+/// The queued entry the queued one at `index` swaps with when moved up
+/// (`delta` < 0) or down; `None` when it isn't queued or is already at that
+/// end of the queue.
+fn queued_neighbour(items: &[Download], index: usize, delta: i32) -> Option<usize> {
+    if delta == 0 || !items.get(index)?.is_queued() {
+        return None;
+    }
+    if delta < 0 {
+        items[..index].iter().rposition(Download::is_queued)
+    } else {
+        items[index + 1..]
+            .iter()
+            .position(Download::is_queued)
+            .map(|i| index + 1 + i)
+    }
+}
+
 fn file_name_of(path: &str) -> String {
     path.rsplit('/').next().unwrap_or(path).to_string()
 }
@@ -293,3 +735,75 @@ pub fn format_size(bytes: u64) -> String {
         format!("{value:.1} {}", UNITS[unit])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An entry with nothing but a name and a state.
+    fn entry(filename: &str, state: State) -> Download {
+        Download {
+            url: format!("https://example.test/{filename}"),
+            filename: filename.to_string(),
+            path: String::new(),
+            received: 0,
+            total: 0,
+            time: 0,
+            state,
+            referer: None,
+            suggested_name: None,
+            validator: None,
+            retry_in: 0,
+            unpack: None,
+            checksums: None,
+            verdict: Verdict::Unchecked,
+            verify: None,
+            shared: None,
+        }
+    }
+
+    /// Queued entries start top-first while the limit leaves room; `0` lifts it.
+    #[test]
+    fn queue_respects_the_limit() {
+        let items = vec![
+            entry("a", State::Active),
+            entry("b", State::Queued),
+            entry("c", State::Paused(None)),
+            entry("d", State::Queued),
+            entry("e", State::Queued),
+        ];
+        assert_eq!(due(&items, 1), Vec::<usize>::new());
+        assert_eq!(due(&items, 2), [1]);
+        assert_eq!(due(&items, 3), [1, 3]);
+        assert_eq!(due(&items, 0), [1, 3, 4]);
+    }
+
+    /// A newcomer waits behind the queued entries, or goes on top.
+    #[test]
+    fn newcomers_join_the_end_of_the_queue() {
+        let mut items = vec![entry("a", State::Active), entry("b", State::Done)];
+        assert_eq!(queue_end(&items), 0);
+        items.insert(0, entry("q1", State::Queued));
+        items.insert(2, entry("q2", State::Queued));
+        assert_eq!(queue_end(&items), 3);
+    }
+
+    /// L2 / R2 swap a queued entry with the next queued one, stepping over
+    /// the rest; other entries don't move.
+    #[test]
+    fn only_queued_entries_reorder() {
+        let items = vec![
+            entry("a", State::Queued),
+            entry("b", State::Active),
+            entry("c", State::Queued),
+            entry("d", State::Done),
+        ];
+        assert_eq!(queued_neighbour(&items, 2, -1), Some(0));
+        assert_eq!(queued_neighbour(&items, 0, 1), Some(2));
+        assert_eq!(queued_neighbour(&items, 0, -1), None);
+        assert_eq!(queued_neighbour(&items, 2, 1), None);
+        assert_eq!(queued_neighbour(&items, 1, 1), None);
+        assert_eq!(queued_neighbour(&items, 3, -1), None);
+        assert_eq!(queued_neighbour(&items, 9, 1), None);
+    }
+}
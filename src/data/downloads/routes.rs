@@ -0,0 +1,169 @@
+//! Per-type destination folders (`[downloads.routes]`): a file whose name
+//! matches a route is saved in that route's folder instead of the download dir,
+//! so ROMs land straight in e.g. `roms/gba/`. A key is a bare extension (`gba`,
+//! `tar.gz`) or a glob over the whole name (`*.pbp`, `sonic*.md`), matched
+//! case-insensitively; globs are tried first, longest first, then extensions.
+//! A relative folder is taken inside the download dir. The workers apply this
+//! once the save name is settled (see [`super::worker`]).
+
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+enum Pattern {
+    /// Lowercased, without the leading dot.
+    Extension(String),
+    /// Lowercased; `*` any run, `?` any one character.
+    Glob(Vec<char>),
+}
+
+impl Pattern {
+    fn matches(&self, name: &str) -> bool {
+        match self {
+            Pattern::Extension(ext) => name
+                .strip_suffix(ext.as_str())
+                .and_then(|stem| stem.strip_suffix('.'))
+                .is_some_and(|stem| !stem.is_empty()),
+            Pattern::Glob(glob) => {
+                let name: Vec<char> = name.chars().collect();
+                glob_match(glob, &name)
+            }
+        }
+    }
+}
+
+fn glob_match(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some('*'), _) => {
+            glob_match(&pattern[1..], name) || (!name.is_empty() && glob_match(pattern, &name[1..]))
+        }
+        (Some('?'), Some(_)) => glob_match(&pattern[1..], &name[1..]),
+        (Some(p), Some(c)) if p == c => glob_match(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+struct Route {
+    pattern: Pattern,
+    /// Destination, with a trailing separator.
+    dir: String,
+}
+
+/// The download dir and the routes out of it, shared with the workers.
+pub(super) struct Routes {
+    /// Default save directory, with a trailing separator.
+    dir: String,
+    routes: Vec<Route>,
+}
+
+impl Routes {
+    /// Routes from the `[downloads.routes]` table over the resolved download
+    /// dir `dir`. Entries with an empty key or folder are skipped.
+    pub fn new(dir: String, table: &BTreeMap<String, String>) -> Self {
+        let mut routes: Vec<Route> = table
+            .iter()
+            .filter_map(|(key, folder)| {
+                let key = key.trim().to_lowercase();
+                let folder = folder.trim().trim_end_matches('/');
+                if key.is_empty() || folder.is_empty() {
+                    log::warn!("downloads: route `{key}` = `{folder}` skipped");
+                    return None;
+                }
+                let pattern = if key.contains(['*', '?']) {
+                    Pattern::Glob(key.chars().collect())
+                } else {
+                    Pattern::Extension(key.trim_start_matches('.').to_string())
+                };
+                let dir = if folder.starts_with('/') {
+                    format!("{folder}/")
+                } else {
+                    format!("{dir}{folder}/")
+                };
+                Some(Route { pattern, dir })
+            })
+            .collect();
+        routes.sort_by_key(|route| match &route.pattern {
+            Pattern::Glob(glob) => (0, Reverse(glob.len())),
+            Pattern::Extension(_) => (1, Reverse(0)),
+        });
+        Self { dir, routes }
+    }
+
+    /// The download dir itself.
+    pub fn dir(&self) -> &str {
+        &self.dir
+    }
+
+    /// Folder (with a trailing separator) to save `filename` in.
+    pub fn dir_for(&self, filename: &str) -> &str {
+        let name = filename.to_lowercase();
+        self.routes
+            .iter()
+            .find(|route| route.pattern.matches(&name))
+            .map_or(&self.dir, |route| &route.dir)
+    }
+
+    /// How the folder of a file saved at `path` reads in its menu row: `None`
+    /// for the download dir itself, relative inside it, else in full.
+    pub fn folder_label(&self, path: &str) -> Option<String> {
+        let (parent, _) = path.rsplit_once('/')?;
+        let parent = format!("{parent}/");
+        if parent == self.dir {
+            return None;
+        }
+        Some(
+            parent
+                .strip_prefix(&self.dir)
+                .unwrap_or(&parent)
+                .to_string(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn routes(table: &[(&str, &str)]) -> Routes {
+        let table = table
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        Routes::new("/dl/".to_string(), &table)
+    }
+
+    /// Extensions match case-insensitively and only as a suffix after a dot;
+    /// a glob beats an extension, and a longer glob a shorter one.
+    #[test]
+    fn picks_the_most_specific_route() {
+        let r = routes(&[
+            ("gba", "roms/gba"),
+            ("zip", "archives"),
+            ("*.pbp", "/mnt/sd/psx/"),
+            ("sonic*.zip", "roms/megadrive"),
+            ("*.zip", "zips"),
+        ]);
+        assert_eq!(r.dir_for("Golden Sun.GBA"), "/dl/roms/gba/");
+        assert_eq!(r.dir_for("gba"), "/dl/");
+        assert_eq!(r.dir_for("notagba.txt"), "/dl/");
+        assert_eq!(r.dir_for("game.pbp"), "/mnt/sd/psx/");
+        assert_eq!(r.dir_for("Sonic 2.zip"), "/dl/roms/megadrive/");
+        assert_eq!(r.dir_for("tools.zip"), "/dl/zips/");
+        assert_eq!(r.dir_for("readme.pdf"), "/dl/");
+    }
+
+    /// Rows name a routed folder relative to the download dir when inside it.
+    #[test]
+    fn labels_the_folder() {
+        let r = routes(&[]);
+        assert_eq!(r.folder_label("/dl/game.gba"), None);
+        assert_eq!(
+            r.folder_label("/dl/roms/gba/game.gba").as_deref(),
+            Some("roms/gba/")
+        );
+        assert_eq!(
+            r.folder_label("/mnt/sd/psx/game.pbp").as_deref(),
+            Some("/mnt/sd/psx/")
+        );
+    }
+}
@@ -1,12 +1,24 @@
-//! Persistence of the download list: finished entries (done or failed) go to
-//! `downloads.toml` in the user data dir, mirroring `bookmarks.toml` /
-//! `history.toml`. Active downloads are never written — they can't be resumed
-//! across a restart, so they simply vanish from the list.
+//! Persistence of the download list: every entry goes to `downloads.toml` in the
+//! user data dir, mirroring `bookmarks.toml` / `history.toml`. An unfinished one
+//! also records how to pick up its `.part` (Referer, `If-Range` validator) and
+//! whether it was running or queued, in which case it is queued again at the
+//! next launch (see [`super::Downloads::resume_unfinished`]). The list's order
+//! is kept, and with it the queue's. A finished one keeps its hashes and what
+//! they were checked against.
 
-use super::{Download, State};
+use super::{Digests, Download, State, Unpack, Verdict};
 use serde::{Deserialize, Serialize};
 
-/// On-disk shape of a finished download.
+/// An unfinished download's state when it was saved.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Pending {
+    Queued,
+    Active,
+    Paused,
+}
+
+/// On-disk shape of a download.
 #[derive(Serialize, Deserialize)]
 struct DiskEntry {
     url: String,
@@ -15,9 +27,34 @@ struct DiskEntry {
     size: u64,
     #[serde(default)]
     time: u64,
-    /// `None` for a successful download.
+    /// Why a download failed, or paused on its own; `None` otherwise.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     error: Option<String>,
+    /// `None` once finished.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pending: Option<Pending>,
+    /// Full size of an unfinished download, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    total: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    referer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    suggested_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    validator: Option<String>,
+    /// Folder a zip was unpacked into.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    unpacked: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    unpack_error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checksums: Option<Digests>,
+    /// The published hash the file matched (see [`Verdict`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    verified: Option<String>,
+    /// The published hash the file didn't match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mismatch: Option<String>,
 }
 
 /// On-disk shape (a TOML table can't be a bare array, so wrap the list).
@@ -36,39 +73,91 @@ pub(super) fn load() -> Vec<Download> {
         .collect()
 }
 
-/// Best-effort persist of the finished entries; failures are logged, not fatal.
+/// Best-effort persist of the list; failures are logged, not fatal.
 pub(super) fn save(items: &[Download]) {
     let store = Store {
-        entries: items
-            .iter()
-            .filter(|d| !d.is_active())
-            .map(|d| DiskEntry {
-                url: d.url.clone(),
-                path: d.path.clone(),
-                size: d.received,
-                time: d.time,
-                error: match &d.state {
-                    State::Failed(e) => Some(e.clone()),
-                    _ => None,
-                },
-            })
-            .collect(),
+        entries: items.iter().map(into_entry).collect(),
     };
     crate::data::save_toml("downloads.toml", &store, "downloads");
 }
 
+fn into_entry(d: &Download) -> DiskEntry {
+    let pending = match d.state {
+        State::Queued => Some(Pending::Queued),
+        State::Active => Some(Pending::Active),
+        State::Paused(_) => Some(Pending::Paused),
+        State::Done | State::Failed(_) => None,
+    };
+    let unfinished = pending.is_some();
+    // One still unpacking is saved as not unpacked.
+    let (unpacked, unpack_error) = match &d.unpack {
+        Some(Unpack::Done(dir)) => (Some(dir.clone()), None),
+        Some(Unpack::Failed(e)) => (None, Some(e.clone())),
+        Some(Unpack::Running(_)) | None => (None, None),
+    };
+    let (verified, mismatch) = match &d.verdict {
+        Verdict::Verified(what) => (Some(what.clone()), None),
+        Verdict::Mismatch(what) => (None, Some(what.clone())),
+        Verdict::Unchecked => (None, None),
+    };
+    DiskEntry {
+        url: d.url.clone(),
+        path: d.path.clone(),
+        size: d.received,
+        time: d.time,
+        error: match &d.state {
+            State::Failed(e) | State::Paused(Some(e)) => Some(e.clone()),
+            _ => None,
+        },
+        total: (unfinished && d.total > 0).then_some(d.total),
+        pending,
+        referer: d.referer.clone(),
+        suggested_name: d.suggested_name.clone().filter(|_| unfinished),
+        validator: d.validator.clone(),
+        unpacked,
+        unpack_error,
+        checksums: d.checksums.clone(),
+        verified,
+        mismatch,
+    }
+}
+
+/// Restored entries have no worker yet; active and queued ones get one from
+/// [`super::Downloads::resume_unfinished`].
 fn into_download(entry: DiskEntry) -> Download {
+    let (state, total) = match entry.pending {
+        Some(Pending::Queued) => (State::Queued, entry.total.unwrap_or(0)),
+        Some(Pending::Active) => (State::Active, entry.total.unwrap_or(0)),
+        Some(Pending::Paused) => (State::Paused(entry.error.clone()), entry.total.unwrap_or(0)),
+        None => match entry.error {
+            Some(ref e) => (State::Failed(e.clone()), entry.size),
+            None => (State::Done, entry.size),
+        },
+    };
     Download {
         filename: entry_filename(&entry),
         url: entry.url,
         received: entry.size,
-        total: entry.size,
+        total,
         time: entry.time,
-        state: match entry.error {
-            Some(e) => State::Failed(e),
-            None => State::Done,
+        state,
+        referer: entry.referer,
+        suggested_name: entry.suggested_name,
+        validator: entry.validator,
+        retry_in: 0,
+        unpack: match (entry.unpacked, entry.unpack_error) {
+            (Some(dir), _) => Some(Unpack::Done(dir)),
+            (None, Some(e)) => Some(Unpack::Failed(e)),
+            (None, None) => None,
+        },
+        checksums: entry.checksums,
+        verdict: match (entry.verified, entry.mismatch) {
+            (Some(what), _) => Verdict::Verified(what),
+            (None, Some(what)) => Verdict::Mismatch(what),
+            (None, None) => Verdict::Unchecked,
         },
         path: entry.path,
+        verify: None,
         shared: None,
     }
 }
@@ -1,50 +1,106 @@
 //! One background thread per file (ureq is blocking): stream to `<path>.part`,
 //! rename into place. Requests present as the browser (User-Agent, Referer) and
 //! the save name is picked from the response (Content-Disposition, `download`
-//! attribute, redirect target). A watchdog fails stalled transfers (ureq has
-//! no idle timeout).
-
+//! attribute, redirect target), and with it the folder (see [`super::routes`]).
+//! A `.part` left by an earlier attempt, a pause or
+//! a previous session is continued with `Range`, guarded by `If-Range` so a file
+//! that changed on the server starts over. Dropped connections and server errors
+//! retry with backoff; a watchdog abandons stalled transfers (ureq has no idle
+//! timeout) and starts the next attempt itself. Once the attempts run out the
+//! `.part` stays for the user to resume; only a cancel removes it. The body is
+//! hashed as it streams and the finished file checked against a hash in its
+//! link (see [`super::checksum`]); one published beside it is looked up after.
+
+use super::checksum::{self, Digests, Verdict};
+use super::routes::Routes;
 use crate::browser::DownloadRequest;
 use crate::event::user::{UserEvent, UserEventSender};
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
-use std::sync::{Arc, LazyLock, Mutex};
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 /// Deadline for each pre-body phase of the request (DNS, connect, headers).
 const PHASE_TIMEOUT: Duration = Duration::from_secs(30);
 
-/// No received bytes for this long fails the transfer as stalled.
+/// No received bytes for this long abandons the attempt as stalled.
 const STALL_TIMEOUT: Duration = Duration::from_secs(60);
 
-/// How often the watchdog checks progress and the cancel flag.
+/// How often the watchdog checks progress and the stop flags.
 const WATCH_INTERVAL: Duration = Duration::from_secs(1);
 
 /// Cancel grace before the watchdog resolves the entry for a blocked worker.
 const CANCEL_GRACE: Duration = Duration::from_secs(3);
 
+/// Attempts per download, the first included, before it gives up for now.
+const MAX_ATTEMPTS: u32 = 6;
+
+/// Wait before attempt `n` (1-based retries): 2 s, doubling up to a minute.
+fn backoff(n: u32) -> Duration {
+    Duration::from_secs((1u64 << n.min(6)).min(60))
+}
+
 /// Worker → main-thread progress; `result` is write-once via `done`.
 pub(super) struct Shared {
     pub received: AtomicU64,
     pub total: AtomicU64,
     pub cancel: AtomicBool,
-    /// Destination path, set once the response headers picked the final name.
+    /// Stop like `cancel`, but keep the `.part` for a later resume.
+    pub pause: AtomicBool,
+    /// Destination path, set once the response headers picked the final name
+    /// (up front when resuming).
     pub path: Mutex<Option<String>>,
+    /// The response's strong ETag or Last-Modified, sent back as `If-Range`.
+    pub validator: Mutex<Option<String>>,
+    /// Seconds left before the next attempt while a retry waits, else `0`.
+    pub retry_in: AtomicU64,
+    /// Set while the worker re-reads a `.part` instead of receiving; the
+    /// watchdog doesn't take that for a stall.
+    checking: AtomicBool,
+    /// The finished file's hashes and how they checked out, set just before a
+    /// successful `result`.
+    pub checksums: Mutex<Option<(Digests, Verdict)>>,
+    /// The attempt allowed to touch the files; the watchdog moves it past a
+    /// stalled one, which then gives up whenever its read returns.
+    attempt: AtomicU32,
     /// Claimed (exactly once) by whoever stores `result`.
     done: AtomicBool,
-    pub result: Mutex<Option<Result<(), String>>>,
+    pub result: Mutex<Option<Result<(), Failure>>>,
 }
 
-/// Shared agent with per-phase deadlines; mid-body stalls are the watchdog's job.
-fn agent() -> &'static ureq::Agent {
-    static AGENT: LazyLock<ureq::Agent> = LazyLock::new(|| {
-        ureq::Agent::config_builder()
+impl Shared {
+    /// Stopped for a later resume: paused, and not cancelled since.
+    pub fn paused(&self) -> bool {
+        self.pause.load(Ordering::Relaxed) && !self.cancel.load(Ordering::Relaxed)
+    }
+
+    /// Why the worker was told to stop, if it was.
+    fn stop_reason(&self) -> Option<String> {
+        if self.cancel.load(Ordering::Relaxed) {
+            Some("cancelled".to_string())
+        } else if self.pause.load(Ordering::Relaxed) {
+            Some("paused".to_string())
+        } else {
+            None
+        }
+    }
+}
+
+/// Where a paused download, or one a previous session left running, stopped.
+pub(super) struct Resume {
+    pub path: String,
+    pub validator: Option<String>,
+}
+
+/// An agent for `url` with per-phase deadlines; mid-body stalls are the
+/// watchdog's job.
+fn agent(url: &str) -> ureq::Agent {
+    crate::net::agent_with(url, |config| {
+        config
             .timeout_resolve(Some(PHASE_TIMEOUT))
             .timeout_connect(Some(PHASE_TIMEOUT))
             .timeout_recv_response(Some(PHASE_TIMEOUT))
-            .build()
-            .new_agent()
-    });
-    &AGENT
+    })
 }
 
 /// Everything one worker needs to fetch its file.
@@ -53,133 +109,367 @@ struct Job {
     referer: Option<String>,
     suggested_name: Option<String>,
     user_agent: String,
-    dir: String,
+    routes: Arc<Routes>,
+}
+
+/// Why an attempt ended without the file.
+pub(super) enum Failure {
+    /// Worth another attempt (a lost connection, a server error); the `.part`
+    /// stays for it to continue. As a download's result, the attempts ran out
+    /// and it waits for the user to resume it.
+    Retry(String),
+    /// Final: a refusal, a local file problem, or a stop.
+    Fatal(String),
 }
 
-/// Spawn the worker and its watchdog; the destination path arrives through the
-/// returned handle once known.
+/// Spawn the worker and its watchdog, continuing `resume`'s `.part` if given;
+/// otherwise the destination path arrives through the returned handle once
+/// known.
 pub(super) fn spawn(
     request: &DownloadRequest,
+    resume: Option<Resume>,
     user_agent: &str,
-    dir: &str,
+    routes: &Arc<Routes>,
     sender: &UserEventSender,
 ) -> Arc<Shared> {
+    let (path, validator) = match resume {
+        Some(resume) => (Some(resume.path), resume.validator),
+        None => (None, None),
+    };
     let shared = Arc::new(Shared {
         received: AtomicU64::new(0),
         total: AtomicU64::new(0),
         cancel: AtomicBool::new(false),
-        path: Mutex::new(None),
+        pause: AtomicBool::new(false),
+        path: Mutex::new(path),
+        validator: Mutex::new(validator),
+        retry_in: AtomicU64::new(0),
+        checking: AtomicBool::new(false),
+        checksums: Mutex::new(None),
+        attempt: AtomicU32::new(0),
         done: AtomicBool::new(false),
         result: Mutex::new(None),
     });
+    let job = Arc::new(Job {
+        url: request.url.clone(),
+        referer: request.referer.clone(),
+        suggested_name: request.suggested_name.clone(),
+        user_agent: user_agent.to_string(),
+        routes: routes.clone(),
+    });
     {
-        let job = Job {
-            url: request.url.clone(),
-            referer: request.referer.clone(),
-            suggested_name: request.suggested_name.clone(),
-            user_agent: user_agent.to_string(),
-            dir: dir.to_string(),
-        };
-        let shared = shared.clone();
-        let sender = sender.clone();
-        std::thread::spawn(move || run(job, shared, sender));
+        let (job, shared, sender) = (job.clone(), shared.clone(), sender.clone());
+        std::thread::spawn(move || run(job, shared, sender, 0));
     }
     {
         let shared = shared.clone();
         let sender = sender.clone();
-        std::thread::spawn(move || watch(shared, sender));
+        std::thread::spawn(move || watch(job, shared, sender));
     }
     shared
 }
 
-/// Worker-thread entry: fetch, then publish the result.
-fn run(job: Job, shared: Arc<Shared>, sender: UserEventSender) {
-    let result = fetch(&job, &shared, &sender);
-    if let Err(e) = &result {
-        log::warn!("download `{}` failed: {e}", job.url);
+/// Worker-thread entry: fetch from `attempt` on (past 0 when the watchdog
+/// replaced a stalled one), retrying with backoff, then publish the result.
+fn run(job: Arc<Job>, shared: Arc<Shared>, sender: UserEventSender, mut attempt: u32) {
+    loop {
+        if attempt > 0 && !wait_backoff(&shared, &sender, attempt) {
+            let reason = shared.stop_reason().unwrap_or_default();
+            finish(&shared, Err(Failure::Fatal(reason)), &sender);
+            return;
+        }
+        let result = fetch(&job, &shared, &sender, attempt);
+        if shared.attempt.load(Ordering::Relaxed) != attempt {
+            // Abandoned by the watchdog; a newer attempt owns the entry.
+            return;
+        }
+        if let Err(Failure::Retry(e)) = &result {
+            if attempt + 1 < MAX_ATTEMPTS {
+                attempt += 1;
+                log::warn!("download `{}`: {e}; retry {attempt}", job.url);
+                shared.attempt.store(attempt, Ordering::Relaxed);
+                continue;
+            }
+        }
+        match &result {
+            Err(_) if shared.paused() => log::info!("download `{}` paused", job.url),
+            Err(Failure::Retry(e)) => log::warn!("download `{}` gave up: {e}", job.url),
+            Err(Failure::Fatal(e)) => log::warn!("download `{}` failed: {e}", job.url),
+            Ok(()) => {}
+        }
+        finish(&shared, result, &sender);
+        return;
+    }
+}
+
+/// Sleep out the backoff before `attempt`, counting down in `retry_in` for the
+/// row; `false` if the download was stopped meanwhile.
+fn wait_backoff(shared: &Shared, sender: &UserEventSender, attempt: u32) -> bool {
+    let mut left = backoff(attempt).as_secs();
+    while left > 0 && shared.stop_reason().is_none() {
+        shared.retry_in.store(left, Ordering::Relaxed);
+        sender.send(UserEvent::DownloadUpdate);
+        std::thread::sleep(Duration::from_secs(1));
+        left -= 1;
     }
-    finish(&shared, result, &sender);
+    shared.retry_in.store(0, Ordering::Relaxed);
+    shared.stop_reason().is_none()
 }
 
 /// Publish `result` once (first of worker/watchdog wins) and wake the main loop.
-fn finish(shared: &Shared, result: Result<(), String>, sender: &UserEventSender) {
+/// A cancel removes the `.part`; any other end keeps it for a resume.
+fn finish(shared: &Shared, result: Result<(), Failure>, sender: &UserEventSender) {
     if shared.done.swap(true, Ordering::Relaxed) {
         return;
     }
+    if result.is_err() && shared.cancel.load(Ordering::Relaxed) {
+        if let Some(path) = shared.path.lock().unwrap().as_deref() {
+            let _ = std::fs::remove_file(format!("{path}.part"));
+        }
+    }
     *shared.result.lock().unwrap() = Some(result);
     sender.send(UserEvent::DownloadUpdate);
 }
 
-/// Resolve what the worker can't: an unacknowledged cancel or a stalled socket.
-fn watch(shared: Arc<Shared>, sender: UserEventSender) {
+/// Resolve what the worker can't: an unacknowledged stop or a stalled socket.
+fn watch(job: Arc<Job>, shared: Arc<Shared>, sender: UserEventSender) {
     let mut last_received = 0;
     let mut last_change = Instant::now();
-    let mut cancelled_at: Option<Instant> = None;
+    let mut stopped_at: Option<Instant> = None;
     loop {
         std::thread::sleep(WATCH_INTERVAL);
         if shared.done.load(Ordering::Relaxed) {
             return;
         }
         let received = shared.received.load(Ordering::Relaxed);
-        if received != last_received {
+        // A retry waiting out its backoff isn't a stall, nor is hashing.
+        if received != last_received
+            || shared.retry_in.load(Ordering::Relaxed) > 0
+            || shared.checking.load(Ordering::Relaxed)
+        {
             last_received = received;
             last_change = Instant::now();
         }
-        if shared.cancel.load(Ordering::Relaxed) {
-            if cancelled_at.get_or_insert_with(Instant::now).elapsed() >= CANCEL_GRACE {
-                finish(&shared, Err("cancelled".to_string()), &sender);
+        if let Some(reason) = shared.stop_reason() {
+            if stopped_at.get_or_insert_with(Instant::now).elapsed() >= CANCEL_GRACE {
+                // The blocked worker must leave the files alone when it wakes.
+                shared.attempt.fetch_add(1, Ordering::Relaxed);
+                finish(&shared, Err(Failure::Fatal(reason)), &sender);
                 return;
             }
         } else if last_change.elapsed() >= STALL_TIMEOUT {
-            shared.cancel.store(true, Ordering::Relaxed);
-            finish(
-                &shared,
-                Err(format!("stalled: no data for {}s", STALL_TIMEOUT.as_secs())),
-                &sender,
-            );
-            return;
+            let stalled = format!("stalled: no data for {}s", STALL_TIMEOUT.as_secs());
+            let attempt = shared.attempt.fetch_add(1, Ordering::Relaxed) + 1;
+            if attempt >= MAX_ATTEMPTS {
+                log::warn!("download `{}` gave up: {stalled}", job.url);
+                finish(&shared, Err(Failure::Retry(stalled)), &sender);
+                return;
+            }
+            log::warn!("download `{}`: {stalled}; retry {attempt}", job.url);
+            let (job, shared, sender) = (job.clone(), shared.clone(), sender.clone());
+            std::thread::spawn(move || run(job, shared, sender, attempt));
+            last_change = Instant::now();
         }
     }
 }
 
-/// Fetch the URL, pick the save name from the response, and stream to
-/// `<path>.part`; renamed into place on success, removed on failure.
-fn fetch(job: &Job, shared: &Shared, sender: &UserEventSender) -> Result<(), String> {
+/// Fetch the URL and stream it to `<path>.part`, renamed into place on
+/// success. A `.part` already there is continued when the server honours the
+/// range, and rewritten from the start when it doesn't; a new download picks
+/// its save name from the response, and its folder by that name. The hashes
+/// cover the whole file, a continued `.part` included.
+fn fetch(
+    job: &Job,
+    shared: &Shared,
+    sender: &UserEventSender,
+    attempt: u32,
+) -> Result<(), Failure> {
     use ureq::ResponseExt;
 
-    let mut request = agent().get(&job.url).header("User-Agent", &job.user_agent);
+    let known = shared.path.lock().unwrap().clone();
+    let offset = known
+        .as_ref()
+        .and_then(|path| std::fs::metadata(format!("{path}.part")).ok())
+        .map_or(0, |meta| meta.len());
+    let mut request = agent(&job.url)
+        .get(&job.url)
+        .header("User-Agent", &job.user_agent);
     if let Some(referer) = &job.referer {
         request = request.header("Referer", referer);
     }
-    let response = request.call().map_err(|e| e.to_string())?;
-    if let Some(total) = crate::net::content_length(response.headers()) {
+    if offset > 0 {
+        request = request.header("Range", format!("bytes={offset}-"));
+        if let Some(validator) = shared.validator.lock().unwrap().as_deref() {
+            request = request.header("If-Range", validator);
+        }
+    }
+    let response = match request.call() {
+        Ok(response) => response,
+        // The `.part` is longer than the file now is: start over.
+        Err(ureq::Error::StatusCode(416)) if offset > 0 => {
+            if let Some(path) = &known {
+                let _ = std::fs::remove_file(format!("{path}.part"));
+            }
+            return Err(Failure::Retry("range not satisfiable".to_string()));
+        }
+        Err(e) => return Err(failure(e)),
+    };
+    let range = content_range(response.headers());
+    let resumed = response.status() == 206;
+    if resumed && range.map(|(start, _)| start) != Some(offset) {
+        if let Some(path) = &known {
+            let _ = std::fs::remove_file(format!("{path}.part"));
+        }
+        return Err(Failure::Retry("server sent the wrong range".to_string()));
+    }
+    let base = if resumed { offset } else { 0 };
+    let total = match range {
+        Some((_, Some(total))) if resumed => Some(total),
+        _ => crate::net::content_length(response.headers()).map(|len| base + len),
+    };
+    if let Some(total) = total {
         shared.total.store(total, Ordering::Relaxed);
     }
+    shared.received.store(base, Ordering::Relaxed);
+    let validator = validator_of(response.headers());
+    if validator.is_some() || !resumed {
+        *shared.validator.lock().unwrap() = validator;
+    }
 
-    let name = pick_filename(
-        response.headers(),
-        response.get_uri().path(),
-        job.suggested_name.as_deref(),
-        &job.url,
-    );
-    let (path, part, file) = create_unique(&job.dir, &name)?;
-    log::info!("downloading `{}` -> `{path}`", job.url);
+    let (path, part, file) = match known {
+        Some(path) => {
+            let part = format!("{path}.part");
+            let file = std::fs::OpenOptions::new()
+                .create(true)
+                .write(true)
+                .append(resumed)
+                .truncate(!resumed)
+                .open(&part)
+                .map_err(|e| Failure::Fatal(format!("open: {e}")))?;
+            (path, part, file)
+        }
+        None => {
+            let name = pick_filename(
+                response.headers(),
+                response.get_uri().path(),
+                job.suggested_name.as_deref(),
+                &job.url,
+            );
+            let dir = job.routes.dir_for(&name);
+            std::fs::create_dir_all(dir).map_err(|e| Failure::Fatal(format!("create dir: {e}")))?;
+            create_unique(dir, &name).map_err(Failure::Fatal)?
+        }
+    };
+    if resumed {
+        log::info!("resuming `{}` -> `{path}` at {offset} bytes", job.url);
+    } else {
+        log::info!("downloading `{}` -> `{path}`", job.url);
+    }
     *shared.path.lock().unwrap() = Some(path.clone());
     sender.send(UserEvent::DownloadUpdate);
 
+    let mut hasher = if resumed {
+        shared.checking.store(true, Ordering::Relaxed);
+        let seeded = checksum::Hasher::from_file(&part);
+        shared.checking.store(false, Ordering::Relaxed);
+        seeded.map_err(|e| Failure::Fatal(format!("read: {e}")))?
+    } else {
+        checksum::Hasher::new()
+    };
     let reader = response.into_body().into_reader();
-    let result = crate::net::stream(reader, file, |_, received, due| {
-        shared.received.store(received, Ordering::Relaxed);
+    let writer = AttemptWriter {
+        file,
+        shared,
+        attempt,
+    };
+    let result = crate::net::stream(reader, writer, |chunk, received, due| {
+        hasher.update(chunk);
+        shared.received.store(base + received, Ordering::Relaxed);
         if due {
             sender.send(UserEvent::DownloadUpdate);
         }
-        !shared.cancel.load(Ordering::Relaxed)
-    })
-    .and_then(|()| std::fs::rename(&part, &path).map_err(|e| format!("rename: {e}")));
-    if result.is_err() {
-        let _ = std::fs::remove_file(&part);
+        shared.stop_reason().is_none()
+    });
+    match result {
+        Ok(()) => {
+            if shared.attempt.load(Ordering::Relaxed) != attempt {
+                // Abandoned while reading: the `.part` is a newer attempt's now.
+                return Err(Failure::Fatal("abandoned".to_string()));
+            }
+            std::fs::rename(&part, &path).map_err(|e| Failure::Fatal(format!("rename: {e}")))?;
+            let digests = hasher.finish();
+            let verdict = Verdict::of(&digests, checksum::from_link(&job.url));
+            if let Verdict::Mismatch(what) = &verdict {
+                log::warn!("download `{}`: {what} doesn't match", job.url);
+            }
+            *shared.checksums.lock().unwrap() = Some((digests, verdict));
+            Ok(())
+        }
+        Err(e) => Err(match shared.stop_reason() {
+            Some(reason) => Failure::Fatal(reason),
+            None => Failure::Retry(e),
+        }),
+    }
+}
+
+/// Sort a failed request: lost connections and server trouble are worth
+/// another attempt, a refusal (4xx) isn't.
+fn failure(e: ureq::Error) -> Failure {
+    match e {
+        ureq::Error::StatusCode(code) if code < 500 && !matches!(code, 408 | 429) => {
+            Failure::Fatal(e.to_string())
+        }
+        ureq::Error::StatusCode(_)
+        | ureq::Error::Io(_)
+        | ureq::Error::Timeout(_)
+        | ureq::Error::HostNotFound
+        | ureq::Error::ConnectionFailed => Failure::Retry(e.to_string()),
+        e => Failure::Fatal(e.to_string()),
+    }
+}
+
+/// `(first byte, complete length)` from `Content-Range: bytes a-b/len`; the
+/// length is `None` when the server sent `*`.
+fn content_range(headers: &ureq::http::HeaderMap) -> Option<(u64, Option<u64>)> {
+    let value = headers.get("Content-Range")?.to_str().ok()?;
+    let (span, len) = value.trim().strip_prefix("bytes ")?.split_once('/')?;
+    let start = span.split_once('-')?.0.trim().parse().ok()?;
+    Some((start, len.trim().parse().ok()))
+}
+
+/// What `If-Range` may send back: a strong ETag (weak ones aren't allowed
+/// there), else Last-Modified.
+fn validator_of(headers: &ureq::http::HeaderMap) -> Option<String> {
+    let get = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    };
+    get("ETag")
+        .filter(|etag| !etag.starts_with("W/"))
+        .or_else(|| get("Last-Modified"))
+}
+
+/// One attempt's `.part` writer. It refuses once the attempt was abandoned, so
+/// a stalled read that returns late can't write into its successor's file.
+struct AttemptWriter<'a> {
+    file: std::fs::File,
+    shared: &'a Shared,
+    attempt: u32,
+}
+
+impl Write for AttemptWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.shared.attempt.load(Ordering::Relaxed) != self.attempt {
+            return Err(std::io::Error::other("abandoned"));
+        }
+        self.file.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()
     }
-    result
 }
 
 /// Save-name precedence (per the HTML spec): Content-Disposition, the page's
@@ -441,7 +731,7 @@ mod tests {
         assert_eq!(sanitize("  .. "), None);
     }
 
-    use std::io::{BufRead, BufReader, Write};
+    use std::io::{BufRead, BufReader};
     use std::net::TcpListener;
 
     /// Fresh download dir (with the trailing separator the workers expect).
@@ -466,10 +756,15 @@ mod tests {
         }
     }
 
+    /// Routes saving everything in `dir`.
+    fn routes(dir: &str) -> Arc<Routes> {
+        Arc::new(Routes::new(dir.to_string(), &Default::default()))
+    }
+
     fn wait_result(shared: &Shared) -> Result<(), String> {
         for _ in 0..1000 {
             if let Some(result) = shared.result.lock().unwrap().take() {
-                return result;
+                return result.map_err(|(Failure::Retry(e) | Failure::Fatal(e))| e);
             }
             std::thread::sleep(Duration::from_millis(10));
         }
@@ -510,7 +805,7 @@ mod tests {
             referer: Some("https://example.test/page".to_string()),
             suggested_name: None,
         };
-        let shared = spawn(&request, "retsurf-test-ua", &dir, &sender);
+        let shared = spawn(&request, None, "retsurf-test-ua", &routes(&dir), &sender);
 
         assert_eq!(wait_result(&shared), Ok(()));
         let path = shared.path.lock().unwrap().take().expect("published path");
@@ -550,7 +845,7 @@ mod tests {
             referer: None,
             suggested_name: None,
         };
-        let shared = spawn(&request, "retsurf-test-ua", &dir, &sender);
+        let shared = spawn(&request, None, "retsurf-test-ua", &routes(&dir), &sender);
 
         for _ in 0..1000 {
             if shared.received.load(Ordering::Relaxed) > 0 {
@@ -567,4 +862,85 @@ mod tests {
         assert!(!std::path::Path::new(&format!("{path}.part")).exists());
         std::fs::remove_dir_all(dir.trim_end_matches('/')).expect("cleanup");
     }
+
+    /// A `.part` from an earlier session is continued with `Range` and
+    /// `If-Range`, and the 206 body is appended to it and hashed after it.
+    #[test]
+    fn resume_continues_the_partial() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let port = listener.local_addr().expect("addr").port();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().expect("accept");
+            let request = read_request(&mut stream);
+            stream
+                .write_all(
+                    b"HTTP/1.1 206 Partial Content\r\nContent-Length: 2\r\n\
+                      Content-Range: bytes 3-4/5\r\nETag: \"v1\"\r\n\r\nlo",
+                )
+                .expect("response");
+            request
+        });
+
+        let dir = temp_dir("resume");
+        let path = format!("{dir}game.zip");
+        std::fs::write(format!("{path}.part"), b"hel").expect("partial");
+        let sender = UserEventSender::new();
+        let request = DownloadRequest {
+            url: format!("http://127.0.0.1:{port}/game.zip"),
+            referer: None,
+            suggested_name: None,
+        };
+        let resume = Resume {
+            path: path.clone(),
+            validator: Some("\"v1\"".to_string()),
+        };
+        let shared = spawn(
+            &request,
+            Some(resume),
+            "retsurf-test-ua",
+            &routes(&dir),
+            &sender,
+        );
+
+        assert_eq!(wait_result(&shared), Ok(()));
+        assert_eq!(std::fs::read(&path).expect("saved file"), b"hello");
+        assert_eq!(shared.total.load(Ordering::Relaxed), 5);
+        // The hashes cover the resumed bytes as well as the new ones.
+        let (digests, verdict) = shared.checksums.lock().unwrap().take().expect("hashed");
+        assert_eq!(digests.md5, "5d41402abc4b2a76b9719d911017c592");
+        assert_eq!(verdict, Verdict::Unchecked);
+        let request = server.join().expect("server");
+        let has = |h: &str| request.iter().any(|l| l.eq_ignore_ascii_case(h));
+        assert!(has("range: bytes=3-"));
+        assert!(has("if-range: \"v1\""));
+        std::fs::remove_dir_all(dir.trim_end_matches('/')).expect("cleanup");
+    }
+
+    /// Content-Range gives the resume offset and the full size; only a strong
+    /// ETag (or Last-Modified) may guard a range.
+    #[test]
+    fn range_headers() {
+        let mut h = ureq::http::HeaderMap::new();
+        h.insert(
+            "Content-Range",
+            ureq::http::HeaderValue::from_static("bytes 100-199/1000"),
+        );
+        h.insert("ETag", ureq::http::HeaderValue::from_static("W/\"weak\""));
+        h.insert(
+            "Last-Modified",
+            ureq::http::HeaderValue::from_static("Tue, 01 Sep 2026 10:00:00 GMT"),
+        );
+        assert_eq!(content_range(&h), Some((100, Some(1000))));
+        assert_eq!(
+            validator_of(&h).as_deref(),
+            Some("Tue, 01 Sep 2026 10:00:00 GMT")
+        );
+        h.insert(
+            "Content-Range",
+            ureq::http::HeaderValue::from_static("bytes 5-9/*"),
+        );
+        h.insert("ETag", ureq::http::HeaderValue::from_static("\"strong\""));
+        assert_eq!(content_range(&h), Some((5, None)));
+        assert_eq!(validator_of(&h).as_deref(), Some("\"strong\""));
+    }
 }
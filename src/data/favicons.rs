@@ -0,0 +1,188 @@
+//! Favicon cache: each site's icon as a small RGBA PNG under
+//! `<data>/cache/favicons/`, one file per host (see [`crate::config::cache_dir`]).
+//! Written when a page reports its favicon (the delegate hands the decoded
+//! pixels to the main loop, see [`crate::browser`], which queues them on a
+//! [`Writer`] so the shrink and PNG encode stay off the UI thread); read back by
+//! [`crate::ui`] for the menu rows and the speed-dial tiles. Losing the directory only costs
+//! the icons until the sites are visited again.
+
+use crate::config;
+use crate::event::user::{UserEvent, UserEventSender};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+
+/// Longest side of a stored icon (px). Rows and tiles draw them at 16–32
+/// logical px, so anything larger is wasted disk and texture memory.
+pub const MAX_SIDE: u32 = 32;
+
+/// Straight (unpremultiplied) RGBA8 pixels, row-major.
+#[derive(Clone)]
+pub struct Icon {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+impl Icon {
+    /// The icon shrunk (box-averaged) so neither side exceeds `max`, keeping the
+    /// aspect ratio; unchanged when it already fits.
+    pub fn fit(self, max: u32) -> Self {
+        if self.width <= max && self.height <= max {
+            return self;
+        }
+        let scale = max as f32 / self.width.max(self.height) as f32;
+        let width = ((self.width as f32 * scale).round() as u32).max(1);
+        let height = ((self.height as f32 * scale).round() as u32).max(1);
+        let mut rgba = Vec::with_capacity((width * height * 4) as usize);
+        for y in 0..height {
+            let (y0, y1) = span(y, height, self.height);
+            for x in 0..width {
+                let (x0, x1) = span(x, width, self.width);
+                let mut sum = [0u32; 4];
+                for sy in y0..y1 {
+                    for sx in x0..x1 {
+                        let at = ((sy * self.width + sx) * 4) as usize;
+                        for (c, s) in sum.iter_mut().enumerate() {
+                            *s += self.rgba[at + c] as u32;
+                        }
+                    }
+                }
+                let n = (y1 - y0) * (x1 - x0);
+                rgba.extend(sum.iter().map(|s| (s / n) as u8));
+            }
+        }
+        Self {
+            width,
+            height,
+            rgba,
+        }
+    }
+}
+
+/// Source rows (or columns) `[start, end)` averaged into output pixel `i` of
+/// `out`, over a source `len` long. Never empty.
+fn span(i: u32, out: u32, len: u32) -> (u32, u32) {
+    let start = i * len / out;
+    let end = ((i + 1) * len / out).max(start + 1);
+    (start, end.min(len))
+}
+
+/// The cache file for `url`'s host, or `None` for a URL without one
+/// (`retsurf:` pages, `data:` URLs).
+fn path(url: &str) -> Option<PathBuf> {
+    let host = url::Url::parse(url).ok()?.host_str()?.to_ascii_lowercase();
+    // Hosts can't hold a path separator, but a hand-built URL shouldn't get to
+    // pick where the file goes either.
+    if host.contains(['/', '\\']) || host.starts_with('.') {
+        return None;
+    }
+    Some(PathBuf::from(format!(
+        "{}favicons/{host}.png",
+        config::cache_dir()
+    )))
+}
+
+/// Store `icon` as the favicon of `url`'s host, shrunk to [`MAX_SIDE`]. Returns
+/// whether a file was written; failures are logged, not fatal.
+pub fn save(url: &str, icon: Icon) -> bool {
+    let Some(path) = path(url) else {
+        return false;
+    };
+    let icon = icon.fit(MAX_SIDE);
+    let result = (|| -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = std::io::BufWriter::new(std::fs::File::create(&path)?);
+        let mut encoder = png::Encoder::new(file, icon.width, icon.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&icon.rgba)?;
+        writer.finish()?;
+        Ok(())
+    })();
+    match result {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!("could not write favicon `{}`: {e}", path.display());
+            false
+        }
+    }
+}
+
+/// Saves icons on a background thread, one at a time in arrival order. The URLs
+/// written are handed back through [`Self::take_saved`], with a
+/// [`UserEvent::FaviconSaved`] to wake the loop.
+pub struct Writer {
+    queue: Sender<(String, Icon)>,
+    saved: Arc<Mutex<Vec<String>>>,
+}
+
+impl Writer {
+    pub fn new(sender: UserEventSender) -> Self {
+        let (queue, jobs) = mpsc::channel::<(String, Icon)>();
+        let saved = Arc::new(Mutex::new(Vec::new()));
+        let done = saved.clone();
+        // Ends when the writer (the only sender) is dropped.
+        std::thread::spawn(move || {
+            for (url, icon) in jobs {
+                if save(&url, icon) {
+                    done.lock().unwrap().push(url);
+                    sender.send(UserEvent::FaviconSaved);
+                }
+            }
+        });
+        Self { queue, saved }
+    }
+
+    /// Queue `icon` to be stored for `url` (see [`save`]).
+    pub fn save(&self, url: String, icon: Icon) {
+        let _ = self.queue.send((url, icon));
+    }
+
+    /// The URLs whose icons were written since the last call.
+    pub fn take_saved(&self) -> Vec<String> {
+        std::mem::take(&mut *self.saved.lock().unwrap())
+    }
+}
+
+/// The cached favicon of `url`'s host, if one was saved and still decodes.
+pub fn load(url: &str) -> Option<Icon> {
+    let bytes = std::fs::read(path(url)?).ok()?;
+    let mut reader = png::Decoder::new(std::io::Cursor::new(bytes))
+        .read_info()
+        .ok()?;
+    let mut buf = vec![0u8; reader.output_buffer_size()?];
+    let info = reader.next_frame(&mut buf).ok()?;
+    // Only what `save` writes; anything else was put there by hand.
+    if info.color_type != png::ColorType::Rgba || info.bit_depth != png::BitDepth::Eight {
+        return None;
+    }
+    buf.truncate(info.line_size * info.height as usize);
+    Some(Icon {
+        width: info.width,
+        height: info.height,
+        rgba: buf,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A large icon shrinks to the cap with its aspect kept, averaging pixels.
+    #[test]
+    fn fit_shrinks_to_the_cap() {
+        let icon = Icon {
+            width: 64,
+            height: 32,
+            rgba: [10, 20, 30, 255].repeat(64 * 32),
+        };
+        let small = icon.fit(MAX_SIDE);
+        assert_eq!((small.width, small.height), (32, 16));
+        assert_eq!(small.rgba.len(), 32 * 16 * 4);
+        assert_eq!(&small.rgba[..4], &[10, 20, 30, 255]);
+    }
+}
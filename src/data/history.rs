@@ -4,17 +4,25 @@
 //! [`crate::config`]). The full-screen menu (see [`crate::overlay::menu`]) renders it; the
 //! central router drives selection / open / delete / clear.
 
+use super::clean_url;
 use crate::config::HistoryConfig;
 use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-/// A visited page and when it was last visited (unix seconds, `0` if the device
-/// clock was unavailable — common on handhelds without an RTC until NTP syncs).
+/// A visited page, its title (empty until the page reports one), when it was
+/// last visited (unix seconds, `0` if the device clock was unavailable — common
+/// on handhelds without an RTC until NTP syncs), and how often.
 #[derive(Clone, Serialize, Deserialize)]
 pub struct Entry {
     pub url: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub title: String,
     #[serde(default)]
     pub time: u64,
+    /// Visits counted so far (`0` in files from before they were counted); with
+    /// `time`, it ranks address-bar suggestions (see [`crate::overlay::suggest`]).
+    #[serde(default)]
+    pub visits: u32,
 }
 
 /// On-disk shape (a TOML table can't be a bare array, so wrap the list).
@@ -106,26 +114,37 @@ impl History {
     }
 
     /// Record a visit: most-recent-first, de-duplicated (a revisit moves to the
-    /// top and re-stamps its time), capped at `max_entries`. No-op when recording
-    /// is disabled or the URL is empty. Marks the store dirty (the disk write is
-    /// deferred — see [`Self::flush`]) rather than rewriting the file per visit.
+    /// top, re-stamps its time and counts another visit), capped at
+    /// `max_entries`, stored without its tracking parameters (see
+    /// [`clean_url`]). No-op when recording is disabled or the URL is empty.
+    /// Marks the store dirty (the disk write is deferred — see [`Self::flush`])
+    /// rather than rewriting the file per visit.
     pub fn record(&mut self, url: &str) {
         if !self.enabled || url.is_empty() {
             return;
         }
+        let url = &*clean_url::clean(url);
         // Already on top → just keep the existing entry (avoids reordering the
         // list for the page we're currently on).
         if self.entries.first().is_some_and(|e| e.url == url) {
             return;
         }
-        if let Some(i) = self.entries.iter().position(|e| e.url == url) {
-            self.entries.remove(i);
-        }
+        // A revisit keeps the title (the page re-reports it only on a change)
+        // and adds to the count.
+        let (title, visits) = match self.entries.iter().position(|e| e.url == url) {
+            Some(i) => {
+                let entry = self.entries.remove(i);
+                (entry.title, entry.visits.max(1))
+            }
+            None => (String::new(), 0),
+        };
         self.entries.insert(
             0,
             Entry {
                 url: url.to_string(),
+                title,
                 time: now_unix(),
+                visits: visits.saturating_add(1),
             },
         );
         self.entries.truncate(self.max_entries);
@@ -133,6 +152,25 @@ impl History {
         self.dirty = true;
     }
 
+    /// Adopt the title a visited page reported. Marks the store dirty like
+    /// [`Self::record`]; a URL not in the history is ignored.
+    pub fn set_title(&mut self, url: &str, title: &str) {
+        for entry in self.entries.iter_mut().filter(|e| e.url == url) {
+            if entry.title != title {
+                entry.title = title.to_string();
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// The last title recorded for `url`, if it has one.
+    pub fn title_of(&self, url: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.url == url && !e.title.is_empty())
+            .map(|e| e.title.as_str())
+    }
+
     /// Reset the highlight when the menu opens: land on the first entry (index
     /// 1), not the destructive "Clear all" row at index 0 — mirrors how the Tabs
     /// section starts on the first tab rather than "+ New tab".
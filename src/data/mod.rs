@@ -1,15 +1,69 @@
 //! User data stores, all shaped alike: an in-memory list with a highlighted row
 //! for the menu, persisted as TOML in the user data dir (see
 //! [`crate::config::data_dir`]). [`crate::overlay::menu`] owns one of each; [`crate::ui`]
-//! renders them.
+//! renders them. [`favicons`] is the odd one out: a PNG cache, not a list;
+//! [`site_settings`] is a host-keyed table the browser owns, not the menu; and
+//! [`clean_url`] is no store at all, but the tracking-parameter filter the
+//! stores and the browser's page loads share.
+//!
+//! Changes that can come in bursts (a visit, a page's title, a zoom step) don't
+//! write at once: the store marks itself dirty and writes on its `flush`, which
+//! [`crate::app`] calls every few seconds and at exit. A page animating its
+//! title would otherwise rewrite the file on every change.
 
 pub mod bookmarks;
+pub mod clean_url;
 pub mod dial;
 pub mod downloads;
+pub mod favicons;
 pub mod history;
+pub mod session;
+pub mod site_settings;
 
 use crate::config;
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// A speed-dial pin: its URL and the title the page last reported, empty until
+/// it has reported one. (Bookmarks carry tags too, see [`bookmarks::Bookmark`].)
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Page {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub title: String,
+}
+
+impl Page {
+    pub fn new(url: &str, title: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            title: title.to_string(),
+        }
+    }
+}
+
+/// Fold a store's legacy bare-URL list (the `urls` array files had before titles
+/// were kept) into its pages, untitled and in order, skipping ones already there.
+/// Stores read `urls` but never write it, so the next save completes the upgrade.
+fn upgrade_urls(mut pages: Vec<Page>, urls: Vec<String>) -> Vec<Page> {
+    for url in urls {
+        if !pages.iter().any(|p| p.url == url) {
+            pages.push(Page::new(&url, ""));
+        }
+    }
+    pages
+}
+
+/// Set the title of every page saved at `url`; returns whether any changed.
+fn retitle(pages: &mut [Page], url: &str, title: &str) -> bool {
+    let mut changed = false;
+    for page in pages.iter_mut().filter(|p| p.url == url) {
+        if page.title != title {
+            page.title = title.to_string();
+            changed = true;
+        }
+    }
+    changed
+}
 
 /// Full path of a data file (`<file>`) in the user data dir.
 fn data_path(file: &str) -> String {
@@ -92,6 +146,11 @@ impl ListCursor {
         self.selected = self.selected.min((len + self.reserved).saturating_sub(1));
     }
 
+    /// Highlight entry `index` (counted past the reserved rows).
+    fn select_entry(&mut self, index: usize) {
+        self.selected = index + self.reserved;
+    }
+
     /// Index into the entry list for the highlighted row, or `None` when a
     /// reserved row (e.g. "Clear all") is highlighted.
     fn entry_index(&self) -> Option<usize> {
@@ -0,0 +1,185 @@
+//! Session restore: the open tabs (URL, title, page zoom) and which one is shown,
+//! persisted to `session.toml` in the user data dir so a restart — or a battery
+//! dying mid-browse on a handheld — reopens them. Snapshots are taken from the
+//! browser on the same deferred cadence as history (see [`crate::app`]): an
+//! unchanged tab list never touches the disk.
+//!
+//! A panic leaves a `session.crashed` marker beside the file (written by the
+//! panic hook in `lib.rs`, consumed by the next launch). That launch then asks
+//! before reopening anything, so a page that takes the engine down can't crash
+//! every start in a row.
+
+use serde::{Deserialize, Serialize};
+
+/// Marker left by the panic hook; its presence means the last run didn't exit
+/// cleanly.
+const CRASH_MARKER: &str = "session.crashed";
+
+/// One saved tab.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionTab {
+    pub url: String,
+    /// Title shown for the tab until its page reports one again.
+    #[serde(default)]
+    pub title: String,
+    /// The tab's page zoom (1.0 = 100%).
+    #[serde(default = "default_zoom")]
+    pub zoom: f32,
+}
+
+fn default_zoom() -> f32 {
+    1.0
+}
+
+/// On-disk shape: the tab list plus the shown tab's index.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    #[serde(default)]
+    pub active: usize,
+    #[serde(default)]
+    pub tabs: Vec<SessionTab>,
+}
+
+impl Snapshot {
+    /// The tabs worth reopening: ones that never navigated have no URL to load.
+    /// `active` is re-pointed at the same tab within the filtered list.
+    pub fn restorable(&self) -> (Vec<SessionTab>, usize) {
+        let mut active = 0;
+        let mut tabs = Vec::with_capacity(self.tabs.len());
+        for (i, tab) in self.tabs.iter().enumerate() {
+            if tab.url.is_empty() {
+                continue;
+            }
+            if i == self.active {
+                active = tabs.len();
+            }
+            tabs.push(tab.clone());
+        }
+        (tabs, active)
+    }
+}
+
+pub struct Session {
+    /// The last snapshot handed to [`Self::update`] (or loaded from disk).
+    current: Snapshot,
+    /// The previous run's tabs while the crash prompt is unanswered. Snapshots
+    /// are held back meanwhile, so the file still has them if the app goes down
+    /// again before the user picks.
+    offer: Option<Snapshot>,
+    /// Set when `current` has unsaved changes; cleared by a successful write.
+    dirty: bool,
+}
+
+impl Session {
+    /// Load the saved session (missing/invalid file → no tabs).
+    pub fn load() -> Self {
+        Self {
+            current: super::load_toml::<Snapshot>("session.toml"),
+            offer: None,
+            dirty: false,
+        }
+    }
+
+    /// The previous run's tabs, as loaded.
+    pub fn saved(&self) -> &Snapshot {
+        &self.current
+    }
+
+    /// Hold the loaded tabs back for the crash prompt (see [`Self::take_offer`]).
+    pub fn hold_offer(&mut self) {
+        self.offer = Some(self.current.clone());
+    }
+
+    /// The held-back tabs, once the crash prompt is answered; snapshots resume.
+    pub fn take_offer(&mut self) -> Option<Snapshot> {
+        self.offer.take()
+    }
+
+    /// Adopt the browser's current tabs; only marks the session dirty when they
+    /// changed (the write is deferred — see [`Self::flush`]).
+    pub fn update(&mut self, snapshot: Snapshot) {
+        if self.offer.is_some() || snapshot == self.current {
+            return;
+        }
+        self.current = snapshot;
+        self.dirty = true;
+    }
+
+    /// Write pending changes to disk if any. Called on the main loop's history
+    /// throttle and at shutdown, like [`super::history::History::flush`].
+    pub fn flush(&mut self) {
+        if self.dirty && super::save_toml("session.toml", &self.current, "session") {
+            self.dirty = false;
+        }
+    }
+
+    /// Whether the last run ended in a panic.
+    pub fn crashed() -> bool {
+        std::path::Path::new(&super::data_path(CRASH_MARKER)).exists()
+    }
+
+    /// Leave the crash marker. Called from the panic hook, so it must not panic
+    /// itself; a failed write only costs the prompt.
+    pub fn mark_crashed() {
+        let _ = std::fs::write(super::data_path(CRASH_MARKER), "");
+    }
+
+    /// Remove the crash marker once startup has read it, and again on a clean
+    /// exit: a panic off the main thread (one the app survived) leaves one too.
+    pub fn clear_crashed() {
+        let _ = std::fs::remove_file(super::data_path(CRASH_MARKER));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tab(url: &str) -> SessionTab {
+        SessionTab {
+            url: url.to_string(),
+            title: String::new(),
+            zoom: 1.0,
+        }
+    }
+
+    /// Tabs that never navigated are dropped, and the shown tab stays shown.
+    #[test]
+    fn restorable_skips_blank_tabs_and_keeps_active() {
+        let snapshot = Snapshot {
+            active: 2,
+            tabs: vec![tab(""), tab("https://a.test/"), tab("https://b.test/")],
+        };
+        let (tabs, active) = snapshot.restorable();
+        assert_eq!(tabs.len(), 2);
+        assert_eq!(tabs[active].url, "https://b.test/");
+    }
+
+    /// An old file without titles or zoom still loads, at 100%.
+    #[test]
+    fn missing_fields_default() {
+        let snapshot: Snapshot = toml::from_str("[[tabs]]\nurl = \"https://a.test/\"\n").unwrap();
+        assert_eq!(snapshot.active, 0);
+        assert_eq!(snapshot.tabs[0].zoom, 1.0);
+        assert!(snapshot.tabs[0].title.is_empty());
+    }
+
+    /// While the crash prompt is pending, snapshots don't overwrite the offer.
+    #[test]
+    fn update_waits_for_the_offer() {
+        let mut session = Session {
+            current: Snapshot {
+                active: 0,
+                tabs: vec![tab("https://a.test/")],
+            },
+            offer: None,
+            dirty: false,
+        };
+        session.hold_offer();
+        session.update(Snapshot::default());
+        assert!(!session.dirty);
+        assert_eq!(session.take_offer().unwrap().tabs.len(), 1);
+        session.update(Snapshot::default());
+        assert!(session.dirty);
+    }
+}
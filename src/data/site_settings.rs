@@ -0,0 +1,182 @@
+//! Per-site overrides of the global browsing config, keyed by host and persisted
+//! to `site_settings.toml` in the user data dir: page zoom, forced dark and the
+//! data-saving content blocks, the settings that can be applied per tab, per
+//! document or per request. (The user agent and JavaScript are process-wide
+//! prefs in Servo, so they stay global. Turning the ad blocker off for a site is an allowlist rule in
+//! the user filters instead; see [`crate::browser::adblock`].) A host also
+//! covers its subdomains unless one has an entry of its own. The browser owns
+//! the store and applies it as each page loads (see
+//! [`crate::browser::AppBrowser::set_site_settings`]); the toolbar's site
+//! settings sheet ([`crate::overlay::site_edit`]) edits the active tab's entry.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const FILE: &str = "site_settings.toml";
+
+/// One site's overrides. `None` follows the global config, so an entry only
+/// holds what differs.
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Site {
+    /// Page zoom (1.0 = 100%), remembered from the zoom gestures too.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zoom: Option<f32>,
+    /// Invert the site (`true`) or never (`false`), whatever `page_theme` says.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub forced_dark: Option<bool>,
+    /// The `[data_saving]` blocks, per site.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_images: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_media: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_fonts: Option<bool>,
+}
+
+impl Site {
+    /// Whether the entry overrides nothing (and so needn't be stored).
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// On-disk shape: `[sites."example.com"]` tables.
+#[derive(Default, Serialize, Deserialize)]
+struct Store {
+    #[serde(default)]
+    sites: BTreeMap<String, Site>,
+}
+
+pub struct SiteSettings {
+    sites: BTreeMap<String, Site>,
+    /// Unsaved zoom changes, for [`Self::flush`].
+    dirty: bool,
+}
+
+impl SiteSettings {
+    /// Load the saved overrides; a missing or invalid file means none.
+    pub fn load() -> Self {
+        let mut sites = super::load_toml::<Store>(FILE).sites;
+        // Entries that only held a key since dropped (`user_agent`).
+        sites.retain(|_, site| !site.is_empty());
+        Self {
+            sites,
+            dirty: false,
+        }
+    }
+
+    /// Best-effort persist; failures are logged, not fatal.
+    fn save(&mut self) {
+        let store = Store {
+            sites: self.sites.clone(),
+        };
+        if super::save_toml(FILE, &store, "site settings") {
+            self.dirty = false;
+        }
+    }
+
+    /// Persist a deferred zoom change, if any.
+    pub fn flush(&mut self) {
+        if self.dirty {
+            self.save();
+        }
+    }
+
+    /// The key a page's own entry is stored under: its host, lowercased and
+    /// without a leading `www.`. `None` for URLs without a host (`about:`,
+    /// `file:`, the start page).
+    pub fn host_key(url: &str) -> Option<String> {
+        let url = url::Url::parse(url).ok()?;
+        let host = url.host_str()?.to_ascii_lowercase();
+        let host = host.strip_prefix("www.").unwrap_or(&host);
+        (!host.is_empty()).then(|| host.to_string())
+    }
+
+    /// The entry covering `url` and the key it is stored under: the host's own,
+    /// else the nearest parent domain's.
+    pub fn lookup(&self, url: &str) -> Option<(String, &Site)> {
+        let host = Self::host_key(url)?;
+        let mut rest = host.as_str();
+        loop {
+            if let Some(site) = self.sites.get(rest) {
+                return Some((rest.to_string(), site));
+            }
+            rest = rest.split_once('.')?.1;
+        }
+    }
+
+    /// The overrides for a page at `url`, if any cover it.
+    pub fn for_url(&self, url: &str) -> Option<&Site> {
+        self.lookup(url).map(|(_, site)| site)
+    }
+
+    /// The entry an edit for the page at `url` goes to, with its overrides: the
+    /// one covering it, else a blank one for its own host. `None` without a host.
+    pub fn entry_for(&self, url: &str) -> Option<(String, Site)> {
+        match self.lookup(url) {
+            Some((key, site)) => Some((key, site.clone())),
+            None => Self::host_key(url).map(|key| (key, Site::default())),
+        }
+    }
+
+    /// Store `site` under `key`, dropping the entry once it overrides nothing;
+    /// persists only on a change.
+    pub fn set(&mut self, key: &str, site: Site) {
+        if self.put(key, site) {
+            self.save();
+        }
+    }
+
+    /// Like [`Self::set`] for a zoom gesture: the write waits for [`Self::flush`].
+    pub fn set_zoom(&mut self, key: &str, site: Site) {
+        if self.put(key, site) {
+            self.dirty = true;
+        }
+    }
+
+    /// Store `site` under `key` (or drop the entry); whether anything changed.
+    fn put(&mut self, key: &str, site: Site) -> bool {
+        if site.is_empty() {
+            self.sites.remove(key).is_some()
+        } else if self.sites.get(key) != Some(&site) {
+            self.sites.insert(key.to_string(), site);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A subdomain falls back to its parent's entry; `www.` is the bare host.
+    #[test]
+    fn lookup_walks_up_to_the_parent_domain() {
+        let mut sites = BTreeMap::new();
+        let zoomed = Site {
+            zoom: Some(1.5),
+            ..Site::default()
+        };
+        sites.insert("example.com".to_string(), zoomed);
+        sites.insert("news.example.com".to_string(), Site::default());
+        let store = SiteSettings {
+            sites,
+            dirty: false,
+        };
+
+        let (key, _) = store.lookup("https://www.Example.com/a").unwrap();
+        assert_eq!(key, "example.com");
+        let (key, site) = store.lookup("https://m.shop.example.com/").unwrap();
+        assert_eq!(key, "example.com");
+        assert_eq!(site.zoom, Some(1.5));
+        assert_eq!(
+            store.lookup("https://news.example.com/").unwrap().0,
+            "news.example.com"
+        );
+        assert!(store.lookup("https://example.org/").is_none());
+        assert!(store.lookup("retsurf:home").is_none());
+    }
+}
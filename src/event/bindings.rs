@@ -16,7 +16,7 @@
 //! Plain key gestures (no Ctrl/Alt, Vimium-style) are muted while a text input
 //! holds focus; see [`crate::event::keyboard`].
 
-use crate::app::{AppCommand, InputCommand, MenuAction, SettingsAction};
+use crate::app::{AppCommand, BlockedAction, FindAction, InputCommand, MenuAction, SettingsAction};
 use crate::browser::BrowserCommand;
 use crate::config;
 use crate::overlay::osk::OskCommand;
@@ -63,6 +63,8 @@ pub enum Action {
     TabPrev,
     /// Open a new tab at the home page.
     NewTab,
+    /// Open a new no-history tab at the home page.
+    NewNoHistoryTab,
     /// Step the page zoom up / down the ladder, or back to the config default.
     ZoomIn,
     ZoomOut,
@@ -77,10 +79,25 @@ pub enum Action {
     /// Toggle the D-pad / left stick between cursor and page scroll, for devices
     /// with no right stick. Latched inside the gamepad, never a command.
     Scroll,
+    /// Toggle game mode on the current tab: the controller goes to the page's
+    /// Gamepad API instead of driving the browser.
+    GameMode,
+    /// Leave game mode: the one gamepad binding that fires while it's on (and
+    /// the only time it fires), so the pad must keep one (see [`REQUIRED`]).
+    GameExit,
+    /// Open the find-in-page bar and type a query (see [`crate::overlay::find`]).
+    Find,
+    /// Step to the next / previous match of the last find, reopening the bar.
+    /// While it's open the shoulders step too.
+    FindNext,
+    FindPrev,
+    /// Open the blocked-requests panel on the tab's request log (see
+    /// [`crate::overlay::blocked`]).
+    Requests,
 }
 
 /// Every action. [`GROUPS`] decides display order, so this only has to be complete.
-const ALL: [Action; 24] = [
+const ALL: [Action; 31] = [
     Action::Confirm,
     Action::Cancel,
     Action::Osk,
@@ -97,6 +114,7 @@ const ALL: [Action; 24] = [
     Action::TabNext,
     Action::TabPrev,
     Action::NewTab,
+    Action::NewNoHistoryTab,
     Action::ZoomIn,
     Action::ZoomOut,
     Action::ZoomReset,
@@ -105,6 +123,12 @@ const ALL: [Action; 24] = [
     Action::NavLeft,
     Action::NavRight,
     Action::Scroll,
+    Action::GameMode,
+    Action::GameExit,
+    Action::Find,
+    Action::FindNext,
+    Action::FindPrev,
+    Action::Requests,
 ];
 
 impl Bindable for Action {
@@ -126,6 +150,7 @@ impl Bindable for Action {
             Action::TabNext => "tab_next",
             Action::TabPrev => "tab_prev",
             Action::NewTab => "new_tab",
+            Action::NewNoHistoryTab => "new_no_history_tab",
             Action::ZoomIn => "zoom_in",
             Action::ZoomOut => "zoom_out",
             Action::ZoomReset => "zoom_reset",
@@ -134,6 +159,12 @@ impl Bindable for Action {
             Action::NavLeft => "nav_left",
             Action::NavRight => "nav_right",
             Action::Scroll => "scroll",
+            Action::GameMode => "game_mode",
+            Action::GameExit => "game_exit",
+            Action::Find => "find",
+            Action::FindNext => "find_next",
+            Action::FindPrev => "find_prev",
+            Action::Requests => "requests",
         }
     }
 
@@ -164,6 +195,7 @@ impl Bindable for Action {
             Action::TabNext => "Next tab",
             Action::TabPrev => "Previous tab",
             Action::NewTab => "New tab",
+            Action::NewNoHistoryTab => "New no-history tab",
             Action::ZoomIn => "Zoom in",
             Action::ZoomOut => "Zoom out",
             Action::ZoomReset => "Zoom reset",
@@ -172,6 +204,12 @@ impl Bindable for Action {
             Action::NavLeft => "Nav left",
             Action::NavRight => "Nav right",
             Action::Scroll => "Scroll toggle",
+            Action::GameMode => "Game mode",
+            Action::GameExit => "Leave game mode",
+            Action::Find => "Find in page",
+            Action::FindNext => "Find next",
+            Action::FindPrev => "Find previous",
+            Action::Requests => "Network log",
         }
     }
 
@@ -231,6 +269,7 @@ impl Action {
             Action::TabNext => AppCommand::Input(InputCommand::CycleTab(1)),
             Action::TabPrev => AppCommand::Input(InputCommand::CycleTab(-1)),
             Action::NewTab => AppCommand::Menu(MenuAction::NewTab),
+            Action::NewNoHistoryTab => AppCommand::Menu(MenuAction::NewNoHistoryTab),
             Action::ZoomIn => AppCommand::Browser(BrowserCommand::Zoom(1)),
             Action::ZoomOut => AppCommand::Browser(BrowserCommand::Zoom(-1)),
             Action::ZoomReset => AppCommand::Browser(BrowserCommand::Zoom(0)),
@@ -238,6 +277,12 @@ impl Action {
             Action::NavDown => AppCommand::Input(InputCommand::Nav(0, 1)),
             Action::NavLeft => AppCommand::Input(InputCommand::Nav(-1, 0)),
             Action::NavRight => AppCommand::Input(InputCommand::Nav(1, 0)),
+            // The gamepad only lets it out in game mode, so the toggle leaves.
+            Action::GameMode | Action::GameExit => AppCommand::Browser(BrowserCommand::GameMode),
+            Action::Find => AppCommand::Find(FindAction::Open),
+            Action::FindNext => AppCommand::Find(FindAction::Step(1)),
+            Action::FindPrev => AppCommand::Find(FindAction::Step(-1)),
+            Action::Requests => AppCommand::Blocked(BlockedAction::OpenRequests),
             // Scroll is resolved inside the gamepad, not routed.
             Action::Scroll => return None,
         })
@@ -281,9 +326,23 @@ pub const GROUPS: Groups<Action> = &[
             Action::ZoomIn,
             Action::ZoomOut,
             Action::ZoomReset,
+            Action::GameMode,
+            Action::GameExit,
+            Action::Find,
+            Action::FindNext,
+            Action::FindPrev,
+            Action::Requests,
+        ],
+    ),
+    (
+        "Tabs",
+        &[
+            Action::TabNext,
+            Action::TabPrev,
+            Action::NewTab,
+            Action::NewNoHistoryTab,
         ],
     ),
-    ("Tabs", &[Action::TabNext, Action::TabPrev, Action::NewTab]),
 ];
 
 /// What the pad must keep, whatever else is rebound: a handheld has no keyboard
@@ -292,6 +351,7 @@ pub const REQUIRED: &[Requirement<Action>] = &[
     ("Confirm", &[Action::Confirm]),
     ("Cancel", &[Action::Cancel]),
     ("Opening settings", &[Action::Settings]),
+    ("Leaving game mode", &[Action::GameExit]),
 ];
 
 /// Per-surface override tables (`[surface.<name>]`). None: the router is what
@@ -329,7 +389,14 @@ fn default_gamepad_bindings() -> inputbind::Table {
         // Pressed again while settings is open this quits — the only gamepad
         // exit on a handheld. Bind `quit` directly for a one-press exit.
         ("select+start", Action::Settings),
-        ("start+select", Action::Settings),
+        // The reverse order leaves game mode, and does nothing outside it.
+        // Emulators' Select+Start hotkeys press Select first, so they reach the
+        // page instead.
+        ("start+select", Action::GameExit),
+        // Select is already deferred by the chord above, so this costs no tap.
+        ("select+r1", Action::GameMode),
+        ("select+l1", Action::Find),
+        ("select+y", Action::Requests),
     ]
     .into_iter()
     .map(|(gesture, action)| (gesture.to_string(), action.name().to_string()))
@@ -351,9 +418,15 @@ fn default_keyboard_bindings() -> inputbind::Table {
         ("ctrl+t", Action::TabNext),
         ("ctrl+shift+t", Action::TabPrev),
         ("t", Action::NewTab),
+        ("ctrl+shift+n", Action::NewNoHistoryTab),
         ("ctrl+=", Action::ZoomIn),
         ("ctrl+-", Action::ZoomOut),
         ("ctrl+0", Action::ZoomReset),
+        ("ctrl+g", Action::GameMode),
+        ("ctrl+f", Action::Find),
+        ("f3", Action::FindNext),
+        ("shift+f3", Action::FindPrev),
+        ("f12", Action::Requests),
         // Vimium-style plain keys (muted while typing).
         ("f", Action::Hints),
         ("enter", Action::Confirm),
@@ -387,7 +460,26 @@ fn bindings_path() -> String {
 
 /// Load `bindings.toml`, writing the defaults as a template on first run.
 pub fn load_store() -> Store {
-    Store::load(bindings_path(), default_store)
+    let mut store = Store::load(bindings_path(), default_store);
+    // A file from before `game_exit` had no way out of game mode: give it the
+    // stock gesture, unless the user put something else there (then the
+    // required-binding check in Settings asks for one).
+    let exit = Action::GameExit.name();
+    if !store.gamepad.values().any(|action| action == exit) {
+        match store.gamepad.get("start+select") {
+            None => {
+                log::info!("bindings: `start+select` now leaves game mode");
+                store
+                    .gamepad
+                    .insert("start+select".to_string(), exit.to_string());
+            }
+            Some(taken) => log::warn!(
+                "bindings: nothing leaves game mode and `start+select` is bound to \
+                 `{taken}`; bind `{exit}` in Settings"
+            ),
+        }
+    }
+    store
 }
 
 /// Write an edited store back (the settings overlay saving on close).
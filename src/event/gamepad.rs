@@ -8,6 +8,12 @@
 //!
 //! [`Action::Scroll`] is handled here rather than routed: it latches
 //! `scroll_mode`, which turns the aim vector into page scrolling.
+//!
+//! In game mode the controller belongs to the page (the handler forwards it,
+//! see [`crate::event::handler`]) and there's no analog state; the pads still
+//! go through the gesture machine, but only [`Action::GameExit`] is let out —
+//! a required binding (see [`crate::event::bindings::REQUIRED`]), so a
+//! rebinding can't lock the user in.
 
 use crate::app::{AppCommand, InputCommand};
 use crate::config::InputConfig;
@@ -34,6 +40,8 @@ pub struct Gamepad {
     scroll_mode: bool,
     /// Scratch for resolved actions, reused so the input path never allocates.
     actions: Vec<(Action, Edge)>,
+    /// Whether game mode has the controller (see [`Self::set_game_mode`]).
+    game_mode: bool,
 }
 
 impl Gamepad {
@@ -47,10 +55,60 @@ impl Gamepad {
             right_trigger: Trigger::new(Pad::R2, cfg.trigger_threshold),
             scroll_mode: cfg.starts_in_scroll_mode(),
             actions: Vec::with_capacity(Pad::COUNT),
+            game_mode: false,
             cfg,
         }
     }
 
+    /// Hand the controller to the page (`true`) or take it back. Both ways the
+    /// gesture machine and the analog state are cleared: what was held belongs
+    /// to the side that saw the press, and the sticks only report on change.
+    pub fn set_game_mode(&mut self, on: bool, commands: &mut Vec<AppCommand>) {
+        if on == self.game_mode {
+            return;
+        }
+        self.reset(commands);
+        for horizontal in [true, false] {
+            _ = self.left.axis(horizontal, 0.0);
+            _ = self.right.axis(horizontal, 0.0);
+        }
+        self.game_mode = on;
+    }
+
+    #[inline]
+    pub fn in_game_mode(&self) -> bool {
+        self.game_mode
+    }
+
+    /// A button edge in game mode: the page gets it (the caller forwards it);
+    /// here it only feeds the gesture machine, for the `game_exit` binding.
+    pub fn on_game_button(
+        &mut self,
+        button: Button,
+        pressed: bool,
+        bindings: &Bindings<Action>,
+        commands: &mut Vec<AppCommand>,
+    ) {
+        if let Some(pad) = pad_of(button) {
+            self.press(pad, pressed, bindings, commands);
+        }
+    }
+
+    /// An axis in game mode: the triggers feed the gesture machine like
+    /// [`Self::on_game_button`]; the sticks are the page's alone.
+    pub fn on_game_axis(
+        &mut self,
+        axis: Axis,
+        value: i16,
+        bindings: &Bindings<Action>,
+        commands: &mut Vec<AppCommand>,
+    ) {
+        if trigger_of(axis).is_some() {
+            let edges = self.trigger_edges(axis, value);
+            self.feed_edges(edges, bindings, commands);
+        }
+    }
+
     /// Retuned in place, so a live edit cannot drop a gesture in flight.
     pub fn set_config(&mut self, cfg: InputConfig) {
         self.pads.set_timing(hold_of(&cfg), cadence_of(&cfg));
@@ -97,15 +155,22 @@ impl Gamepad {
     /// Whether the loop should keep ticking at ~60fps: to animate cursor/scroll,
     /// and to time pending holds and repeats (no SDL event marks either).
     pub fn is_active(&self) -> bool {
+        if self.game_mode {
+            return self.pads.next_deadline(Instant::now()).is_some();
+        }
         self.aim() != (0.0, 0.0)
             || self.right.vector().1 != 0.0
             || self.pads.next_deadline(Instant::now()).is_some()
     }
 
     /// The gesture machine pairs a held action's edges; this carries them through.
+    /// In game mode only [`Action::GameExit`] gets out, and nowhere else does it.
     fn dispatch(&mut self, commands: &mut Vec<AppCommand>) {
         let mut actions = std::mem::take(&mut self.actions);
         for &(action, edge) in &actions {
+            if (action == Action::GameExit) != self.game_mode {
+                continue;
+            }
             let pressed = edge == Edge::Press;
             if action == Action::Scroll {
                 // Not a held action, so only its press ever arrives.
@@ -220,6 +285,10 @@ impl Gamepad {
     pub fn tick(&mut self, commands: &mut Vec<AppCommand>) {
         self.pads.tick(Instant::now(), &mut self.actions);
         self.dispatch(commands);
+        // Game mode emits no analog state: the page has the sticks.
+        if self.game_mode {
+            return;
+        }
 
         commands.push(AppCommand::Input(InputCommand::Analog {
             aim: self.aim(),
@@ -1,5 +1,6 @@
 use super::gamepad::Gamepad;
 use super::keyboard::KeyEvent;
+use super::sdl2_servo;
 use crate::event::bindings::{self, Action};
 use crate::{
     app::{AppCommand, SettingsAction},
@@ -91,6 +92,13 @@ impl AppEventHandler {
             self.gamepad.reset(commands);
         }
 
+        // Game mode follows the active tab: toggled, started by an allowlisted
+        // site, or switched to / away from.
+        let game_mode = browser.game_mode();
+        if game_mode != self.gamepad.in_game_mode() {
+            self.set_game_mode(game_mode, browser, commands);
+        }
+
         // Block for the next event only when idle. When the gamepad is active or
         // the page is animating, return promptly so the main loop keeps ticking
         // (vsync caps the rate); blocking here would stall cursor/scroll motion.
@@ -130,6 +138,22 @@ impl AppEventHandler {
         self.gamepad.tick(commands);
     }
 
+    /// Hand the controllers to the active page or take them back. The page sees
+    /// them connect on entry and disconnect on exit, so a game pauses rather than
+    /// reading a stale last state.
+    fn set_game_mode(&mut self, on: bool, browser: &AppBrowser, commands: &mut Vec<AppCommand>) {
+        log::info!("game mode {}", if on { "on" } else { "off" });
+        self.gamepad.set_game_mode(on, commands);
+        for controller in &self.game_controllers {
+            let which = controller.instance_id();
+            browser.handle_input(if on {
+                sdl2_servo::gamepad_connected(which, controller.name())
+            } else {
+                sdl2_servo::gamepad_disconnected(which)
+            });
+        }
+    }
+
     /// A raw event taken before egui sees it, which would eat Tab/arrows/Enter/Esc
     /// and leave them unbindable. Returns whether capture consumed it.
     fn on_capture_event(&mut self, event: &Event, commands: &mut Vec<AppCommand>) -> bool {
@@ -204,14 +228,43 @@ impl AppEventHandler {
         match event {
             Event::ControllerDeviceAdded { which, .. } => {
                 if let Ok(controller) = self.game_controller_subsystem.open(which) {
+                    if self.gamepad.in_game_mode() {
+                        browser.handle_input(sdl2_servo::gamepad_connected(
+                            controller.instance_id(),
+                            controller.name(),
+                        ));
+                    }
                     self.game_controllers.push(controller);
                     log::info!("Controller {which} connected");
                 }
             }
             Event::ControllerDeviceRemoved { which, .. } => {
                 self.game_controllers.retain(|c| c.instance_id() != which);
+                if self.gamepad.in_game_mode() {
+                    browser.handle_input(sdl2_servo::gamepad_disconnected(which));
+                }
                 log::info!("Controller {which} disconnected");
             }
+            // Game mode: the page gets the controller verbatim; the gamepad
+            // only resolves the `game_exit` gesture.
+            Event::ControllerAxisMotion {
+                which, axis, value, ..
+            } if self.gamepad.in_game_mode() => {
+                self.gamepad
+                    .on_game_axis(axis, value, &self.bindings, commands);
+                browser.handle_input(sdl2_servo::gamepad_axis(which, axis, value));
+            }
+            Event::ControllerButtonDown { which, button, .. }
+            | Event::ControllerButtonUp { which, button, .. }
+                if self.gamepad.in_game_mode() =>
+            {
+                let pressed = matches!(event, Event::ControllerButtonDown { .. });
+                self.gamepad
+                    .on_game_button(button, pressed, &self.bindings, commands);
+                if let Some(event) = sdl2_servo::gamepad_button(which, button, pressed) {
+                    browser.handle_input(event);
+                }
+            }
             Event::MouseButtonUp {
                 mouse_btn, x, y, ..
             } => {
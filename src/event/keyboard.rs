@@ -5,7 +5,7 @@
 //! has focus, and the menu / hint overlays get their fixed keys first. Whatever
 //! isn't consumed is forwarded to the page as a Servo keyboard event.
 
-use crate::app::{AppCommand, InputCommand, MenuAction};
+use crate::app::{AppCommand, FindAction, InputCommand, MenuAction};
 use crate::browser::AppBrowser;
 use crate::event::bindings::Action;
 use crate::ui::{AppUi, Focus};
@@ -106,16 +106,25 @@ fn on_menu_key(key: &KeyEvent, bindings: &Bindings<Action>, commands: &mut Vec<A
                 1
             })));
         }
-        Keycode::Escape => commands.push(AppCommand::Menu(MenuAction::Close)),
+        // Esc is B: it closes the bookmark edit sheet or leaves a bookmark
+        // folder before it closes the menu.
+        Keycode::Escape => commands.push(AppCommand::Input(InputCommand::Cancel)),
         Keycode::Return | Keycode::KpEnter => {
             commands.push(AppCommand::Menu(MenuAction::OpenSelected))
         }
         Keycode::Delete | Keycode::Backspace => {
             commands.push(AppCommand::Menu(MenuAction::RemoveSelected))
         }
-        // P pins / unpins the selected Bookmarks or History entry to the speed
-        // dial (Y's role) — a no-op in the other sections (handled in the router).
+        // P is Y: edits the selected bookmark, bookmarks the selected History
+        // entry / tab, pauses / resumes a download or shows a finished one's
+        // details (handled in the router).
         Keycode::P => commands.push(AppCommand::Input(InputCommand::Hints)),
+        // Ctrl+Up / Ctrl+Down move the highlighted bookmark or download (L2 /
+        // R2's role).
+        Keycode::Up | Keycode::Down if key.keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD) => {
+            let delta = if key.kc == Keycode::Up { -1 } else { 1 };
+            commands.push(AppCommand::Menu(MenuAction::MoveEntry(delta)));
+        }
         _ => {}
     }
 }
@@ -158,6 +167,25 @@ fn on_key_down(
         }
     }
 
+    // The find bar: Enter / Shift+Enter step to the next / previous match, Esc
+    // closes it. Its query is typed on the on-screen keyboard, so nothing else
+    // needs capturing; arrows step through the `nav_*` bindings below.
+    if ui.focus() == Focus::Find {
+        match key.kc {
+            Keycode::Return | Keycode::KpEnter => {
+                let shift = key.keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD);
+                let delta = if shift { -1 } else { 1 };
+                commands.push(AppCommand::Find(FindAction::Step(delta)));
+                return;
+            }
+            Keycode::Escape => {
+                commands.push(AppCommand::Input(InputCommand::Cancel));
+                return;
+            }
+            _ => {}
+        }
+    }
+
     // The start page: arrows move the selection and Enter activates — the same
     // intents the gamepad routes. While its search field holds keyboard focus,
     // typing/caret/Enter belong to the text editor; only Down leaves the field.
@@ -251,7 +279,12 @@ fn on_key_down(
     // the focused value (Left/Right); Tab / Shift+Tab and Ctrl+Left/Right switch
     // section; Enter activates; Esc saves and closes. No text field can hold egui
     // focus here (typing goes through the OSK), so arrows are never caret moves.
-    if ui.focus() == Focus::Settings {
+    // The site settings sheet and the blocked-requests panel take the same keys
+    // (Tab is a no-op there).
+    if matches!(
+        ui.focus(),
+        Focus::Settings | Focus::SiteEdit | Focus::Blocked
+    ) {
         if matches!(key.kc, Keycode::Tab) {
             if !key.repeat {
                 let shift = key.keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD);
@@ -278,7 +311,15 @@ fn on_key_down(
 
     // Overlays whose navigation comes from the `nav_*` bindings, so vim hjkl
     // works there and not just the arrows the fixed handlers above catch.
-    let overlay = matches!(ui.focus(), Focus::Osk | Focus::Hints | Focus::Settings);
+    let overlay = matches!(
+        ui.focus(),
+        Focus::Osk
+            | Focus::Hints
+            | Focus::Settings
+            | Focus::SiteEdit
+            | Focus::Blocked
+            | Focus::Find
+    );
     let typing = browser.text_input_focused()
         || ui.address_bar_focused()
         || ui.home_field_editing()
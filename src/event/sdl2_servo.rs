@@ -113,3 +113,88 @@ pub fn named_keyboard_event(
     };
     servo::KeyboardEvent::new(event)
 }
+
+/// Gamepad API "connected" for a controller in game mode. The index is SDL's
+/// instance id, stable for as long as the controller stays plugged in.
+pub fn gamepad_connected(which: u32, name: String) -> servo::InputEvent {
+    let bounds = servo::GamepadInputBounds {
+        axis_bounds: (-1.0, 1.0),
+        button_bounds: (0.0, 1.0),
+    };
+    // Rumble isn't wired up, so none is advertised.
+    let haptics = servo::GamepadSupportedHapticEffects {
+        supports_dual_rumble: false,
+        supports_trigger_rumble: false,
+    };
+    servo::InputEvent::Gamepad(servo::GamepadEvent::Connected(
+        servo::GamepadIndex(which as usize),
+        name,
+        bounds,
+        haptics,
+    ))
+}
+
+pub fn gamepad_disconnected(which: u32) -> servo::InputEvent {
+    servo::InputEvent::Gamepad(servo::GamepadEvent::Disconnected(servo::GamepadIndex(
+        which as usize,
+    )))
+}
+
+/// A button edge as a Gamepad API update, in the W3C "standard" layout SDL's
+/// controller mapping already follows (A/B/X/Y by position). `None` for buttons
+/// the layout lacks (paddles, touchpad, misc).
+pub fn gamepad_button(
+    which: u32,
+    button: sdl2::controller::Button,
+    pressed: bool,
+) -> Option<servo::InputEvent> {
+    use sdl2::controller::Button;
+    let index = match button {
+        Button::A => 0,
+        Button::B => 1,
+        Button::X => 2,
+        Button::Y => 3,
+        Button::LeftShoulder => 4,
+        Button::RightShoulder => 5,
+        // 6 / 7 are the triggers, which SDL reports as axes.
+        Button::Back => 8,
+        Button::Start => 9,
+        Button::LeftStick => 10,
+        Button::RightStick => 11,
+        Button::DPadUp => 12,
+        Button::DPadDown => 13,
+        Button::DPadLeft => 14,
+        Button::DPadRight => 15,
+        Button::Guide => 16,
+        _ => return None,
+    };
+    let value = if pressed { 1.0 } else { 0.0 };
+    Some(gamepad_update(
+        which,
+        servo::GamepadUpdateType::Button(index, value),
+    ))
+}
+
+/// An axis move as a Gamepad API update: the sticks are standard axes 0–3, the
+/// triggers analog buttons 6 / 7.
+pub fn gamepad_axis(which: u32, axis: sdl2::controller::Axis, value: i16) -> servo::InputEvent {
+    use sdl2::controller::Axis;
+    let value = f64::from(inputbind::sdl::axis_value(value));
+    let update = match axis {
+        Axis::LeftX => servo::GamepadUpdateType::Axis(0, value),
+        Axis::LeftY => servo::GamepadUpdateType::Axis(1, value),
+        Axis::RightX => servo::GamepadUpdateType::Axis(2, value),
+        Axis::RightY => servo::GamepadUpdateType::Axis(3, value),
+        Axis::TriggerLeft => servo::GamepadUpdateType::Button(6, value),
+        Axis::TriggerRight => servo::GamepadUpdateType::Button(7, value),
+    };
+    gamepad_update(which, update)
+}
+
+#[inline]
+fn gamepad_update(which: u32, update: servo::GamepadUpdateType) -> servo::InputEvent {
+    servo::InputEvent::Gamepad(servo::GamepadEvent::Updated(
+        servo::GamepadIndex(which as usize),
+        update,
+    ))
+}
@@ -13,12 +13,22 @@ pub fn handle_user(code: i32) -> Option<AppCommand> {
         // Sent by the hint-collection JS callback purely to wake the loop; the
         // main loop drains the collected rects.
         UserEvent::HintsReady => None,
-        // Sent by the embedder-control delegate purely to wake the loop; the
-        // main loop drains the pending/dismissed controls.
+        // Sent by the embedder-control delegate (and the certificate error
+        // check) purely to wake the loop; the main loop drains the
+        // pending/dismissed controls and the errors.
         UserEvent::ControlPending => None,
         // Sent by the self-update worker purely to wake the loop; the About tab
         // re-reads the updater snapshot each frame, so the wake just repaints.
         UserEvent::UpdateProgress => None,
+        // Sent by the find-in-page JS callback purely to wake the loop; the main
+        // loop drains the page's answer into the find bar.
+        UserEvent::FindReady => None,
+        // Sent by the search-suggestions worker purely to wake the loop; the UI
+        // polls the answer each frame.
+        UserEvent::SuggestReady => None,
+        // Sent by the favicon writer purely to wake the loop; the main loop
+        // drains the saved URLs so the menu reloads their icons.
+        UserEvent::FaviconSaved => None,
     }
 }
 
@@ -31,16 +41,22 @@ pub enum UserEvent {
     HintsReady = 3,
     ControlPending = 4,
     UpdateProgress = 5,
+    FindReady = 6,
+    SuggestReady = 7,
+    FaviconSaved = 8,
 }
 
 impl UserEvent {
-    pub const ALL: [UserEvent; 6] = [
+    pub const ALL: [UserEvent; 9] = [
         UserEvent::BrowserWakeup,
         UserEvent::BrowserFrameReady,
         UserEvent::DownloadUpdate,
         UserEvent::HintsReady,
         UserEvent::ControlPending,
         UserEvent::UpdateProgress,
+        UserEvent::FindReady,
+        UserEvent::SuggestReady,
+        UserEvent::FaviconSaved,
     ];
 
     pub fn from_code(code: i32) -> UserEvent {
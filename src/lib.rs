@@ -40,6 +40,12 @@ pub fn run_app() {
         .install_default()
         .expect("Error initializing crypto provider");
     let mut app_config = config::AppConfig::load();
+    // Before anything connects: every network path reads the CAs, proxy and
+    // DoH settings from here (the CAs first: the DoH client trusts them too).
+    net::tls::init(&app_config.network);
+    net::proxy::init(&app_config.network);
+    data::clean_url::configure(&app_config.url_cleaning);
+    import_bookmarks_arg();
     if let Ok(v) = std::env::var("RETSURF_GLES") {
         app_config.display.use_gles = v != "0";
     }
@@ -88,6 +94,27 @@ pub fn run_app() {
     app.run();
 }
 
+/// `--import-bookmarks <file>`: merge another browser's `bookmarks.html` into
+/// the saved bookmarks before the menu loads them, then launch as usual. For
+/// launcher scripts and handheld users copying an export over on the SD card; a
+/// bad file is logged, never fatal.
+fn import_bookmarks_arg() {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg != "--import-bookmarks" {
+            continue;
+        }
+        let Some(file) = args.next() else {
+            log::warn!("--import-bookmarks needs a file");
+            return;
+        };
+        match data::bookmarks::Bookmarks::load().import_html(&file) {
+            Ok(added) => log::info!("imported {added} new bookmarks from {file}"),
+            Err(e) => log::warn!("bookmark import failed: {e}"),
+        }
+    }
+}
+
 /// SDL's Android shell (`SDLActivity`) `dlopen`s our cdylib and calls this C
 /// entry point on SDL's dedicated thread. We just hand off to `run_app`.
 #[cfg(target_os = "android")]
@@ -129,8 +156,10 @@ fn init_logging() {
 }
 
 /// Mirror panics to a file in addition to stderr. The path is `RETSURF_PANIC_FILE`
-/// if set, else `retsurf-panic.log` in the working directory. The default backtrace
-/// hook still runs after us, so desktop behavior is unchanged.
+/// if set, else `retsurf-panic.log` in the working directory. It also leaves the
+/// session crash marker so the next launch offers to reopen the tabs (see
+/// [`data::session`]). The default backtrace hook still runs after us, so desktop
+/// behavior is unchanged.
 fn install_panic_hook() {
     let default = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
@@ -142,6 +171,7 @@ fn install_panic_hook() {
             &path,
             format!("retsurf {BUILD_ID}\n\n{info}\n\nbacktrace:\n{backtrace}\n"),
         );
+        data::session::Session::mark_crashed();
         default(info);
     }));
 }
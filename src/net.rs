@@ -2,9 +2,20 @@
 //! ([`crate::data::downloads`]) and the self-updater ([`crate::update`]):
 //! chunked writes with a throttled progress callback. Per-chunk work (hashing,
 //! cancellation, notifying the main loop) stays with the caller.
+//!
+//! Also where the app's own ureq agents come from ([`agent`]), so each one
+//! follows the `[network]` settings ([`proxy`], [`tls`]).
+
+mod bridge;
+mod doh;
+pub mod proxy;
+mod socks;
+pub mod tls;
 
 use std::io::{Read, Write};
 use std::time::{Duration, Instant};
+use ureq::config::ConfigBuilder;
+use ureq::typestate::AgentScope;
 
 /// Throttle for [`stream`]'s progress notifications.
 const NOTIFY_EVERY: Duration = Duration::from_millis(250);
@@ -12,6 +23,46 @@ const NOTIFY_EVERY: Duration = Duration::from_millis(250);
 /// Read buffer size for response streaming.
 const CHUNK: usize = 64 * 1024;
 
+/// A ureq agent for requests to `url`, through the `[network]` proxy unless
+/// the host bypasses it, trusting its extra CAs ([`tls`]).
+pub fn agent(url: &str) -> ureq::Agent {
+    agent_with(url, |config| config)
+}
+
+/// [`agent`] with the caller's own settings (timeouts) on top.
+pub fn agent_with(
+    url: &str,
+    tune: impl FnOnce(ConfigBuilder<AgentScope>) -> ConfigBuilder<AgentScope>,
+) -> ureq::Agent {
+    let proxy = proxy::uri_for(url).and_then(|uri| match ureq::Proxy::new(uri) {
+        Ok(proxy) => Some(proxy),
+        Err(e) => {
+            log::warn!("proxy: unusable for ureq: {e}");
+            None
+        }
+    });
+    let mut config = ureq::Agent::config_builder().proxy(proxy);
+    if let Some(tls) = tls::config() {
+        config = config.tls_config(tls);
+    }
+    tune(config).build().new_agent()
+}
+
+/// 128 unpredictable bits, hex: the local proxy bridge's secret, and the
+/// token marking Servo's own error pages (`browser::error_page`). std's
+/// `RandomState` is keyed from the OS's random source, so a fresh one's hash of
+/// anything will do.
+pub fn secret() -> String {
+    use std::hash::{BuildHasher, Hasher};
+    (0..2u8)
+        .map(|i| {
+            let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+            hasher.write_u8(i);
+            format!("{:016x}", hasher.finish())
+        })
+        .collect()
+}
+
 /// A response's `Content-Length`, or `None` when absent/unparsable.
 pub fn content_length(headers: &ureq::http::HeaderMap) -> Option<u64> {
     headers
@@ -0,0 +1,324 @@
+//! A local HTTP proxy that opens each requested connection its own way, for
+//! what Servo and ureq — which only speak to HTTP proxies — can't do alone:
+//! going through a SOCKS5 server ([`super::socks`]), or resolving names over
+//! DNS-over-HTTPS ([`super::doh`]). See [`super::proxy`].
+//!
+//! `CONNECT` tunnels (HTTPS) are spliced through as they are; a plain-HTTP
+//! request is sent on in origin form, one per connection. One thread per
+//! connection, up to [`MAX_CLIENTS`]: the clients are the browser's own, a
+//! handful at a time.
+//!
+//! Any local process can reach a loopback port, and a bridge holding SOCKS
+//! credentials would relay for it. So each bridge makes up a secret at start,
+//! handed to Servo and ureq as the proxy URL's credentials, and refuses
+//! requests without it in their `Proxy-Authorization`.
+
+use super::doh::Resolver;
+use super::socks;
+use base64::Engine;
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Cap on a request head; longer ones are refused.
+const MAX_HEAD: usize = 64 * 1024;
+
+/// How long a client has to send its request head.
+const HEAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long opening the upstream connection may take: the TCP connect, and
+/// each read of the SOCKS handshake.
+pub(super) const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Connections served at once; more are answered 503 until some finish.
+const MAX_CLIENTS: usize = 64;
+
+/// The user name in the bridge's proxy URL; only the secret is checked.
+const USER: &str = "retsurf";
+
+/// How the bridge reaches a target.
+#[derive(Clone)]
+pub(super) enum Upstream {
+    /// Through a SOCKS5 server, which resolves the name.
+    Socks(socks::Server),
+    /// Directly, with the name resolved over DoH.
+    Doh(&'static Resolver),
+}
+
+impl Upstream {
+    fn connect(&self, host: &str, port: u16) -> io::Result<TcpStream> {
+        match self {
+            Upstream::Socks(server) => socks::connect(server, host, port),
+            Upstream::Doh(resolver) => dial(&resolver.resolve(host, port)?),
+        }
+    }
+}
+
+/// Connect to the first of `addrs` that answers within [`CONNECT_TIMEOUT`].
+pub(super) fn dial(addrs: &[SocketAddr]) -> io::Result<TcpStream> {
+    let mut last = io::Error::new(io::ErrorKind::InvalidInput, "no address to connect to");
+    for addr in addrs {
+        match TcpStream::connect_timeout(addr, CONNECT_TIMEOUT) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last = e,
+        }
+    }
+    Err(last)
+}
+
+/// Listen on a free loopback port and serve connections through `upstream` in
+/// the background. Returns the proxy URL to use, its secret included.
+pub(super) fn start(upstream: Upstream) -> io::Result<String> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let addr = listener.local_addr()?;
+    let secret = super::secret();
+    let expected = credentials(&secret);
+    let clients = Arc::new(AtomicUsize::new(0));
+    std::thread::spawn(move || {
+        for client in listener.incoming() {
+            let Ok(mut client) = client else {
+                continue;
+            };
+            if clients.fetch_add(1, Ordering::Relaxed) >= MAX_CLIENTS {
+                clients.fetch_sub(1, Ordering::Relaxed);
+                let _ = client.write_all(b"HTTP/1.1 503 Service Unavailable\r\n\r\n");
+                continue;
+            }
+            let upstream = upstream.clone();
+            let expected = expected.clone();
+            let clients = clients.clone();
+            std::thread::spawn(move || {
+                if let Err(e) = serve(client, &upstream, &expected) {
+                    log::debug!("proxy: bridge connection failed: {e}");
+                }
+                clients.fetch_sub(1, Ordering::Relaxed);
+            });
+        }
+    });
+    Ok(format!("http://{USER}:{secret}@{addr}"))
+}
+
+/// The `Proxy-Authorization` value a client holding `secret` sends.
+fn credentials(secret: &str) -> String {
+    let token = base64::engine::general_purpose::STANDARD.encode(format!("{USER}:{secret}"));
+    format!("Basic {token}")
+}
+
+/// Whether `head` carries the `expected` `Proxy-Authorization`.
+fn authorized(head: &[u8], expected: &str) -> bool {
+    head.split(|b| *b == b'\n').skip(1).any(|header| {
+        let header = header.strip_suffix(b"\r").unwrap_or(header);
+        let Some(colon) = header.iter().position(|b| *b == b':') else {
+            return false;
+        };
+        let (name, value) = (&header[..colon], &header[colon + 1..]);
+        name.trim_ascii()
+            .eq_ignore_ascii_case(b"proxy-authorization")
+            && value.trim_ascii() == expected.as_bytes()
+    })
+}
+
+/// Serve one client connection: read its request head, open the connection
+/// it names, and splice the two.
+fn serve(mut client: TcpStream, upstream: &Upstream, expected: &str) -> io::Result<()> {
+    client.set_read_timeout(Some(HEAD_TIMEOUT))?;
+    let (head, rest) = read_head(&mut client)?;
+    // A tunnel may idle for as long as its ends like.
+    client.set_read_timeout(None)?;
+    if !authorized(&head, expected) {
+        client.write_all(
+            b"HTTP/1.1 407 Proxy Authentication Required\r\n\
+              Proxy-Authenticate: Basic realm=\"retsurf\"\r\n\r\n",
+        )?;
+        return Ok(());
+    }
+    let line_end = head
+        .windows(2)
+        .position(|w| w == b"\r\n")
+        .unwrap_or(head.len());
+    let request_line = String::from_utf8_lossy(&head[..line_end]);
+    let Some(target) = Target::parse(&request_line) else {
+        client.write_all(b"HTTP/1.1 400 Bad Request\r\n\r\n")?;
+        return Ok(());
+    };
+    let mut upstream = match upstream.connect(&target.host, target.port) {
+        Ok(stream) => stream,
+        Err(e) => {
+            client.write_all(b"HTTP/1.1 502 Bad Gateway\r\n\r\n")?;
+            return Err(e);
+        }
+    };
+    match &target.origin_line {
+        None => client.write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")?,
+        Some(line) => upstream.write_all(&origin_head(&head, line))?,
+    }
+    // Any body bytes that came with the head.
+    upstream.write_all(&rest)?;
+    splice(client, upstream)
+}
+
+/// What a request head asks for.
+#[derive(Debug, PartialEq)]
+struct Target {
+    host: String,
+    port: u16,
+    /// The request line to send on for a plain-HTTP request; `None` for a
+    /// `CONNECT` tunnel.
+    origin_line: Option<String>,
+}
+
+impl Target {
+    fn parse(request_line: &str) -> Option<Self> {
+        let mut parts = request_line.split(' ');
+        let (method, target, version) = (parts.next()?, parts.next()?, parts.next()?);
+        if method.eq_ignore_ascii_case("CONNECT") {
+            let (host, port) = target.rsplit_once(':')?;
+            return Some(Self {
+                host: host
+                    .trim_start_matches('[')
+                    .trim_end_matches(']')
+                    .to_string(),
+                port: port.parse().ok()?,
+                origin_line: None,
+            });
+        }
+        let url = url::Url::parse(target).ok()?;
+        let mut path = url.path().to_string();
+        if let Some(query) = url.query() {
+            path = format!("{path}?{query}");
+        }
+        Some(Self {
+            host: url
+                .host_str()?
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .to_string(),
+            port: url.port_or_known_default()?,
+            origin_line: Some(format!("{method} {path} {version}")),
+        })
+    }
+}
+
+/// The head to send on for a plain-HTTP request: `line` in place of the
+/// request line, without the hop-by-hop headers meant for the proxy, and
+/// with `Connection: close` — the connection is spliced to one origin, so the
+/// client must not reuse it for a request to another.
+fn origin_head(head: &[u8], line: &str) -> Vec<u8> {
+    let mut out = format!("{line}\r\n").into_bytes();
+    for header in head.split(|b| *b == b'\n').skip(1) {
+        let header = header.strip_suffix(b"\r").unwrap_or(header);
+        let name = header.split(|b| *b == b':').next().unwrap_or_default();
+        let hop = [
+            &b"connection"[..],
+            b"proxy-connection",
+            b"proxy-authorization",
+        ]
+        .iter()
+        .any(|hop| name.trim_ascii().eq_ignore_ascii_case(hop));
+        if header.is_empty() || hop {
+            continue;
+        }
+        out.extend_from_slice(header);
+        out.extend_from_slice(b"\r\n");
+    }
+    out.extend_from_slice(b"Connection: close\r\n\r\n");
+    out
+}
+
+/// Read up to the blank line ending the head; returns the head (including the
+/// blank line) and whatever was read past it.
+fn read_head(client: &mut TcpStream) -> io::Result<(Vec<u8>, Vec<u8>)> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        if let Some(end) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            let rest = buf.split_off(end + 4);
+            return Ok((buf, rest));
+        }
+        if buf.len() > MAX_HEAD {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "request head too long",
+            ));
+        }
+        let n = client.read(&mut chunk)?;
+        if n == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+}
+
+/// Copy both ways until each side is done, passing on the half-closes.
+fn splice(client: TcpStream, upstream: TcpStream) -> io::Result<()> {
+    let (mut client_read, mut upstream_write) = (client.try_clone()?, upstream.try_clone()?);
+    let up = std::thread::spawn(move || {
+        let _ = io::copy(&mut client_read, &mut upstream_write);
+        let _ = upstream_write.shutdown(Shutdown::Write);
+    });
+    let (mut upstream_read, mut client_write) = (upstream, client);
+    let _ = io::copy(&mut upstream_read, &mut client_write);
+    let _ = client_write.shutdown(Shutdown::Write);
+    let _ = up.join();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `CONNECT` names the tunnel's end; a plain request is re-sent in origin
+    /// form to its host, port 80 by default.
+    #[test]
+    fn request_lines_name_their_target() {
+        assert_eq!(
+            Target::parse("CONNECT example.com:443 HTTP/1.1").unwrap(),
+            Target {
+                host: "example.com".to_string(),
+                port: 443,
+                origin_line: None,
+            }
+        );
+        assert_eq!(
+            Target::parse("GET http://example.com/a/b?q=1 HTTP/1.1").unwrap(),
+            Target {
+                host: "example.com".to_string(),
+                port: 80,
+                origin_line: Some("GET /a/b?q=1 HTTP/1.1".to_string()),
+            }
+        );
+        assert!(Target::parse("GET /relative HTTP/1.1").is_none());
+    }
+
+    /// Only a head with the bridge's own credentials gets through.
+    #[test]
+    fn requests_need_the_secret() {
+        let expected = credentials("s3cret");
+        let head =
+            format!("CONNECT example.com:443 HTTP/1.1\r\nproxy-authorization:  {expected}\r\n\r\n");
+        assert!(authorized(head.as_bytes(), &expected));
+        let wrong = format!(
+            "CONNECT example.com:443 HTTP/1.1\r\nProxy-Authorization: {}\r\n\r\n",
+            credentials("guess")
+        );
+        assert!(!authorized(wrong.as_bytes(), &expected));
+        assert!(!authorized(
+            b"CONNECT example.com:443 HTTP/1.1\r\n\r\n",
+            &expected
+        ));
+    }
+
+    /// The origin gets its own request line and a closing connection, not the
+    /// proxy's headers.
+    #[test]
+    fn origin_head_drops_proxy_headers() {
+        let head = b"GET http://example.com/ HTTP/1.1\r\nHost: example.com\r\n\
+                     Proxy-Connection: keep-alive\r\nconnection: keep-alive\r\n\r\n";
+        assert_eq!(
+            origin_head(head, "GET / HTTP/1.1"),
+            b"GET / HTTP/1.1\r\nHost: example.com\r\nConnection: close\r\n\r\n"
+        );
+    }
+}
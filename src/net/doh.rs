@@ -0,0 +1,320 @@
+//! DNS over HTTPS (`[network] doh_url`, RFC 8484), for firmwares whose DNS is
+//! broken or hijacked. Names are asked of the DoH server in wire format (a
+//! `GET` with `?dns=`), cached for their TTL, and looked up with the system
+//! resolver when the server can't be reached or answers nonsense. Connections
+//! use it through the local bridge ([`super::bridge`]); see [`super::proxy`].
+//!
+//! The DoH server's own name goes through the system resolver — an IP
+//! address in `doh_url` (e.g. `https://1.1.1.1/dns-query`) avoids that.
+
+use base64::Engine;
+use std::collections::HashMap;
+use std::io;
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Per query; past it the system resolver is asked instead.
+const QUERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Bounds on how long an answer is cached, whatever its TTL.
+const MIN_TTL: u32 = 60;
+const MAX_TTL: u32 = 60 * 60;
+
+/// Cached names; expired ones are dropped when it fills.
+const MAX_CACHED: usize = 512;
+
+/// Cap on an answer's size.
+const MAX_ANSWER: u64 = 64 * 1024;
+
+const TYPE_A: u16 = 1;
+const TYPE_AAAA: u16 = 28;
+
+static RESOLVER: OnceLock<Resolver> = OnceLock::new();
+
+/// The resolver for `doh_url`, or `None` for system DNS (empty or unusable).
+pub(super) fn init(doh_url: &str) -> Option<&'static Resolver> {
+    let doh_url = doh_url.trim();
+    if doh_url.is_empty() {
+        return None;
+    }
+    if let Err(e) = url::Url::parse(doh_url) {
+        log::error!("doh: `{doh_url}`: {e}; using system DNS");
+        return None;
+    }
+    log::info!("doh: resolving through {doh_url}");
+    Some(RESOLVER.get_or_init(|| Resolver::new(doh_url)))
+}
+
+pub(super) struct Resolver {
+    url: String,
+    /// Direct: the DoH server is never reached through the bridge.
+    agent: ureq::Agent,
+    cache: Mutex<HashMap<String, Cached>>,
+}
+
+struct Cached {
+    ips: Vec<IpAddr>,
+    expires: Instant,
+}
+
+impl Resolver {
+    fn new(url: &str) -> Self {
+        let mut config = ureq::Agent::config_builder().timeout_global(Some(QUERY_TIMEOUT));
+        if let Some(tls) = super::tls::config() {
+            config = config.tls_config(tls);
+        }
+        Self {
+            url: url.to_string(),
+            agent: config.build().new_agent(),
+            cache: Mutex::default(),
+        }
+    }
+
+    /// The addresses of `host`, with `port`: an IP address as it is, a name
+    /// from the cache or the DoH server — or the system resolver if that
+    /// fails. A name the server says doesn't exist is an error.
+    pub(super) fn resolve(&self, host: &str, port: u16) -> io::Result<Vec<SocketAddr>> {
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            return Ok(vec![SocketAddr::new(ip, port)]);
+        }
+        let host = host.to_ascii_lowercase();
+        let ips = match self.lookup(&host) {
+            Ok(ips) => ips,
+            Err(e) => {
+                log::warn!("doh: {host}: {e}; trying system DNS");
+                return (host.as_str(), port)
+                    .to_socket_addrs()
+                    .map(Iterator::collect);
+            }
+        };
+        if ips.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{host}: no such host"),
+            ));
+        }
+        Ok(ips
+            .into_iter()
+            .map(|ip| SocketAddr::new(ip, port))
+            .collect())
+    }
+
+    /// `host`'s addresses, IPv4 first: IPv6 is only asked for when there are
+    /// none. Empty for a name that doesn't exist.
+    fn lookup(&self, host: &str) -> Result<Vec<IpAddr>, String> {
+        let now = Instant::now();
+        if let Some(hit) = self.cache.lock().unwrap().get(host) {
+            if hit.expires > now {
+                return Ok(hit.ips.clone());
+            }
+        }
+        let (mut ips, mut ttl) = self.query(host, TYPE_A)?;
+        if ips.is_empty() {
+            (ips, ttl) = self.query(host, TYPE_AAAA)?;
+        }
+        if !ips.is_empty() {
+            let mut cache = self.cache.lock().unwrap();
+            if cache.len() >= MAX_CACHED {
+                cache.retain(|_, cached| cached.expires > now);
+                if cache.len() >= MAX_CACHED {
+                    cache.clear();
+                }
+            }
+            let ttl = Duration::from_secs(ttl.clamp(MIN_TTL, MAX_TTL).into());
+            cache.insert(
+                host.to_string(),
+                Cached {
+                    ips: ips.clone(),
+                    expires: now + ttl,
+                },
+            );
+        }
+        Ok(ips)
+    }
+
+    /// One DoH query; the answer's addresses of `qtype` and their lowest TTL.
+    fn query(&self, host: &str, qtype: u16) -> Result<(Vec<IpAddr>, u32), String> {
+        let message =
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(encode_query(host, qtype)?);
+        let mut response = self
+            .agent
+            .get(&self.url)
+            .query("dns", &message)
+            .header("Accept", "application/dns-message")
+            .call()
+            .map_err(|e| e.to_string())?;
+        let answer = response
+            .body_mut()
+            .with_config()
+            .limit(MAX_ANSWER)
+            .read_to_vec()
+            .map_err(|e| e.to_string())?;
+        parse_answer(&answer, qtype)
+    }
+}
+
+/// A recursive query for `host`'s `qtype` records. ID 0, as RFC 8484 asks so
+/// that answers cache well.
+fn encode_query(host: &str, qtype: u16) -> Result<Vec<u8>, String> {
+    let mut message = vec![0, 0, 1, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+    for label in host.trim_end_matches('.').split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(format!("bad host name `{host}`"));
+        }
+        message.push(label.len() as u8);
+        message.extend_from_slice(label.as_bytes());
+    }
+    message.push(0);
+    message.extend_from_slice(&qtype.to_be_bytes());
+    message.extend_from_slice(&1u16.to_be_bytes()); // class IN
+    Ok(message)
+}
+
+/// The `qtype` addresses in an answer, skipping the CNAMEs on the way, and
+/// their lowest TTL. No addresses for a name that doesn't exist (NXDOMAIN).
+fn parse_answer(message: &[u8], qtype: u16) -> Result<(Vec<IpAddr>, u32), String> {
+    let malformed = || "malformed DNS answer".to_string();
+    let u16_at = |at: usize| {
+        message
+            .get(at..at + 2)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]))
+            .ok_or_else(malformed)
+    };
+    match u16_at(2)? & 0xf {
+        0 => {}
+        3 => return Ok((Vec::new(), 0)),
+        rcode => return Err(format!("DNS error (rcode {rcode})")),
+    }
+    let (questions, answers) = (u16_at(4)?, u16_at(6)?);
+    let mut at = 12;
+    for _ in 0..questions {
+        at = skip_name(message, at).ok_or_else(malformed)? + 4;
+    }
+    let (mut ips, mut ttl) = (Vec::new(), u32::MAX);
+    for _ in 0..answers {
+        at = skip_name(message, at).ok_or_else(malformed)?;
+        let rtype = u16_at(at)?;
+        let record_ttl = (u32::from(u16_at(at + 4)?) << 16) | u32::from(u16_at(at + 6)?);
+        let len = usize::from(u16_at(at + 8)?);
+        let data = message.get(at + 10..at + 10 + len).ok_or_else(malformed)?;
+        at += 10 + len;
+        if rtype != qtype {
+            continue;
+        }
+        let ip = match data.len() {
+            4 => IpAddr::from(<[u8; 4]>::try_from(data).unwrap()),
+            16 => IpAddr::from(<[u8; 16]>::try_from(data).unwrap()),
+            _ => return Err(malformed()),
+        };
+        ips.push(ip);
+        ttl = ttl.min(record_ttl);
+    }
+    Ok((ips, ttl))
+}
+
+/// The offset past the name at `at`: labels up to the root, or up to a
+/// compression pointer.
+fn skip_name(message: &[u8], mut at: usize) -> Option<usize> {
+    loop {
+        match *message.get(at)? {
+            0 => return Some(at + 1),
+            len if len & 0xc0 == 0xc0 => return Some(at + 2),
+            len => at += 1 + usize::from(len),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// An answer to `query`: a CNAME, then `ip` for the name it points at.
+    fn answer(query: &[u8], ip: [u8; 4], ttl: u32) -> Vec<u8> {
+        let mut message = query.to_vec();
+        message[2..4].copy_from_slice(&0x8180u16.to_be_bytes());
+        message[6..8].copy_from_slice(&2u16.to_be_bytes());
+        // CNAME for the question's name -> "cdn.<name>".
+        message.extend_from_slice(&[0xc0, 12, 0, 5, 0, 1]);
+        message.extend_from_slice(&3600u32.to_be_bytes());
+        message.extend_from_slice(&[0, 6, 3, b'c', b'd', b'n', 0xc0, 12]);
+        let cname = message.len() - 6;
+        message.extend_from_slice(&[0xc0 | (cname >> 8) as u8, cname as u8, 0, 1, 0, 1]);
+        message.extend_from_slice(&ttl.to_be_bytes());
+        message.extend_from_slice(&[0, 4]);
+        message.extend_from_slice(&ip);
+        message
+    }
+
+    /// Addresses come out past the CNAME and compressed names; a missing
+    /// name is empty rather than an error.
+    #[test]
+    fn answers_parse() {
+        let query = encode_query("example.test", TYPE_A).unwrap();
+        let (ips, ttl) = parse_answer(&answer(&query, [10, 1, 2, 3], 300), TYPE_A).unwrap();
+        assert_eq!(ips, vec![IpAddr::from([10, 1, 2, 3])]);
+        assert_eq!(ttl, 300);
+        assert!(parse_answer(&answer(&query, [10, 1, 2, 3], 300), TYPE_AAAA)
+            .unwrap()
+            .0
+            .is_empty());
+
+        let mut missing = query.clone();
+        missing[2..4].copy_from_slice(&0x8183u16.to_be_bytes());
+        assert!(parse_answer(&missing, TYPE_A).unwrap().0.is_empty());
+        assert!(encode_query("a..b", TYPE_A).is_err());
+    }
+
+    /// Against a local stand-in DoH server: the first lookup asks it, the
+    /// second comes from the cache; with the server gone, system DNS answers.
+    #[test]
+    fn resolves_through_a_stand_in_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let asked = Arc::new(AtomicUsize::new(0));
+        let counter = asked.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut head = Vec::new();
+                let mut byte = [0u8; 1];
+                while !head.ends_with(b"\r\n\r\n") && stream.read(&mut byte).unwrap() == 1 {
+                    head.push(byte[0]);
+                }
+                counter.fetch_add(1, Ordering::SeqCst);
+                let head = String::from_utf8(head).unwrap();
+                let param = head.split("dns=").nth(1).unwrap();
+                let param = param.split([' ', '&']).next().unwrap();
+                let query = base64::engine::general_purpose::URL_SAFE_NO_PAD
+                    .decode(param)
+                    .unwrap();
+                let body = answer(&query, [10, 1, 2, 3], 300);
+                let reply = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/dns-message\r\n\
+                     Content-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                stream.write_all(reply.as_bytes()).unwrap();
+                stream.write_all(&body).unwrap();
+            }
+        });
+
+        let resolver = Resolver::new(&format!("http://{addr}/dns-query"));
+        let expected = vec![SocketAddr::from(([10, 1, 2, 3], 443))];
+        assert_eq!(resolver.resolve("Example.test", 443).unwrap(), expected);
+        assert_eq!(resolver.resolve("example.test", 443).unwrap(), expected);
+        assert_eq!(asked.load(Ordering::SeqCst), 1);
+
+        let gone = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let resolver = Resolver::new(&format!("http://{gone}/dns-query"));
+        let addrs = resolver.resolve("localhost", 80).unwrap();
+        assert!(addrs.iter().all(|addr| addr.ip().is_loopback()));
+    }
+}
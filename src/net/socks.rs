@@ -0,0 +1,83 @@
+//! The SOCKS5 client side of the local bridge (see [`super::bridge`]): the
+//! handshake that opens a connection through a SOCKS server.
+
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+
+/// A SOCKS5 server to go through.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Server {
+    /// `host:port`.
+    pub addr: String,
+    /// Username and password, for servers that ask for them.
+    pub auth: Option<(String, String)>,
+}
+
+/// Open a connection to `host:port` through `server` (RFC 1928, with RFC 1929
+/// username/password auth when configured). The name is resolved by the server.
+pub(super) fn connect(server: &Server, host: &str, port: u16) -> io::Result<TcpStream> {
+    let addrs: Vec<_> = server.addr.to_socket_addrs()?.collect();
+    let mut stream = super::bridge::dial(&addrs)?;
+    // A server that stops answering mid-handshake mustn't hold the client.
+    stream.set_read_timeout(Some(super::bridge::CONNECT_TIMEOUT))?;
+    handshake(&mut stream, server, host, port)?;
+    stream.set_read_timeout(None)?;
+    Ok(stream)
+}
+
+fn handshake(stream: &mut TcpStream, server: &Server, host: &str, port: u16) -> io::Result<()> {
+    let refused = |what: &str| io::Error::new(io::ErrorKind::ConnectionRefused, what.to_string());
+
+    let methods: &[u8] = if server.auth.is_some() { &[0, 2] } else { &[0] };
+    stream.write_all(&[5, methods.len() as u8])?;
+    stream.write_all(methods)?;
+    let mut reply = [0u8; 2];
+    stream.read_exact(&mut reply)?;
+    match (reply[1], &server.auth) {
+        (0, _) => {}
+        (2, Some((user, password))) => {
+            let (user, password) = (user.as_bytes(), password.as_bytes());
+            if user.len() > 255 || password.len() > 255 {
+                return Err(refused("SOCKS credentials too long"));
+            }
+            let mut auth = vec![1, user.len() as u8];
+            auth.extend_from_slice(user);
+            auth.push(password.len() as u8);
+            auth.extend_from_slice(password);
+            stream.write_all(&auth)?;
+            stream.read_exact(&mut reply)?;
+            if reply[1] != 0 {
+                return Err(refused("SOCKS authentication failed"));
+            }
+        }
+        _ => return Err(refused("no acceptable SOCKS auth method")),
+    }
+
+    let name = host.as_bytes();
+    if name.len() > 255 {
+        return Err(refused("host name too long"));
+    }
+    let mut request = vec![5, 1, 0, 3, name.len() as u8];
+    request.extend_from_slice(name);
+    request.extend_from_slice(&port.to_be_bytes());
+    stream.write_all(&request)?;
+    let mut head = [0u8; 4];
+    stream.read_exact(&mut head)?;
+    if head[1] != 0 {
+        return Err(refused(&format!("SOCKS connect failed (code {})", head[1])));
+    }
+    // The bound address, unused: IPv4, a name, or IPv6; then the port.
+    let skip = match head[3] {
+        1 => 4,
+        4 => 16,
+        3 => {
+            let mut len = [0u8; 1];
+            stream.read_exact(&mut len)?;
+            len[0] as usize
+        }
+        _ => return Err(refused("bad SOCKS reply")),
+    };
+    let mut bound = vec![0u8; skip + 2];
+    stream.read_exact(&mut bound)?;
+    Ok(())
+}
@@ -0,0 +1,133 @@
+//! `[network] extra_ca_certs`: CAs to trust on top of the public roots (an
+//! internal CA on staging servers), for Servo's page loads and the app's ureq
+//! agents alike. The agents get the combined roots (see [`super::agent`]);
+//! Servo reads one PEM bundle of both, written to the cache dir at startup
+//! (see `browser::engine`).
+
+use crate::config::{self, NetworkConfig};
+use base64::Engine;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+use ureq::tls::{Certificate, PemItem, RootCerts, TlsConfig};
+
+/// Set once by [`init`], before anything connects.
+static ROOTS: OnceLock<Option<Roots>> = OnceLock::new();
+
+/// The bundle Servo reads, under the cache dir.
+const BUNDLE: &str = "ca-bundle.pem";
+
+struct Roots {
+    /// The public roots, then the configured CAs.
+    certs: Arc<Vec<Certificate<'static>>>,
+    /// Where the same certificates are as PEM; `None` if it couldn't be
+    /// written (page loads then only trust the public roots).
+    bundle: Option<String>,
+}
+
+/// Read the configured PEM files once at startup. A file that can't be read
+/// is logged and skipped.
+pub fn init(cfg: &NetworkConfig) {
+    ROOTS.get_or_init(|| {
+        let mut extra = Vec::new();
+        for path in &cfg.extra_ca_certs {
+            let full = Path::new(&config::data_dir()).join(path);
+            match std::fs::read(&full)
+                .map_err(|e| e.to_string())
+                .and_then(|pem| read_pem(&pem))
+            {
+                Ok(certs) => {
+                    log::info!("tls: {} CA certificate(s) from {path}", certs.len());
+                    extra.extend(certs);
+                }
+                Err(e) => log::error!("tls: `{path}`: {e}; skipped"),
+            }
+        }
+        if extra.is_empty() {
+            return None;
+        }
+        let mut certs: Vec<Certificate<'static>> = webpki_root_certs::TLS_SERVER_ROOT_CERTS
+            .iter()
+            .map(|der| Certificate::from_der(der.as_ref()))
+            .collect();
+        certs.extend(extra);
+        let path = Path::new(&config::cache_dir()).join(BUNDLE);
+        let bundle = match std::fs::write(&path, pem(&certs)) {
+            Ok(()) => Some(path.to_string_lossy().into_owned()),
+            Err(e) => {
+                log::error!("tls: could not write {}: {e}", path.display());
+                None
+            }
+        };
+        Some(Roots {
+            certs: Arc::new(certs),
+            bundle,
+        })
+    });
+}
+
+/// The TLS settings for a ureq agent; `None` keeps ureq's public roots.
+pub(super) fn config() -> Option<TlsConfig> {
+    let roots = ROOTS.get()?.as_ref()?;
+    Some(
+        TlsConfig::builder()
+            .root_certs(RootCerts::Specific(roots.certs.clone()))
+            .build(),
+    )
+}
+
+/// The PEM bundle for Servo's `certificate_path`; `None` without extra CAs.
+pub fn bundle_path() -> Option<&'static str> {
+    ROOTS.get()?.as_ref()?.bundle.as_deref()
+}
+
+/// The certificates in a PEM file (other items, like keys, are ignored).
+fn read_pem(pem: &[u8]) -> Result<Vec<Certificate<'static>>, String> {
+    let mut certs = Vec::new();
+    for item in ureq::tls::parse_pem(pem) {
+        if let PemItem::Certificate(cert) = item.map_err(|e| e.to_string())? {
+            certs.push(cert.to_owned());
+        }
+    }
+    if certs.is_empty() {
+        return Err("no certificates".to_string());
+    }
+    Ok(certs)
+}
+
+/// `certs` as PEM, 64 columns a line.
+fn pem(certs: &[Certificate]) -> String {
+    let mut out = String::new();
+    for cert in certs {
+        let encoded = base64::engine::general_purpose::STANDARD.encode(cert.der());
+        out.push_str("-----BEGIN CERTIFICATE-----\n");
+        for line in encoded.as_bytes().chunks(64) {
+            out.push_str(std::str::from_utf8(line).unwrap());
+            out.push('\n');
+        }
+        out.push_str("-----END CERTIFICATE-----\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The bundle reads back as the certificates it was written from; a file
+    /// without any is refused.
+    #[test]
+    fn pem_round_trips() {
+        let ders: [&'static [u8]; 2] = [&[0x30, 0x03, 0x02, 0x01, 0x01], &[0x42; 100]];
+        let certs: Vec<Certificate<'static>> =
+            ders.iter().map(|der| Certificate::from_der(der)).collect();
+        let text = pem(&certs);
+        assert!(text.lines().all(|line| line.len() <= 64));
+        let read: Vec<Vec<u8>> = read_pem(text.as_bytes())
+            .unwrap()
+            .iter()
+            .map(|cert| cert.der().to_vec())
+            .collect();
+        assert_eq!(read, ders.map(<[u8]>::to_vec));
+        assert!(read_pem(b"not a certificate").is_err());
+    }
+}
@@ -0,0 +1,122 @@
+//! The blocked-requests panel, opened from the toolbar's shield chip: what the
+//! ad blocker and lightweight mode blocked on the active tab's page (see
+//! [`crate::browser::block_log`]), newest first, with the focused load's full
+//! URL and matching rule spelled out. Its Requests section (L1/R1, or the
+//! `requests` binding) lists every load the tab made instead (see
+//! [`crate::browser::net_log`]), and Y saves that as a HAR file. Snapshots
+//! taken on opening; read-only. [`crate::ui`]'s `blocked` renderer draws it.
+
+use crate::browser::block_log::{BlockLog, Blocked};
+use crate::browser::net_log::{Entry, NetLog};
+
+/// The panel's two lists.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum BlockedSection {
+    Blocked,
+    Requests,
+}
+
+pub struct BlockedView {
+    visible: bool,
+    section: BlockedSection,
+    log: BlockLog,
+    requests: NetLog,
+    selected: usize,
+    /// Where the last HAR export went, shown under the heading.
+    note: Option<String>,
+}
+
+impl BlockedView {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            section: BlockedSection::Blocked,
+            log: BlockLog::default(),
+            requests: NetLog::default(),
+            selected: 0,
+            note: None,
+        }
+    }
+
+    #[inline]
+    pub fn visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Open on `section` of `log` and `requests`, focusing the newest load.
+    pub fn open(&mut self, log: BlockLog, requests: NetLog, section: BlockedSection) {
+        self.log = log;
+        self.requests = requests;
+        self.section = section;
+        self.selected = 0;
+        self.note = None;
+        self.visible = true;
+    }
+
+    pub fn close(&mut self) {
+        self.visible = false;
+        self.log = BlockLog::default();
+        self.requests = NetLog::default();
+    }
+
+    pub fn section(&self) -> BlockedSection {
+        self.section
+    }
+
+    /// Switch between the two lists (L1/R1; there are only two, so either
+    /// direction flips).
+    pub fn switch_section(&mut self) {
+        self.section = match self.section {
+            BlockedSection::Blocked => BlockedSection::Requests,
+            BlockedSection::Requests => BlockedSection::Blocked,
+        };
+        self.selected = 0;
+    }
+
+    pub fn log(&self) -> &BlockLog {
+        &self.log
+    }
+
+    pub fn requests(&self) -> &NetLog {
+        &self.requests
+    }
+
+    pub fn note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
+
+    pub fn set_note(&mut self, note: String) {
+        self.note = Some(note);
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// The focused blocked load, shown in full above the list.
+    pub fn selected_entry(&self) -> Option<&Blocked> {
+        self.log.entries().nth(self.selected)
+    }
+
+    /// The focused request, in the Requests section.
+    pub fn selected_request(&self) -> Option<&Entry> {
+        self.requests.entries().nth(self.selected)
+    }
+
+    /// Focus row `index` (clicking it).
+    pub fn set_selected(&mut self, index: usize) {
+        self.selected = index.min(self.len().saturating_sub(1));
+    }
+
+    pub fn move_sel(&mut self, dy: i32) {
+        let last = self.len().saturating_sub(1) as i32;
+        self.selected = (self.selected as i32 + dy).clamp(0, last) as usize;
+    }
+
+    fn len(&self) -> usize {
+        match self.section {
+            BlockedSection::Blocked => self.log.entries().count(),
+            BlockedSection::Requests => self.requests.len(),
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! The bookmark edit sheet: a small form over the menu's Bookmarks section
+//! (opened with Y on a row, or straight after "+ New folder") for renaming a
+//! folder, or retitling, tagging, filing and pinning a page. State here is the
+//! focused field and the edit buffers; nothing touches the store until the sheet
+//! is saved (see [`crate::overlay::menu::Menu::edit_close`]). The on-screen
+//! keyboard types into the text fields, the central router drives the rest, and
+//! [`crate::ui`]'s menu renderer draws it.
+
+/// A row of the sheet. A folder only has [`Field::Title`] (its name) and
+/// [`Field::Save`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Title,
+    Tags,
+    /// The folder the page is filed in; Left/Right (or A) cycles it.
+    Folder,
+    /// Whether the page is pinned to the start-page speed dial; A toggles.
+    Pin,
+    Save,
+}
+
+const PAGE_FIELDS: [Field; 5] = [
+    Field::Title,
+    Field::Tags,
+    Field::Folder,
+    Field::Pin,
+    Field::Save,
+];
+const FOLDER_FIELDS: [Field; 2] = [Field::Title, Field::Save];
+
+/// What the sheet edits: an entry of the open folder, by its index there.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum EditTarget {
+    Folder(usize),
+    Page(usize),
+}
+
+pub struct BookmarkEdit {
+    target: EditTarget,
+    /// The page's URL (empty for a folder) — what the pin applies to.
+    url: String,
+    selected: usize,
+    /// Title (a folder's name) and comma-separated tags, as typed.
+    title: String,
+    tags: String,
+    /// Every folder a page can be filed in, as `(path, label)`, and the chosen one.
+    folders: Vec<(Vec<usize>, String)>,
+    folder: usize,
+    pinned: bool,
+}
+
+impl BookmarkEdit {
+    /// A sheet for the folder at entry `index`, named `name`.
+    pub fn folder(index: usize, name: &str) -> Self {
+        Self {
+            target: EditTarget::Folder(index),
+            url: String::new(),
+            selected: 0,
+            title: name.to_string(),
+            tags: String::new(),
+            folders: Vec::new(),
+            folder: 0,
+            pinned: false,
+        }
+    }
+
+    /// A sheet for the page at entry `index`, filed in the folder at `path` (one
+    /// of `folders`, see [`crate::data::bookmarks::Bookmarks::folder_paths`]).
+    pub fn page(
+        index: usize,
+        url: &str,
+        title: &str,
+        tags: &[String],
+        folders: Vec<(Vec<usize>, String)>,
+        path: &[usize],
+        pinned: bool,
+    ) -> Self {
+        let folder = folders.iter().position(|(p, _)| p == path).unwrap_or(0);
+        Self {
+            target: EditTarget::Page(index),
+            url: url.to_string(),
+            selected: 0,
+            title: title.to_string(),
+            tags: tags.join(", "),
+            folders,
+            folder,
+            pinned,
+        }
+    }
+
+    pub fn target(&self) -> EditTarget {
+        self.target
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The rows of this sheet, top to bottom.
+    pub fn fields(&self) -> &'static [Field] {
+        match self.target {
+            EditTarget::Folder(_) => &FOLDER_FIELDS,
+            EditTarget::Page(_) => &PAGE_FIELDS,
+        }
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn field(&self) -> Field {
+        self.fields()[self.selected]
+    }
+
+    /// Focus row `index` (clicking it).
+    pub fn set_selected(&mut self, index: usize) {
+        self.selected = index.min(self.fields().len() - 1);
+    }
+
+    pub fn move_sel(&mut self, dy: i32) {
+        let last = self.fields().len() as i32 - 1;
+        self.selected = (self.selected as i32 + dy).clamp(0, last) as usize;
+    }
+
+    /// Step the focused value by `dx`: cycle the folder, or flip the pin.
+    pub fn adjust(&mut self, dx: i32) {
+        match self.field() {
+            Field::Folder if !self.folders.is_empty() => {
+                let n = self.folders.len() as i32;
+                self.folder = (self.folder as i32 + dx).rem_euclid(n) as usize;
+            }
+            Field::Pin => self.pinned = !self.pinned,
+            _ => {}
+        }
+    }
+
+    /// Whether the focused row is one the on-screen keyboard types into.
+    pub fn text_selected(&self) -> bool {
+        matches!(self.field(), Field::Title | Field::Tags)
+    }
+
+    /// The focused text row's buffer.
+    pub fn text_mut(&mut self) -> Option<&mut String> {
+        match self.field() {
+            Field::Title => Some(&mut self.title),
+            Field::Tags => Some(&mut self.tags),
+            _ => None,
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn tags(&self) -> &str {
+        &self.tags
+    }
+
+    /// The chosen folder's path and label (empty at the top level).
+    pub fn folder_choice(&self) -> (&[usize], &str) {
+        self.folders
+            .get(self.folder)
+            .map_or((&[][..], ""), |(path, label)| {
+                (path.as_slice(), label.as_str())
+            })
+    }
+
+    pub fn pinned(&self) -> bool {
+        self.pinned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The folder row wraps through the choices and starts on the page's own.
+    #[test]
+    fn folder_choice_cycles() {
+        let folders = vec![
+            (vec![], String::new()),
+            (vec![0], "Work".to_string()),
+            (vec![0, 0], "Work › Docs".to_string()),
+        ];
+        let mut edit = BookmarkEdit::page(2, "https://a.test/", "A", &[], folders, &[0], false);
+        assert_eq!(edit.folder_choice().1, "Work");
+        edit.move_sel(2);
+        assert!(edit.field() == Field::Folder);
+        edit.adjust(1);
+        assert_eq!(edit.folder_choice().0, &[0, 0]);
+        edit.adjust(1);
+        assert_eq!(edit.folder_choice().1, "");
+        edit.move_sel(1);
+        edit.adjust(1);
+        assert!(edit.pinned());
+    }
+}
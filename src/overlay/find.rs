@@ -0,0 +1,104 @@
+//! Find-in-page state. The query is typed on the on-screen keyboard (its Enter
+//! runs the search); the page itself marks every match and scrolls the current
+//! one into view (injected JavaScript, see [`crate::browser`]), reporting back
+//! which match is current out of how many. While the bar is up the shoulders
+//! step to the previous / next match and B closes it, clearing the marks.
+
+/// The find bar: the query being typed and the page's last answer for it.
+pub struct Find {
+    pub visible: bool,
+    /// The OSK's edit buffer while typing the query.
+    query: String,
+    /// The query the page last searched for — stepping re-sends it, so an edit
+    /// that wasn't submitted yet doesn't restart the search.
+    searched: String,
+    /// The current match (1-based) and the match count, as last reported by the
+    /// page. `None` until the first answer for `searched` arrives.
+    result: Option<(usize, usize)>,
+}
+
+impl Find {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            query: String::new(),
+            searched: String::new(),
+            result: None,
+        }
+    }
+
+    /// Show the bar. The previous query is kept, so reopening edits it.
+    pub fn open(&mut self) {
+        self.visible = true;
+    }
+
+    /// Hide the bar and forget the page's answer (its marks are cleared by the
+    /// caller); the query stays for the next open.
+    pub fn close(&mut self) {
+        self.visible = false;
+        self.searched.clear();
+        self.result = None;
+    }
+
+    /// The query as typed so far.
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// The buffer the on-screen keyboard types into.
+    pub fn input_mut(&mut self) -> &mut String {
+        &mut self.query
+    }
+
+    /// A search for `query` was sent to the page; its answer is pending.
+    pub fn begin_search(&mut self, query: &str) {
+        self.searched = query.to_string();
+        self.result = None;
+    }
+
+    /// The query the page is searching for (empty before the first search).
+    pub fn searched(&self) -> &str {
+        &self.searched
+    }
+
+    /// The page answered: match `current` (1-based, 0 with no matches) of `total`.
+    pub fn set_result(&mut self, current: usize, total: usize) {
+        self.result = Some((current, total));
+    }
+
+    /// Whether the page answered the search with nothing found.
+    pub fn no_matches(&self) -> bool {
+        !self.searched.is_empty() && matches!(self.result, Some((_, 0)))
+    }
+
+    /// The counter shown on the bar: `3 / 17`, `No matches`, or nothing while
+    /// there's no search or its answer is still pending.
+    pub fn status(&self) -> String {
+        match self.result {
+            _ if self.searched.is_empty() => String::new(),
+            Some((_, 0)) => "No matches".to_string(),
+            Some((current, total)) => format!("{current} / {total}"),
+            None => String::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The counter follows the page's answer, and a fresh search blanks it.
+    #[test]
+    fn status_tracks_the_last_answer() {
+        let mut find = Find::new();
+        assert_eq!(find.status(), "");
+        find.begin_search("retsurf");
+        assert_eq!(find.status(), "");
+        find.set_result(3, 17);
+        assert_eq!(find.status(), "3 / 17");
+        find.set_result(0, 0);
+        assert_eq!(find.status(), "No matches");
+        find.close();
+        assert_eq!(find.status(), "");
+    }
+}
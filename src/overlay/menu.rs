@@ -1,16 +1,22 @@
 //! The full-screen menu opened with Select (or the menu toolbar button): a tabbed
 //! overlay over the page with Tabs / Bookmarks / History / Downloads sections.
-//! It owns the overlay state (whether it's shown, which section is active) and the
-//! Bookmarks, History, and Downloads stores. The central router ([`crate::app`])
-//! maps gamepad / keyboard / mouse input to section switches, selection moves,
-//! open, delete, and clear; [`crate::ui`] renders it.
+//! It owns the overlay state (whether it's shown, which section is active, the
+//! bookmark edit sheet) and the Bookmarks, History, and Downloads stores. The
+//! central router ([`crate::app`]) maps gamepad / keyboard / mouse input to
+//! section switches, selection moves, open, delete, and clear; [`crate::ui`]
+//! renders it.
 
+use super::bookmark_edit::{BookmarkEdit, EditTarget};
 use crate::config::{DownloadsConfig, HistoryConfig};
-use crate::data::bookmarks::Bookmarks;
+use crate::data::bookmarks::{self, Bookmarks, Entry};
 use crate::data::dial::Dial;
 use crate::data::downloads::Downloads;
 use crate::data::history::History;
 
+/// The Tabs section's leading rows — "+ New tab" and "+ New no-history tab" —
+/// with the tabs after them.
+pub const NEW_TAB_ROWS: usize = 2;
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Section {
     Tabs,
@@ -47,19 +53,25 @@ pub struct Menu {
     section: Section,
     bookmarks: Bookmarks,
     /// The start page's pinned speed-dial (separate from bookmarks; pinned from
-    /// the Bookmarks / History sections with Y). Lives here so both the menu's
-    /// pin action and the start-page renderer ([`crate::ui::home`]) share it.
+    /// a bookmark's edit sheet). Lives here so both the sheet's pin row and the
+    /// start-page renderer ([`crate::ui::home`]) share it.
     pub dial: Dial,
     history: History,
     pub downloads: Downloads,
     /// Highlighted row in the Tabs section. The tab list lives in the browser, so
     /// this index is clamped against `tab_count`, refreshed each frame the menu is
-    /// shown. The row at index `tab_count` is the "+ New tab" entry.
+    /// shown. The first [`NEW_TAB_ROWS`] rows are the new-tab buttons.
     tab_selected: usize,
     tab_count: usize,
     /// The clear row was activated once and awaits confirmation. Clearing history
     /// or the download list can't be undone, so it takes two presses.
     clear_armed: bool,
+    /// The bookmark folder (entry index in the open folder) whose delete was
+    /// pressed once. A folder goes with everything in it, so a non-empty one
+    /// takes two presses, like the clear rows.
+    delete_armed: Option<usize>,
+    /// The bookmark edit sheet, while it's up over the Bookmarks section.
+    edit: Option<BookmarkEdit>,
 }
 
 impl Menu {
@@ -78,32 +90,41 @@ impl Menu {
             tab_selected: 0,
             tab_count: 0,
             clear_armed: false,
+            delete_armed: None,
+            edit: None,
         }
     }
 
     /// Show the menu, resetting every section's highlight to the top. In Tabs,
-    /// index 0 is the "+ New tab" button, so the cursor starts on the first tab
-    /// (index 1) — A then switches tabs rather than spawning a new one.
+    /// the new-tab buttons lead, so the cursor starts on the first tab — A then
+    /// switches tabs rather than spawning a new one.
     pub fn open(&mut self) {
         self.visible = true;
         self.bookmarks.reset();
         self.history.reset();
         self.downloads.reset();
-        self.tab_selected = 1;
+        self.tab_selected = NEW_TAB_ROWS;
         self.clear_armed = false;
+        self.delete_armed = None;
+        self.edit = None;
     }
 
     pub fn close(&mut self) {
         self.visible = false;
-        // History recording defers its disk write; persist on close so a quick
-        // browse-then-quit still saves (see [`crate::data::history::History::flush`]).
-        self.history.flush();
+        self.edit = None;
+        // History recording and page titles defer their disk writes; persist on
+        // close so a quick browse-then-quit still saves (see
+        // [`crate::data::history::History::flush`]).
+        self.flush_stores();
     }
 
-    /// Persist any deferred history changes. Used by the app's periodic throttle
-    /// and the shutdown path (where `Drop` doesn't run — see [`crate::app`]).
-    pub fn flush_history(&mut self) {
+    /// Persist deferred history and title changes (history, bookmarks, the
+    /// dial). Used by the app's periodic throttle and the shutdown path (where
+    /// `Drop` doesn't run — see [`crate::app`]).
+    pub fn flush_stores(&mut self) {
         self.history.flush();
+        self.bookmarks.flush();
+        self.dial.flush();
     }
 
     pub fn section(&self) -> Section {
@@ -114,6 +135,10 @@ impl Menu {
         &self.bookmarks
     }
 
+    pub fn bookmarks_mut(&mut self) -> &mut Bookmarks {
+        &mut self.bookmarks
+    }
+
     pub fn history(&self) -> &History {
         &self.history
     }
@@ -128,12 +153,16 @@ impl Menu {
         let i = (self.section.index() as i32 + delta).clamp(0, last) as usize;
         self.section = Section::ALL[i];
         self.clear_armed = false;
+        self.delete_armed = None;
+        self.edit = None;
     }
 
     /// Jump straight to a section (clicking its tab).
     pub fn set_section(&mut self, section: Section) {
         self.section = section;
         self.clear_armed = false;
+        self.delete_armed = None;
+        self.edit = None;
     }
 
     /// Whether the clear row is awaiting its confirming second press.
@@ -152,20 +181,21 @@ impl Menu {
     /// Move the active section's selection by `dy` rows.
     pub fn move_sel(&mut self, dy: i32) {
         self.clear_armed = false;
+        self.delete_armed = None;
         match self.section {
             Section::Bookmarks => self.bookmarks.move_sel(dy),
             Section::History => self.history.move_sel(dy),
             Section::Downloads => self.downloads.move_sel(dy),
-            // Index 0 is the "+ New tab" button; the tabs follow at `1..=tab_count`.
+            // The new-tab buttons lead; the tabs follow.
             Section::Tabs => {
-                let last = self.tab_count as i32;
+                let last = (self.tab_count + NEW_TAB_ROWS - 1) as i32;
                 self.tab_selected = (self.tab_selected as i32 + dy).clamp(0, last) as usize;
             }
         }
     }
 
-    /// Highlighted row in the Tabs section (0 == the "+ New tab" button, then
-    /// the tabs at `1..=tab_count`).
+    /// Highlighted row in the Tabs section (0 == "+ New tab", 1 == "+ New
+    /// no-history tab", then the tabs from [`NEW_TAB_ROWS`]).
     pub fn tab_selected(&self) -> usize {
         self.tab_selected
     }
@@ -185,8 +215,9 @@ impl Menu {
     /// Tabs selection in range. Called each frame the menu is shown.
     pub fn set_tab_count(&mut self, count: usize) {
         self.tab_count = count;
-        if self.tab_selected > count {
-            self.tab_selected = count;
+        let last = count + NEW_TAB_ROWS - 1;
+        if self.tab_selected > last {
+            self.tab_selected = last;
         }
     }
 
@@ -205,7 +236,11 @@ impl Menu {
     /// entry instead while it's still in flight).
     pub fn remove_selected(&mut self) {
         match self.section {
-            Section::Bookmarks => self.bookmarks.remove_selected(),
+            Section::Bookmarks => {
+                if let Some(i) = self.bookmarks.selected_index() {
+                    self.remove_bookmark(i);
+                }
+            }
             Section::History => self.history.remove_selected(),
             Section::Downloads => self.downloads.remove_selected(),
             Section::Tabs => {}
@@ -215,13 +250,142 @@ impl Menu {
     /// Remove the entry at `index` in the active section (clicking its remove button).
     pub fn remove_at(&mut self, index: usize) {
         match self.section {
-            Section::Bookmarks => self.bookmarks.remove(index),
+            Section::Bookmarks => self.remove_bookmark(index),
             Section::History => self.history.remove(index),
             Section::Downloads => self.downloads.remove(index),
             Section::Tabs => {}
         }
     }
 
+    /// Remove entry `index` of the open bookmark folder. A page goes at once; a
+    /// folder with anything in it is armed by the first press and removed by
+    /// the second.
+    fn remove_bookmark(&mut self, index: usize) {
+        let full = match self.bookmarks.folder().entry(index) {
+            Some(Entry::Folder(folder)) => !folder.is_empty(),
+            Some(Entry::Page(_)) => false,
+            None => return,
+        };
+        if full && self.delete_armed != Some(index) {
+            self.delete_armed = Some(index);
+            return;
+        }
+        self.delete_armed = None;
+        self.bookmarks.remove(index);
+    }
+
+    /// The bookmark folder whose delete awaits its confirming second press.
+    pub fn delete_armed(&self) -> Option<usize> {
+        self.delete_armed
+    }
+
+    /// Whether the Bookmarks section's "+ New folder" row is highlighted.
+    pub fn new_folder_selected(&self) -> bool {
+        self.section == Section::Bookmarks && self.bookmarks.new_folder_selected()
+    }
+
+    /// The highlighted bookmark folder's entry index, if a folder is highlighted.
+    pub fn selected_folder(&self) -> Option<usize> {
+        match self.bookmarks.selected_entry()? {
+            Entry::Folder(_) => self.bookmarks.selected_index(),
+            Entry::Page(_) => None,
+        }
+    }
+
+    /// Open the bookmark folder at entry `index`.
+    pub fn enter_folder(&mut self, index: usize) {
+        self.delete_armed = None;
+        self.bookmarks.enter(index);
+    }
+
+    /// Go up one bookmark folder; false at the top level (B then closes the menu).
+    pub fn folder_up(&mut self) -> bool {
+        self.delete_armed = None;
+        self.bookmarks.leave()
+    }
+
+    /// Move the highlighted bookmark or folder (with the edit sheet down)
+    /// `delta` places, or queued download past the next queued one (L2 / R2).
+    /// Other sections don't reorder.
+    pub fn move_entry(&mut self, delta: i32) {
+        match self.section {
+            Section::Bookmarks if self.edit.is_none() => {
+                self.delete_armed = None;
+                self.bookmarks.move_selected(delta);
+            }
+            Section::Downloads => self.downloads.move_selected(delta),
+            _ => {}
+        }
+    }
+
+    /// Add a folder to the open one and open the edit sheet on its name.
+    pub fn new_folder(&mut self) {
+        let index = self.bookmarks.create_folder("New folder");
+        self.edit = Some(BookmarkEdit::folder(index, "New folder"));
+    }
+
+    /// Open the edit sheet on the highlighted bookmark or folder (Y).
+    pub fn edit_selected(&mut self) {
+        if let Some(i) = self.bookmarks.selected_index() {
+            self.edit_at(i);
+        }
+    }
+
+    /// Open the edit sheet on entry `index` of the open folder.
+    pub fn edit_at(&mut self, index: usize) {
+        self.delete_armed = None;
+        self.edit = match self.bookmarks.folder().entry(index) {
+            Some(Entry::Folder(folder)) => Some(BookmarkEdit::folder(index, &folder.name)),
+            Some(Entry::Page(page)) => Some(BookmarkEdit::page(
+                index,
+                &page.url,
+                &page.title,
+                &page.tags,
+                self.bookmarks.folder_paths(),
+                self.bookmarks.path(),
+                self.dial.contains(&page.url),
+            )),
+            None => None,
+        };
+    }
+
+    /// The edit sheet, while it's up.
+    pub fn bookmark_edit(&self) -> Option<&BookmarkEdit> {
+        self.edit.as_ref()
+    }
+
+    pub fn bookmark_edit_mut(&mut self) -> Option<&mut BookmarkEdit> {
+        self.edit.as_mut()
+    }
+
+    /// Close the edit sheet, writing its fields back when `save`: a folder's
+    /// name (a blank one is ignored), or a page's title, tags, pin and folder.
+    pub fn edit_close(&mut self, save: bool) {
+        let Some(edit) = self.edit.take() else {
+            return;
+        };
+        if !save {
+            return;
+        }
+        let title = edit.title().trim();
+        match edit.target() {
+            EditTarget::Folder(index) => {
+                if !title.is_empty() {
+                    self.bookmarks.rename_folder(index, title);
+                }
+            }
+            EditTarget::Page(index) => {
+                let tags = bookmarks::parse_tags(edit.tags());
+                self.bookmarks.edit_page(index, title, tags);
+                if edit.pinned() != self.dial.contains(edit.url()) {
+                    self.dial.toggle(edit.url(), title);
+                }
+                // Last: it moves the entry out from under `index`.
+                self.bookmarks.move_page(index, edit.folder_choice().0);
+            }
+        }
+    }
+
     /// Clear the active section's list: all history entries, or all finished
     /// downloads (active ones stay). Goes through [`Self::clear_or_arm`].
     fn clear(&mut self) {
@@ -233,7 +397,7 @@ impl Menu {
     }
 
     /// Record a visited URL (no-op if history is disabled). Only marks the store
-    /// dirty; the disk write is deferred (see [`Self::flush_history`]).
+    /// dirty; the disk write is deferred (see [`Self::flush_stores`]).
     pub fn record_history(&mut self, url: &str) {
         self.history.record(url);
     }
@@ -245,6 +409,25 @@ impl Menu {
 
     /// Add or remove `url` from saved bookmarks (the bookmark button / Start).
     pub fn toggle_bookmark(&mut self, url: &str) {
-        self.bookmarks.toggle(url);
+        let title = self.known_title(url);
+        self.bookmarks.toggle(url, &title);
+    }
+
+    /// The title last seen for `url` — from history, else a bookmark — so a new
+    /// bookmark or pin starts titled; empty when the page never reported one.
+    pub fn known_title(&self, url: &str) -> String {
+        self.history
+            .title_of(url)
+            .or_else(|| self.bookmarks.title_of(url))
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    /// A page reported its title: adopt it wherever `url` is saved (history,
+    /// bookmarks, the dial). Written on the next [`Self::flush_stores`].
+    pub fn set_page_title(&mut self, url: &str, title: &str) {
+        self.history.set_title(url, title);
+        self.bookmarks.set_title(url, title);
+        self.dial.set_title(url, title);
     }
 }
@@ -1,13 +1,19 @@
 //! State machines of the full-screen / modal overlays: the [`menu`], the
-//! on-screen keyboard ([`osk`]), link-hint navigation ([`hints`]), and the
-//! modal page prompts ([`prompt`]). They hold state and input handling only —
-//! the matching egui renderers live in [`crate::ui`]'s submodules, and the
-//! central router ([`crate::app`]) decides which overlay owns the input.
+//! on-screen keyboard ([`osk`]), link-hint navigation ([`hints`]), find-in-page
+//! ([`find`]), and the modal page prompts ([`prompt`]). They hold state and
+//! input handling only — the matching egui renderers live in [`crate::ui`]'s
+//! submodules, and the central router ([`crate::app`]) decides which overlay
+//! owns the input.
 
+pub mod blocked;
+pub mod bookmark_edit;
 pub mod dial_edit;
+pub mod find;
 pub mod hints;
 pub mod home;
 pub mod menu;
 pub mod osk;
 pub mod prompt;
 pub mod settings;
+pub mod site_edit;
+pub mod suggest;
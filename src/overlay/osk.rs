@@ -3,13 +3,15 @@
 //! doubles as search). Owned and rendered by [`crate::ui`], which drives it from
 //! gamepad input. Beyond grid navigation (D-pad + **A**), the common keys have
 //! direct shortcuts: **X** backspace, **Y** space, **L2** shift, **R2** enter.
+//! Over the address bar and the start page's search, matches are suggested
+//! above the keys ([`crate::overlay::suggest`]).
 //!
 //! Layouts are built in ([`LAYOUTS`]: QWERTY and ЙЦУКЕН so far); the config's
 //! `[osk] layouts` list picks which are enabled, and the **Lang** key cycles
 //! through them in that order. Each layout defines only the four character
 //! rows — the frame (Tab, Caps, Enter, Shift, Space, arrows) is fixed.
 
-use crate::app::{AppCommand, MenuAction, PromptAction};
+use crate::app::{AppCommand, FindAction, MenuAction, PromptAction};
 use crate::browser::{AppBrowser, BrowserCommand};
 use crate::config::OskConfig;
 use crate::event::sdl2_servo::{char_keyboard_event, named_keyboard_event};
@@ -32,6 +34,13 @@ pub enum OskTarget<'a> {
     /// A settings-overlay text field (see [`crate::overlay::settings`]); Enter
     /// just hides the keyboard (the value already lives in the draft).
     Settings(&'a mut String),
+    /// The find bar's query (see [`crate::overlay::find`]); Enter searches the
+    /// page for it.
+    Find(&'a mut String),
+    /// A text row of the bookmark edit sheet (see
+    /// [`crate::overlay::bookmark_edit`]); Enter just hides the keyboard, the
+    /// sheet saves on its own Save row.
+    Bookmark(&'a mut String),
     Page,
 }
 
@@ -314,6 +323,18 @@ impl Osk {
         self.caret
     }
 
+    /// Whether the selection is on the top key row — D-pad Up from here leaves
+    /// the keys (for the suggestion list above them, see
+    /// [`crate::overlay::suggest`]).
+    pub fn on_top_row(&self) -> bool {
+        self.row == 0
+    }
+
+    /// Whether **A** would press the Enter key.
+    pub fn on_enter_key(&self) -> bool {
+        self.current() == Enter
+    }
+
     fn current(&self) -> Key {
         self.layout().keys[self.row][self.col]
     }
@@ -373,7 +394,8 @@ impl Osk {
             Down if matches!(target, OskTarget::Page) => {
                 send_named(browser, NamedKey::ArrowDown, Code::ArrowDown)
             }
-            // Settings has no on-screen caret, so its arrows stay inert.
+            // Settings and the find bar have no on-screen caret, so their
+            // arrows stay inert.
             Tab | Left | Right | Up | Down => {}
             Enter => self.enter(target, browser, commands),
             Lang => {
@@ -395,7 +417,9 @@ impl Osk {
             OskTarget::Prompt(buf)
             | OskTarget::Home(buf)
             | OskTarget::DialEdit(buf)
-            | OskTarget::Settings(buf) => buf.clear(),
+            | OskTarget::Settings(buf)
+            | OskTarget::Find(buf)
+            | OskTarget::Bookmark(buf) => buf.clear(),
             OskTarget::Page => browser.clear_focused_field(),
         }
         self.caret = 0;
@@ -416,7 +440,9 @@ impl Osk {
             OskTarget::Prompt(buf)
             | OskTarget::Home(buf)
             | OskTarget::DialEdit(buf)
-            | OskTarget::Settings(buf) => self.caret = remove_before(buf, self.caret),
+            | OskTarget::Settings(buf)
+            | OskTarget::Find(buf)
+            | OskTarget::Bookmark(buf) => self.caret = remove_before(buf, self.caret),
             OskTarget::Page => send_named(browser, NamedKey::Backspace, Code::Backspace),
         }
     }
@@ -431,7 +457,9 @@ impl Osk {
             OskTarget::Prompt(buf)
             | OskTarget::Home(buf)
             | OskTarget::DialEdit(buf)
-            | OskTarget::Settings(buf) => self.caret = insert_at(buf, self.caret, c),
+            | OskTarget::Settings(buf)
+            | OskTarget::Find(buf)
+            | OskTarget::Bookmark(buf) => self.caret = insert_at(buf, self.caret, c),
             OskTarget::Page => {
                 browser.handle_input(servo::InputEvent::Keyboard(char_keyboard_event(
                     c, shift, true,
@@ -464,9 +492,15 @@ impl Osk {
                     commands.push(AppCommand::Menu(MenuAction::DialAdd(text.to_string())));
                 }
             }
-            // A settings text field already holds the typed value in the draft;
-            // Enter just dismisses the keyboard, back to the settings list.
-            OskTarget::Settings(_) => {}
+            // A settings text field already holds the typed value in the draft
+            // (a bookmark sheet's row, in the sheet); Enter just dismisses the
+            // keyboard, back to the list.
+            OskTarget::Settings(_) | OskTarget::Bookmark(_) => {}
+            // Search even for an empty query: that clears the previous marks.
+            OskTarget::Find(buf) => {
+                let query = buf.trim().to_string();
+                commands.push(AppCommand::Find(FindAction::Search(query)));
+            }
             OskTarget::Page => send_named(browser, NamedKey::Enter, Code::Enter),
         }
         self.visible = false;
@@ -489,7 +523,9 @@ fn target_char_len(target: &OskTarget, browser: &AppBrowser) -> usize {
         OskTarget::Prompt(buf)
         | OskTarget::Home(buf)
         | OskTarget::DialEdit(buf)
-        | OskTarget::Settings(buf) => buf.chars().count(),
+        | OskTarget::Settings(buf)
+        | OskTarget::Find(buf)
+        | OskTarget::Bookmark(buf) => buf.chars().count(),
         OskTarget::Page => 0,
     }
 }
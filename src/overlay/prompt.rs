@@ -2,21 +2,44 @@
 //! can open — `<select>` pickers and the JS simple dialogs (`alert`, `confirm`,
 //! `prompt`). Servo hands these to the embedder (see
 //! [`crate::browser::delegate`]); the queue here owns the pending controls and
-//! resolves the front one, which [`crate::ui`] draws as a modal overlay.
+//! resolves the front one, which [`crate::ui`] draws as a modal overlay. The app
+//! queues its own two-button questions here too ([`AppDialog`]), so they share
+//! the modal and its navigation.
 //!
 //! Navigation works over *slots* — the focusable items of the front control:
 //! a select's enabled options (plus a trailing **OK** for multi-selects), or a
 //! dialog's buttons (**OK**, then **Cancel**). The selection is a slot index;
 //! the renderer maps slots back to rows with the same flattening order.
 
+use crate::app::AppCommand;
 use servo::{EmbedderControl, EmbedderControlId, SelectElement, SimpleDialog};
 use std::collections::VecDeque;
 
+/// A question from retsurf itself rather than the page: a message and two
+/// buttons, each answered by running a command.
+pub struct AppDialog {
+    pub message: String,
+    /// Label of the confirm button (slot 0).
+    pub confirm: &'static str,
+    /// Label of the cancel button (slot 1); B / Esc answer the same.
+    pub cancel: &'static str,
+    pub on_confirm: AppCommand,
+    pub on_cancel: Option<AppCommand>,
+    /// Focus the cancel button first, when confirming is the risky answer.
+    pub focus_cancel: bool,
+}
+
+/// A queued prompt: a page's control or an app question.
+enum Entry {
+    Page(EmbedderControl),
+    App(AppDialog),
+}
+
 pub struct Prompt {
-    /// Pending controls, oldest first; the front one is shown. Dropping a
+    /// Pending prompts, oldest first; the front one is shown. Dropping a page
     /// control answers it with its default response (Servo's `Drop` impls),
     /// so anything removed from the queue is automatically resolved.
-    queue: VecDeque<EmbedderControl>,
+    queue: VecDeque<Entry>,
     /// The focused slot of the front control.
     selected: usize,
     /// Option ids chosen in the front select control. Kept here (not pushed
@@ -42,15 +65,36 @@ impl Prompt {
         !self.queue.is_empty()
     }
 
-    /// The shown control, if any.
+    /// The shown page control, if the front prompt is one.
     #[inline]
     pub fn front(&self) -> Option<&EmbedderControl> {
-        self.queue.front()
+        match self.queue.front() {
+            Some(Entry::Page(control)) => Some(control),
+            _ => None,
+        }
+    }
+
+    /// The shown app question, if the front prompt is one.
+    #[inline]
+    pub fn front_app(&self) -> Option<&AppDialog> {
+        match self.queue.front() {
+            Some(Entry::App(dialog)) => Some(dialog),
+            _ => None,
+        }
     }
 
     /// Queue a control from the page; it shows once it reaches the front.
     pub fn push(&mut self, control: EmbedderControl) {
-        self.queue.push_back(control);
+        self.push_entry(Entry::Page(control));
+    }
+
+    /// Queue a question from the app; it shows once it reaches the front.
+    pub fn push_app(&mut self, dialog: AppDialog) {
+        self.push_entry(Entry::App(dialog));
+    }
+
+    fn push_entry(&mut self, entry: Entry) {
+        self.queue.push_back(entry);
         log::debug!("prompt: control queued (len={})", self.queue.len());
         if self.queue.len() == 1 {
             self.reset_for_front();
@@ -62,7 +106,8 @@ impl Prompt {
     pub fn dismiss(&mut self, id: EmbedderControlId) {
         let was_front = self.front().is_some_and(|c| c.id() == id);
         let before = self.queue.len();
-        self.queue.retain(|c| c.id() != id);
+        self.queue
+            .retain(|e| !matches!(e, Entry::Page(c) if c.id() == id));
         if self.queue.len() != before {
             log::debug!(
                 "prompt: control dismissed by servo (len={})",
@@ -120,11 +165,12 @@ impl Prompt {
 
     /// Activate the focused slot: choose / toggle a select option (a toggle
     /// keeps the picker open), or press the focused dialog button. Resolving
-    /// the control pops it; the next queued one (if any) takes over.
-    pub fn activate(&mut self) {
+    /// the control pops it; the next queued one (if any) takes over. An app
+    /// question hands back the command its button runs.
+    pub fn activate(&mut self) -> Option<AppCommand> {
         let selected = self.selected;
         match self.queue.front_mut() {
-            Some(EmbedderControl::SelectElement(select)) => {
+            Some(Entry::Page(EmbedderControl::SelectElement(select))) => {
                 let ids = slot_ids(select);
                 let multiple = select.allow_select_multiple();
                 if let Some(&id) = ids.get(selected) {
@@ -135,22 +181,26 @@ impl Prompt {
                             Some(i) => _ = self.chosen.remove(i),
                             None => self.chosen.push(id),
                         }
-                        return;
+                        return None;
                     }
                     self.chosen = vec![id];
                 }
                 // A chosen single option, or multi's OK slot (also the only
                 // slot when nothing is selectable): submit what's chosen.
-                let Some(EmbedderControl::SelectElement(mut select)) = self.queue.pop_front()
+                let Some(Entry::Page(EmbedderControl::SelectElement(mut select))) =
+                    self.queue.pop_front()
                 else {
                     unreachable!("front was a select element");
                 };
                 select.select(std::mem::take(&mut self.chosen));
                 select.submit();
                 self.reset_for_front();
+                None
             }
-            Some(EmbedderControl::SimpleDialog(_)) => {
-                let Some(EmbedderControl::SimpleDialog(dialog)) = self.queue.pop_front() else {
+            Some(Entry::Page(EmbedderControl::SimpleDialog(_))) => {
+                let Some(Entry::Page(EmbedderControl::SimpleDialog(dialog))) =
+                    self.queue.pop_front()
+                else {
                     unreachable!("front was a simple dialog");
                 };
                 // Slot 0 is OK, slot 1 Cancel (alerts only have OK).
@@ -165,28 +215,44 @@ impl Prompt {
                     SimpleDialog::Prompt(d) => d.dismiss(),
                 }
                 self.reset_for_front();
+                None
             }
-            _ => {}
+            Some(Entry::App(_)) => {
+                let Some(Entry::App(dialog)) = self.queue.pop_front() else {
+                    unreachable!("front was an app dialog");
+                };
+                self.reset_for_front();
+                if selected == 0 {
+                    Some(dialog.on_confirm)
+                } else {
+                    dialog.on_cancel
+                }
+            }
+            _ => None,
         }
     }
 
     /// Dismiss the front control with its default response (**B** / Esc / ✖):
     /// dropping it answers it — a select keeps its original selection, a
-    /// dialog cancels (alerts just confirm; that's their only answer).
-    pub fn cancel(&mut self) {
-        if self.queue.pop_front().is_some() {
-            self.reset_for_front();
+    /// dialog cancels (alerts just confirm; that's their only answer). An app
+    /// question hands back its cancel command.
+    pub fn cancel(&mut self) -> Option<AppCommand> {
+        let entry = self.queue.pop_front()?;
+        self.reset_for_front();
+        match entry {
+            Entry::App(dialog) => dialog.on_cancel,
+            Entry::Page(_) => None,
         }
     }
 
     /// How many focusable slots the front control has.
     fn slot_count(&self) -> usize {
-        match self.front() {
-            Some(EmbedderControl::SelectElement(select)) => {
+        match self.queue.front() {
+            Some(Entry::Page(EmbedderControl::SelectElement(select))) => {
                 slot_ids(select).len() + select.allow_select_multiple() as usize
             }
-            Some(EmbedderControl::SimpleDialog(SimpleDialog::Alert(_))) => 1,
-            Some(EmbedderControl::SimpleDialog(_)) => 2,
+            Some(Entry::Page(EmbedderControl::SimpleDialog(SimpleDialog::Alert(_)))) => 1,
+            Some(Entry::Page(EmbedderControl::SimpleDialog(_))) | Some(Entry::App(_)) => 2,
             _ => 0,
         }
     }
@@ -200,7 +266,7 @@ impl Prompt {
         // Direct field access (not `self.front()`) so the borrow stays on
         // `queue` and the sibling fields can be assigned.
         match self.queue.front() {
-            Some(EmbedderControl::SelectElement(select)) => {
+            Some(Entry::Page(EmbedderControl::SelectElement(select))) => {
                 self.chosen = select.selected_options();
                 let ids = slot_ids(select);
                 self.selected = ids
@@ -208,9 +274,10 @@ impl Prompt {
                     .position(|id| self.chosen.contains(id))
                     .unwrap_or(0);
             }
-            Some(EmbedderControl::SimpleDialog(SimpleDialog::Prompt(p))) => {
+            Some(Entry::Page(EmbedderControl::SimpleDialog(SimpleDialog::Prompt(p)))) => {
                 self.input = p.current_value().to_string();
             }
+            Some(Entry::App(dialog)) if dialog.focus_cancel => self.selected = 1,
             _ => {}
         }
     }
@@ -5,9 +5,10 @@
 //! not here — it's dynamic (see [`super::CtrlRow`]).
 
 use super::SettingsSection;
+use crate::app::SettingsAction;
 use crate::config::{
-    bounds, AppConfig, Channel, CursorMode, ExperimentalPreset, MemoryProfile, PageTheme,
-    ToolbarPosition,
+    bounds, AppConfig, Channel, CursorMode, ExperimentalPreset, ExtractMode, MemoryProfile,
+    PageTheme, ToolbarPosition,
 };
 
 /// How a field is displayed, edited, and reached in a config. `Choice` carries
@@ -31,6 +32,15 @@ pub enum Kind {
         get: fn(&AppConfig) -> String,
         set: fn(&mut AppConfig, &str),
     },
+    /// A choice among names the config itself lists (the `[[search_engines]]`),
+    /// so its options can't be static. The empty value leads the cycle,
+    /// labeled `none`.
+    Named {
+        names: fn(&AppConfig) -> Vec<String>,
+        none: &'static str,
+        get: fn(&AppConfig) -> String,
+        set: fn(&mut AppConfig, &str),
+    },
     Int {
         min: i64,
         max: i64,
@@ -48,6 +58,9 @@ pub enum Kind {
         get: fn(&AppConfig) -> f64,
         set: fn(&mut AppConfig, f64),
     },
+    /// A button, not a setting: A (or a click) pushes the action, which acts
+    /// right away instead of editing the draft.
+    Action(SettingsAction),
 }
 
 /// `Kind::Bool` over a config path.
@@ -165,6 +178,17 @@ const fn web_features_kind() -> Kind {
     }
 }
 
+/// The default search engine: one of the `[[search_engines]]` by name, or none
+/// (searching the "Search URL" row's page).
+const fn engine_kind() -> Kind {
+    Kind::Named {
+        names: |c| c.search_engines.0.iter().map(|e| e.name.clone()).collect(),
+        none: "Search URL",
+        get: |c| c.browser.search_engine.clone(),
+        set: |c, v| c.browser.search_engine = v.to_string(),
+    }
+}
+
 /// Compact constructor for the [`FIELDS`] table — without it `rustfmt` explodes
 /// each `Field` literal across six lines and drowns the table.
 const fn f(
@@ -192,11 +216,15 @@ use SettingsSection as S;
 #[rustfmt::skip]
 pub(super) static FIELDS: &[Field] = &[
     f(S::Browser,  "Browser",     "Home page",              text!(browser.home_page), false),
+    f(S::Browser,  "Browser",     "Search engine",          engine_kind(), false),
     f(S::Browser,  "Browser",     "Search URL",             text!(browser.search_page), false),
     f(S::Browser,  "Browser",     "User agent",             ua_kind(), true),
     f(S::Browser,  "Browser",     "Page zoom",              float!(browser.page_zoom as f32, bounds::PAGE_ZOOM, 0.05, 2), false),
     f(S::Browser,  "Browser",     "Page theme",             choice!(browser.page_theme: PageTheme), false),
     f(S::Browser,  "Browser",     "Keep site data",         flag!(browser.persist_site_data), true),
+    f(S::Browser,  "Browser",     "Restore tabs on start",  flag!(browser.restore_session), false),
+    f(S::Browser,  "Browser",     "HTTPS only",             flag!(browser.https_only), false),
+    f(S::Browser,  "Browser",     "Strip tracking params",  flag!(url_cleaning.enabled), false),
 
     f(S::Browser,  "Experimental", "Web features",          web_features_kind(), false),
     f(S::Browser,  "Experimental", "WebGL 2",               flag!(experimental.webgl2), false),
@@ -232,7 +260,11 @@ pub(super) static FIELDS: &[Field] = &[
 
     f(S::Content,  "History",     "Record history",         flag!(history.enabled), false),
     f(S::Content,  "History",     "Max entries",            int!(history.max_entries as usize, bounds::HISTORY_MAX, 5), false),
+    f(S::Content,  "Bookmarks",   "Import bookmarks.html",  Kind::Action(SettingsAction::ImportBookmarks), false),
+    f(S::Content,  "Bookmarks",   "Export bookmarks.html",  Kind::Action(SettingsAction::ExportBookmarks), false),
     f(S::Content,  "Ad blocker",  "Enabled",                flag!(adblock.enabled), true),
+    f(S::Content,  "Ad blocker",  "Hide page elements",     flag!(adblock.cosmetic), true),
+    f(S::Content,  "Ad blocker",  "Built-in list offline",  flag!(adblock.bundled), true),
     f(S::Content,  "Ad blocker",  "Update every (days)",    int!(adblock.update_days as u64, bounds::ADBLOCK_UPDATE_DAYS, 1), false),
 
     f(S::Content, "Data saving", "Block images",         flag!(data_saving.block_images), false),
@@ -249,6 +281,12 @@ pub(super) static FIELDS: &[Field] = &[
     f(S::Advanced, "Performance", "Worker pool max (0=auto)", int!(performance.worker_pool_max as u32, bounds::WORKER_POOL_MAX, 1), true),
     f(S::Advanced, "Performance", "HTTP disk cache (MB)",    int!(performance.http_disk_cache_mb as u32, bounds::HTTP_DISK_CACHE_MB, 8, Some("Off")), true),
     f(S::Advanced, "Downloads",   "Save folder",            text!(downloads.dir), true),
+    f(S::Advanced, "Downloads",   "Parallel downloads",     int!(downloads.max_concurrent as usize, bounds::DOWNLOADS_CONCURRENT, 1, Some("Unlimited")), false),
+    f(S::Advanced, "Downloads",   "Unpack zips",            choice!(downloads.extract: ExtractMode), false),
+    f(S::Advanced, "Downloads",   "Delete zip after unpack", flag!(downloads.delete_archive), false),
+    f(S::Advanced, "Downloads",   "Look up checksums",      flag!(downloads.checksum_lookup), false),
+    f(S::Advanced, "Network",     "Proxy",                  text!(network.proxy), true),
+    f(S::Advanced, "Network",     "DNS over HTTPS",         text!(network.doh_url), true),
     f(S::Advanced, "Updates",     "Update channel",         choice!(update.channel: Channel), false),
     f(S::Advanced, "Updates",     "Auto-check on startup",  flag!(update.auto_check), false),
     f(S::Advanced, "Diagnostics", "Memory overlay",         flag!(debug.memory_overlay), false),
@@ -282,6 +320,14 @@ mod tests {
                         assert_eq!(get(&c), *token, "{}", field.label);
                     }
                 }
+                Kind::Named {
+                    names, get, set, ..
+                } => {
+                    for name in names(&c) {
+                        set(&mut c, &name);
+                        assert_eq!(get(&c), name, "{}", field.label);
+                    }
+                }
                 Kind::Int {
                     min, max, get, set, ..
                 } => {
@@ -299,6 +345,7 @@ mod tests {
                         assert!((got - v).abs() < 1e-3, "{}: {got} != {v}", field.label);
                     }
                 }
+                Kind::Action(_) => {}
             }
         }
     }
@@ -24,6 +24,7 @@ pub use about::about_info;
 pub use controls::RESET_ROWS;
 pub use fields::{Field, Kind};
 
+use crate::app::SettingsAction;
 use crate::config::AppConfig;
 use crate::event::bindings::{self, Action, GROUPS, SURFACES};
 use inputbind::editor::Controls;
@@ -106,6 +107,9 @@ pub struct Settings {
     controls: Controls<Action>,
     /// Why the last binding edit was refused; cleared by the next one.
     controls_note: Option<String>,
+    /// What the last action row did (`Ok`) or why it failed (`Err`), shown under
+    /// the hint until the section changes.
+    action_note: Option<Result<String, String>>,
 }
 
 impl Settings {
@@ -119,6 +123,7 @@ impl Settings {
             bindings_orig: Store::default(),
             controls: Controls::new(GROUPS, SURFACES, RESET_ROWS),
             controls_note: None,
+            action_note: None,
         }
     }
 
@@ -142,6 +147,7 @@ impl Settings {
         self.show_controls();
         self.section = SettingsSection::Browser;
         self.selected = 0;
+        self.action_note = None;
         self.visible = true;
     }
 
@@ -230,6 +236,7 @@ impl Settings {
     /// Jump straight to a section (clicking its tab), focusing its first row.
     pub fn set_section(&mut self, section: SettingsSection) {
         self.section = section;
+        self.action_note = None;
         if section == SettingsSection::Controls {
             self.focus_first_control();
             return;
@@ -294,6 +301,26 @@ impl Settings {
         )
     }
 
+    /// The focused row's action, if it's an action row (A pushes it).
+    pub fn selected_action(&self) -> Option<SettingsAction> {
+        if !self.is_field_section() {
+            return None;
+        }
+        match &fields::FIELDS[self.selected].kind {
+            Kind::Action(action) => Some(action.clone()),
+            _ => None,
+        }
+    }
+
+    /// Report the outcome of an action row.
+    pub fn set_action_note(&mut self, note: Result<String, String>) {
+        self.action_note = Some(note);
+    }
+
+    pub fn action_note(&self) -> Option<&Result<String, String>> {
+        self.action_note.as_ref()
+    }
+
     /// The OSK's edit buffer for the focused row — the draft's own `String` for a
     /// `Text` field, so typing lands straight in the draft. `None` otherwise.
     pub fn selected_text_mut(&mut self) -> Option<&mut String> {
@@ -314,7 +341,7 @@ impl Settings {
             return;
         }
         match &fields::FIELDS[self.selected].kind {
-            Kind::Text { .. } => {}
+            Kind::Text { .. } | Kind::Action(_) => {}
             Kind::Bool { get, set } => {
                 let v = !get(&self.draft);
                 set(&mut self.draft, v);
@@ -326,6 +353,20 @@ impl Settings {
                 let next = (idx + dx).rem_euclid(n) as usize;
                 set(&mut self.draft, opts[next].1);
             }
+            Kind::Named {
+                names, get, set, ..
+            } => {
+                let mut opts = vec![String::new()];
+                opts.extend(names(&self.draft));
+                let cur = get(&self.draft);
+                let n = opts.len() as i32;
+                let idx = opts
+                    .iter()
+                    .position(|v| v.eq_ignore_ascii_case(&cur))
+                    .unwrap_or(0) as i32;
+                let next = (idx + dx).rem_euclid(n) as usize;
+                set(&mut self.draft, &opts[next]);
+            }
             Kind::Int {
                 min,
                 max,
@@ -370,6 +411,14 @@ impl Settings {
                     .map(|(label, _)| label.to_string())
                     .unwrap_or(cur)
             }
+            Kind::Named { none, get, .. } => {
+                let cur = get(&self.draft);
+                if cur.is_empty() {
+                    none.to_string()
+                } else {
+                    cur
+                }
+            }
             Kind::Int { zero, get, .. } => {
                 let v = get(&self.draft);
                 match zero {
@@ -380,6 +429,7 @@ impl Settings {
             Kind::Float { decimals, get, .. } => {
                 format!("{:.*}", decimals, get(&self.draft))
             }
+            Kind::Action(_) => String::new(),
         }
     }
 }
@@ -0,0 +1,227 @@
+//! The site settings sheet, opened from the toolbar's sliders button: the
+//! active tab's site overrides (see [`crate::data::site_settings`]) as a short
+//! list of rows, each stepping through its choices with Left/Right or A. State
+//! here is the host, the edited copy, the ad-block toggle (an allowlist rule,
+//! stored apart from the overrides) and the focused row; closing hands them
+//! back for the browser to store and apply (see
+//! [`crate::browser::AppBrowser::set_site_settings`]). [`crate::ui`]'s
+//! `site_edit` renderer draws it.
+
+use crate::browser::ZOOM_LADDER;
+use crate::data::site_settings::Site;
+
+/// A row of the sheet.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Zoom,
+    ForcedDark,
+    Images,
+    Media,
+    Fonts,
+    /// Whether the ad blocker runs on the site (off allowlists it).
+    Adblock,
+    /// Drop every override (A).
+    Reset,
+}
+
+pub const FIELDS: [Field; 7] = [
+    Field::Zoom,
+    Field::ForcedDark,
+    Field::Images,
+    Field::Media,
+    Field::Fonts,
+    Field::Adblock,
+    Field::Reset,
+];
+
+pub struct SiteEdit {
+    visible: bool,
+    /// The key the entry is stored under (the host, or the parent domain whose
+    /// entry covers it).
+    host: String,
+    site: Site,
+    /// The ad blocker runs on the site (it isn't allowlisted).
+    blocking: bool,
+    selected: usize,
+}
+
+impl SiteEdit {
+    pub fn new() -> Self {
+        Self {
+            visible: false,
+            host: String::new(),
+            site: Site::default(),
+            blocking: true,
+            selected: 0,
+        }
+    }
+
+    #[inline]
+    pub fn visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Open on `host`'s overrides and ad-block state, focusing the first row.
+    pub fn open(&mut self, host: String, site: Site, blocking: bool) {
+        self.host = host;
+        self.site = site;
+        self.blocking = blocking;
+        self.selected = 0;
+        self.visible = true;
+    }
+
+    /// Close, handing back the host, its edited overrides and the ad-block
+    /// state to store.
+    pub fn close(&mut self) -> Option<(String, Site, bool)> {
+        if !self.visible {
+            return None;
+        }
+        self.visible = false;
+        Some((
+            std::mem::take(&mut self.host),
+            std::mem::take(&mut self.site),
+            self.blocking,
+        ))
+    }
+
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn field(&self) -> Field {
+        FIELDS[self.selected]
+    }
+
+    /// Focus row `index` (clicking it).
+    pub fn set_selected(&mut self, index: usize) {
+        self.selected = index.min(FIELDS.len() - 1);
+    }
+
+    pub fn move_sel(&mut self, dy: i32) {
+        let last = FIELDS.len() as i32 - 1;
+        self.selected = (self.selected as i32 + dy).clamp(0, last) as usize;
+    }
+
+    /// A on the focused row: reset on the Reset row, else step forward.
+    pub fn activate(&mut self) {
+        match self.field() {
+            Field::Reset => {
+                self.site = Site::default();
+                self.blocking = true;
+            }
+            _ => self.adjust(1),
+        }
+    }
+
+    /// Step the focused row's value by `dx`, wrapping through its choices.
+    pub fn adjust(&mut self, dx: i32) {
+        let field = self.field();
+        let site = &mut self.site;
+        match field {
+            Field::Zoom => {
+                let choices: Vec<Option<f32>> = std::iter::once(None)
+                    .chain(ZOOM_LADDER.iter().copied().map(Some))
+                    .collect();
+                let at = site.zoom.map_or(0, |zoom| {
+                    // An off-ladder zoom (from the config default) lands on the
+                    // nearest step.
+                    let nearest = ZOOM_LADDER
+                        .iter()
+                        .position(|z| *z >= zoom - 0.005)
+                        .unwrap_or(ZOOM_LADDER.len() - 1);
+                    nearest + 1
+                });
+                site.zoom = choices[step(at, dx, choices.len())];
+            }
+            Field::ForcedDark => site.forced_dark = step_choice(site.forced_dark, dx),
+            Field::Images => site.block_images = step_choice(site.block_images, dx),
+            Field::Media => site.block_media = step_choice(site.block_media, dx),
+            Field::Fonts => site.block_fonts = step_choice(site.block_fonts, dx),
+            Field::Adblock => self.blocking = !self.blocking,
+            Field::Reset => {}
+        }
+    }
+
+    /// The row's label, as the sheet shows it.
+    pub fn label(field: Field) -> &'static str {
+        match field {
+            Field::Zoom => "Zoom",
+            Field::ForcedDark => "Forced dark",
+            Field::Images => "Images",
+            Field::Media => "Audio & video",
+            Field::Fonts => "Web fonts",
+            Field::Adblock => "Ad blocking",
+            Field::Reset => "Reset this site",
+        }
+    }
+
+    /// The row's current value; "Default" where the global config decides.
+    pub fn value(&self, field: Field) -> String {
+        let site = &self.site;
+        let blocks = |v: Option<bool>| match v {
+            None => "Default",
+            Some(true) => "Block",
+            Some(false) => "Allow",
+        };
+        match field {
+            Field::Zoom => site.zoom.map_or("Default".to_string(), |z| {
+                format!("{}%", (z * 100.0).round())
+            }),
+            Field::ForcedDark => match site.forced_dark {
+                None => "Default",
+                Some(true) => "On",
+                Some(false) => "Off",
+            }
+            .to_string(),
+            Field::Images => blocks(site.block_images).to_string(),
+            Field::Media => blocks(site.block_media).to_string(),
+            Field::Fonts => blocks(site.block_fonts).to_string(),
+            Field::Adblock => if self.blocking { "On" } else { "Off" }.to_string(),
+            Field::Reset => String::new(),
+        }
+    }
+}
+
+/// Index `at` stepped by `dx` through `len` choices, wrapping.
+fn step(at: usize, dx: i32, len: usize) -> usize {
+    (at as i32 + dx).rem_euclid(len as i32) as usize
+}
+
+/// Step a follow / on (block) / off (allow) choice by `dx`, wrapping.
+fn step_choice(value: Option<bool>, dx: i32) -> Option<bool> {
+    const CHOICES: [Option<bool>; 3] = [None, Some(true), Some(false)];
+    let at = CHOICES.iter().position(|c| *c == value).unwrap_or(0);
+    CHOICES[step(at, dx, CHOICES.len())]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The zoom row steps from "Default" onto the ladder and wraps back; a
+    /// three-way row cycles default → on → off.
+    #[test]
+    fn rows_cycle_through_their_choices() {
+        let mut edit = SiteEdit::new();
+        edit.open("example.com".to_string(), Site::default(), false);
+        edit.adjust(1);
+        assert_eq!(edit.value(Field::Zoom), "50%");
+        edit.adjust(-2);
+        assert_eq!(edit.value(Field::Zoom), "300%");
+        edit.move_sel(1);
+        edit.activate();
+        edit.activate();
+        assert_eq!(edit.value(Field::ForcedDark), "Off");
+        assert_eq!(edit.value(Field::Images), "Default");
+        edit.set_selected(FIELDS.len() - 1);
+        edit.activate();
+        let (host, site, blocking) = edit.close().unwrap();
+        assert_eq!(host, "example.com");
+        assert!(site.is_empty() && blocking);
+        assert!(edit.close().is_none());
+    }
+}
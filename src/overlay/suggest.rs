@@ -0,0 +1,451 @@
+//! Autocomplete for the address bar and the start page's search field. While
+//! the on-screen keyboard types into either, the text is matched against open
+//! tabs, bookmarks, speed-dial pins and history, ranked by frecency (how often
+//! and how recently a page was visited, plus a bonus for pages the user saved or
+//! has open), and the best few are listed just above the keys. The best match
+//! whose address starts with the text also completes it inline; Enter / R2
+//! accepts that, Backspace drops it.
+//!
+//! The list is drawn bottom-up, best match nearest the keys: D-pad Up off the
+//! keyboard's top row lands on it, Up steps to the next, Down walks back to the
+//! keys. State here is the ranked list, its highlight and the completion;
+//! [`crate::ui`] refreshes it as the text changes, routes the keys to it and
+//! renders it.
+
+use crate::browser::TabInfo;
+use crate::data::bookmarks::Bookmark;
+use crate::data::dial::SETTINGS_PIN;
+use crate::data::{history, Page};
+
+/// Rows listed at most — what fits between a top toolbar and the keyboard on a
+/// 480px screen.
+pub const MAX_ROWS: usize = 5;
+
+/// Score bonuses on top of a page's history frecency, worth about one visit
+/// this week for a bookmark.
+const BOOKMARK_BONUS: u64 = 100;
+const DIAL_BONUS: u64 = 80;
+const TAB_BONUS: u64 = 50;
+
+/// Where a suggestion came from, strongest first: a page found in several
+/// places shows as the first of them.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Source {
+    /// An open tab, by index — accepting it switches there instead of loading.
+    Tab(usize),
+    Bookmark,
+    Dial,
+    History,
+    /// A search the default engine suggested (see [`Suggest::add_searches`]);
+    /// the row's address is its results page.
+    Search,
+}
+
+pub struct Suggestion {
+    pub url: String,
+    pub title: String,
+    pub source: Source,
+    score: u64,
+}
+
+/// What the ranking draws from, borrowed for one refresh.
+pub struct Sources<'a> {
+    pub history: &'a [history::Entry],
+    pub bookmarks: Vec<&'a Bookmark>,
+    pub dial: &'a [Page],
+    pub tabs: &'a [TabInfo],
+}
+
+pub struct Suggest {
+    items: Vec<Suggestion>,
+    /// The highlighted row while the D-pad is in the list (`None`: on the keys).
+    selected: Option<usize>,
+    /// The text the list was last ranked for.
+    query: String,
+    /// The field's text when the keyboard opened (`None`: closed). Nothing is
+    /// suggested for it — it's the current page's address, not something typed.
+    baseline: Option<String>,
+    /// What the best match adds to the typed text (empty: nothing).
+    completion: String,
+}
+
+impl Suggest {
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            selected: None,
+            query: String::new(),
+            baseline: None,
+            completion: String::new(),
+        }
+    }
+
+    /// Start suggesting for a field that holds `text` (the keyboard opened on it).
+    pub fn open(&mut self, text: &str) {
+        self.close();
+        self.baseline = Some(text.to_string());
+        self.query = text.to_string();
+    }
+
+    pub fn close(&mut self) {
+        self.items.clear();
+        self.selected = None;
+        self.query.clear();
+        self.baseline = None;
+        self.completion.clear();
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.baseline.is_some()
+    }
+
+    /// Whether the field's `text` differs from what the list was ranked for.
+    pub fn stale(&self, text: &str) -> bool {
+        self.is_open() && self.query != text
+    }
+
+    /// Re-rank for the field's new `text`. Inline completion is only offered
+    /// while typing forward — after a deletion it would put back what was just
+    /// deleted.
+    pub fn refresh(&mut self, text: &str, sources: &Sources, now: u64) {
+        let deleted = self.query.starts_with(text);
+        self.query = text.to_string();
+        self.selected = None;
+        self.completion.clear();
+        if text.trim().is_empty() || self.baseline.as_deref() == Some(text) {
+            self.items.clear();
+            return;
+        }
+        self.items = rank(text, sources, now);
+        if !deleted {
+            self.completion = complete(text, &self.items);
+        }
+    }
+
+    pub fn items(&self) -> &[Suggestion] {
+        &self.items
+    }
+
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    pub fn selected_item(&self) -> Option<&Suggestion> {
+        self.selected.and_then(|i| self.items.get(i))
+    }
+
+    pub fn completion(&self) -> &str {
+        &self.completion
+    }
+
+    /// Step into the list from the keyboard's top row, onto the best match.
+    /// Returns `false` (staying on the keys) when there's nothing listed.
+    pub fn enter_list(&mut self) -> bool {
+        if self.items.is_empty() {
+            return false;
+        }
+        self.selected = Some(0);
+        true
+    }
+
+    /// Move the highlight by `dy` on screen: Up (`-1`) to the next match, Down
+    /// towards the keys. Returns `false` once Down leaves the list.
+    pub fn move_sel(&mut self, dy: i32) -> bool {
+        let Some(i) = self.selected else {
+            return false;
+        };
+        if dy > 0 && i == 0 {
+            self.selected = None;
+            return false;
+        }
+        let last = self.items.len().saturating_sub(1) as i32;
+        self.selected = Some((i as i32 - dy).clamp(0, last) as usize);
+        true
+    }
+
+    /// Add the search engine's `searches` for `query` below the local matches,
+    /// up to [`MAX_ROWS`] — if `query` is still the text being typed (the answer
+    /// comes back over the network). `results` is a search's results page.
+    pub fn add_searches(
+        &mut self,
+        query: &str,
+        searches: Vec<String>,
+        results: impl Fn(&str) -> Option<String>,
+    ) {
+        if !self.is_open() || self.query != query {
+            return;
+        }
+        for search in searches {
+            if self.items.len() >= MAX_ROWS {
+                break;
+            }
+            // The text itself is what Enter searches anyway.
+            if search.eq_ignore_ascii_case(query.trim()) {
+                continue;
+            }
+            let Some(url) = results(&search) else {
+                continue;
+            };
+            if self.items.iter().all(|s| s.url != url) {
+                self.items.push(Suggestion {
+                    url,
+                    title: search,
+                    source: Source::Search,
+                    score: 0,
+                });
+            }
+        }
+    }
+
+    /// Back to the keys (the text changed, or a key was pressed).
+    pub fn leave_list(&mut self) {
+        self.selected = None;
+    }
+}
+
+/// Candidates matching every word of `query` (in the title or the address),
+/// best first, at most [`MAX_ROWS`]. Pages whose address starts with the query
+/// rank three times higher — they're what's being typed.
+fn rank(query: &str, sources: &Sources, now: u64) -> Vec<Suggestion> {
+    let mut found: Vec<Suggestion> = Vec::new();
+    // The active tab is the page being typed over.
+    for (i, tab) in sources.tabs.iter().enumerate() {
+        if !tab.active && !tab.url.is_empty() {
+            add(&mut found, &tab.url, &tab.title, Source::Tab(i), TAB_BONUS);
+        }
+    }
+    for page in &sources.bookmarks {
+        add(
+            &mut found,
+            &page.url,
+            &page.title,
+            Source::Bookmark,
+            BOOKMARK_BONUS,
+        );
+    }
+    for pin in sources.dial.iter().filter(|p| p.url != SETTINGS_PIN) {
+        add(&mut found, &pin.url, &pin.title, Source::Dial, DIAL_BONUS);
+    }
+    for entry in sources.history {
+        let score = frecency(entry, now);
+        add(&mut found, &entry.url, &entry.title, Source::History, score);
+    }
+
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    let typed = query.trim().to_ascii_lowercase();
+    found.retain(|s| {
+        let url = s.url.to_lowercase();
+        let title = s.title.to_lowercase();
+        words.iter().all(|w| url.contains(w) || title.contains(w))
+    });
+    for s in &mut found {
+        if forms(&s.url).any(|f| f.to_ascii_lowercase().starts_with(&typed)) {
+            s.score *= 3;
+        }
+    }
+    // Stable: ties keep source order (tabs, bookmarks, dial, then history).
+    found.sort_by_key(|s| std::cmp::Reverse(s.score));
+    found.truncate(MAX_ROWS);
+    found
+}
+
+/// Count `url` as a candidate from `source`: a page already found adds to its
+/// score (and lends a title if it had none).
+fn add(found: &mut Vec<Suggestion>, url: &str, title: &str, source: Source, score: u64) {
+    match found.iter_mut().find(|s| s.url == url) {
+        Some(seen) => {
+            seen.score += score;
+            if seen.title.is_empty() {
+                seen.title = title.to_string();
+            }
+        }
+        None => found.push(Suggestion {
+            url: url.to_string(),
+            title: title.to_string(),
+            source,
+            score,
+        }),
+    }
+}
+
+/// A history entry's weight: its visits, each worth less the longer ago the
+/// last one was (an unknown time counts as old).
+fn frecency(entry: &history::Entry, now: u64) -> u64 {
+    let days = if entry.time == 0 {
+        u64::MAX
+    } else {
+        now.saturating_sub(entry.time) / 86_400
+    };
+    let recency = match days {
+        0..=3 => 100,
+        4..=13 => 70,
+        14..=30 => 50,
+        31..=89 => 30,
+        _ => 10,
+    };
+    u64::from(entry.visits.max(1)) * recency
+}
+
+/// `url` as someone might start typing it: whole, without the scheme, and
+/// without a leading `www.` too.
+fn forms(url: &str) -> impl Iterator<Item = &str> {
+    let bare = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let short = bare.strip_prefix("www.").unwrap_or(bare);
+    [url, bare, short].into_iter()
+}
+
+/// The inline completion of `text`: the rest of the best match's host (and its
+/// `/`) while the host is still being typed, the rest of its address after.
+fn complete(text: &str, items: &[Suggestion]) -> String {
+    if text.is_empty() || text.contains(char::is_whitespace) {
+        return String::new();
+    }
+    let typed = text.to_ascii_lowercase();
+    for item in items {
+        for form in forms(&item.url) {
+            if form.len() <= typed.len()
+                || !form.is_char_boundary(typed.len())
+                || !form.to_ascii_lowercase().starts_with(&typed)
+            {
+                continue;
+            }
+            let host_start = form.find("://").map_or(0, |i| i + 3);
+            let host_end = form[host_start..]
+                .find('/')
+                .map_or(form.len(), |i| host_start + i);
+            let end = if typed.len() < host_end {
+                (host_end + 1).min(form.len())
+            } else {
+                form.len()
+            };
+            return form[typed.len()..end].to_string();
+        }
+    }
+    String::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn visit(url: &str, title: &str, time: u64, visits: u32) -> history::Entry {
+        history::Entry {
+            url: url.to_string(),
+            title: title.to_string(),
+            time,
+            visits,
+        }
+    }
+
+    const NOW: u64 = 1_700_000_000;
+    const DAY: u64 = 86_400;
+
+    /// A page whose address starts with the text beats ones that only mention
+    /// it, and a saved page's bonus lifts an old visit over a recent one.
+    #[test]
+    fn ranks_by_frecency() {
+        let history = [
+            visit("https://old.test/rust", "Rust", NOW - 200 * DAY, 1),
+            visit("https://docs.rs/", "Docs", NOW - DAY, 5),
+            visit("https://news.test/", "Rust news", NOW - DAY, 1),
+            visit("https://www.rust-lang.org/", "Rust", NOW - 2 * DAY, 1),
+        ];
+        let saved = Bookmark {
+            url: "https://old.test/rust".to_string(),
+            title: "Rust".to_string(),
+            tags: Vec::new(),
+            added: 0,
+        };
+        let sources = Sources {
+            history: &history,
+            bookmarks: vec![&saved],
+            dial: &[],
+            tabs: &[],
+        };
+        let ranked = rank("rust", &sources, NOW);
+        let urls: Vec<&str> = ranked.iter().map(|s| s.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            [
+                "https://www.rust-lang.org/",
+                "https://old.test/rust",
+                "https://news.test/"
+            ]
+        );
+        assert_eq!(ranked[1].source, Source::Bookmark);
+    }
+
+    /// The host completes first (with its slash), then the rest of the address;
+    /// a deletion or a space stops completing.
+    #[test]
+    fn completes_host_then_path() {
+        let history = [visit("https://www.github.com/mxmgorin/retsurf", "", NOW, 3)];
+        let sources = Sources {
+            history: &history,
+            bookmarks: Vec::new(),
+            dial: &[],
+            tabs: &[],
+        };
+        let mut suggest = Suggest::new();
+        suggest.open("https://example.com/");
+        suggest.refresh("", &sources, NOW);
+        suggest.refresh("gi", &sources, NOW);
+        assert_eq!(suggest.completion(), "thub.com/");
+        suggest.refresh("github.com/m", &sources, NOW);
+        assert_eq!(suggest.completion(), "xmgorin/retsurf");
+        suggest.refresh("github.com/", &sources, NOW);
+        assert_eq!(suggest.completion(), "");
+        suggest.refresh("github x", &sources, NOW);
+        assert_eq!(suggest.completion(), "");
+    }
+
+    /// The list is entered at its best match and walked upwards; Down off it
+    /// hands the D-pad back to the keys.
+    #[test]
+    fn list_navigation() {
+        let history = [
+            visit("https://a.test/", "", NOW, 3),
+            visit("https://ab.test/", "", NOW, 2),
+        ];
+        let sources = Sources {
+            history: &history,
+            bookmarks: Vec::new(),
+            dial: &[],
+            tabs: &[],
+        };
+        let mut suggest = Suggest::new();
+        suggest.open("");
+        suggest.refresh("a", &sources, NOW);
+        assert!(suggest.enter_list());
+        assert!(suggest.move_sel(-1));
+        assert!(suggest.move_sel(-1));
+        assert_eq!(suggest.selected_item().unwrap().url, "https://ab.test/");
+        assert!(suggest.move_sel(1));
+        assert!(!suggest.move_sel(1));
+        assert_eq!(suggest.selected(), None);
+    }
+
+    /// An engine's answer lands under the local matches, but only while its
+    /// query is still the text, and never repeats the text itself.
+    #[test]
+    fn appends_engine_searches() {
+        let history = [visit("https://retro.test/", "", NOW, 1)];
+        let sources = Sources {
+            history: &history,
+            bookmarks: Vec::new(),
+            dial: &[],
+            tabs: &[],
+        };
+        let results = |q: &str| Some(format!("https://s.test/?q={q}"));
+        let answer = || vec!["retro".to_string(), "retro games".to_string()];
+        let mut suggest = Suggest::new();
+        suggest.open("");
+        suggest.refresh("retro", &sources, NOW);
+        suggest.add_searches("retr", answer(), results);
+        assert_eq!(suggest.items().len(), 1);
+        suggest.add_searches("retro", answer(), results);
+        let titles: Vec<&str> = suggest.items().iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, ["", "retro games"]);
+        assert_eq!(suggest.items()[1].source, Source::Search);
+    }
+}
@@ -4,6 +4,13 @@ use std::path::PathBuf;
 #[folder = "resources/servo"]
 pub struct ServoResources;
 
+/// The fallback ad-block engine, `default.dat`: the default lists compiled and
+/// serialized by `cargo run --example adblock_dat` before a release build. A
+/// build without one just has no fallback (see [`crate::browser::adblock`]).
+#[derive(rust_embed::Embed)]
+#[folder = "resources/adblock"]
+pub struct AdblockResources;
+
 /// Static reader registered with Servo via the `inventory`-based resource API.
 static RESOURCE_READER: ServoResources = ServoResources;
 
@@ -17,7 +24,7 @@ impl servo::resources::ResourceReaderMethods for ServoResources {
         // Degrade gracefully instead of aborting the browser: a servo version bump
         // can add a Resource variant we haven't vendored into resources/servo.
         match ServoResources::get(name) {
-            Some(resource) => resource.data.to_vec(),
+            Some(resource) => crate::browser::mark_error_page(name, resource.data.to_vec()),
             None => {
                 log::error!("missing embedded servo resource: {name}");
                 Vec::new()
@@ -0,0 +1,189 @@
+//! Rendering of the blocked-requests panel (state lives in
+//! [`crate::overlay::blocked`]): a Blocked / Requests section bar, then the
+//! focused load in full and a row per load — `reason  url` for the blocked
+//! ones, `method type  url  status` for every request. Up/Down move, L1/R1
+//! switch sections, Y saves the requests as HAR, B closes.
+
+use super::menu::row_atoms;
+use super::panel::{self, section_scroll, ROW_GAP, SIDES};
+use super::theme::{ACCENT, DIM, ROW_FONT};
+use crate::app::{AppCommand, BlockedAction};
+use crate::browser::block_log::BlockKind;
+use crate::browser::net_log::Outcome;
+use crate::overlay::blocked::{BlockedSection, BlockedView};
+use egui_phosphor::bold;
+use egui_sdl2::egui::{self, AtomExt as _};
+
+const SECTIONS: [BlockedSection; 2] = [BlockedSection::Blocked, BlockedSection::Requests];
+
+fn section_label(section: BlockedSection) -> &'static str {
+    match section {
+        BlockedSection::Blocked => "Blocked",
+        BlockedSection::Requests => "Requests",
+    }
+}
+
+/// Draw the panel full-screen. A click focuses a row.
+pub(super) fn add_blocked(ctx: &egui::Context, view: &BlockedView, commands: &mut Vec<AppCommand>) {
+    let screen = ctx.content_rect();
+    let closed = panel::panel(ctx, "blocked", screen, |ui| {
+        let requests = view.section() == BlockedSection::Requests;
+        let clicked =
+            panel::section_bar(ui, screen, SECTIONS, view.section(), section_label, |ui| {
+                if requests
+                    && ui
+                        .button(egui::RichText::new("Save as HAR").size(ROW_FONT))
+                        .clicked()
+                {
+                    commands.push(AppCommand::Blocked(BlockedAction::SaveHar));
+                }
+            });
+        if let Some(section) = clicked {
+            commands.push(AppCommand::Blocked(BlockedAction::SetSection(section)));
+        }
+        ui.add_space(8.0);
+        if requests {
+            add_requests(ui, screen, view, commands);
+        } else {
+            add_blocked_loads(ui, screen, view, commands);
+        }
+    });
+    if closed {
+        commands.push(AppCommand::Blocked(BlockedAction::Close));
+    }
+}
+
+fn add_blocked_loads(
+    ui: &mut egui::Ui,
+    screen: egui::Rect,
+    view: &BlockedView,
+    commands: &mut Vec<AppCommand>,
+) {
+    let log = view.log();
+    let heading = format!("Blocked on this page — {}", log.total());
+    ui.label(egui::RichText::new(heading).color(egui::Color32::WHITE));
+    let counts: Vec<String> = BlockKind::ALL
+        .iter()
+        .filter(|kind| log.count(**kind) > 0)
+        .map(|kind| format!("{} {}", kind.label(), log.count(*kind)))
+        .collect();
+    let (up, down) = (bold::CARET_UP, bold::CARET_DOWN);
+    let hint = format!(
+        "{}   {up}{down} move   L1/R1 section   B close",
+        counts.join(" · ")
+    );
+    ui.label(egui::RichText::new(hint).color(DIM));
+    ui.add_space(8.0);
+
+    let full_w = screen.width() - SIDES;
+    match view.selected_entry() {
+        Some(entry) => {
+            ui.add(
+                egui::Label::new(egui::RichText::new(&entry.url).color(egui::Color32::WHITE))
+                    .wrap(),
+            );
+            let rule = format!("{}: {}", entry.kind.label(), entry.rule);
+            ui.label(egui::RichText::new(rule).color(ACCENT));
+        }
+        None => {
+            ui.label(egui::RichText::new("Nothing blocked yet.").color(DIM));
+        }
+    }
+    ui.add_space(8.0);
+
+    section_scroll(ui, screen).show(ui, |ui| {
+        ui.spacing_mut().item_spacing.y = ROW_GAP;
+        for (i, entry) in log.entries().enumerate() {
+            let selected = i == view.selected();
+            let kind = egui::RichText::new(entry.kind.label())
+                .size(ROW_FONT)
+                .color(ACCENT);
+            let url = egui::RichText::new(&entry.url)
+                .size(ROW_FONT)
+                .color(egui::Color32::WHITE);
+            let atoms = (kind, url.atom_shrink(true), egui::Atom::grow());
+            let resp = row_atoms(ui, full_w, selected, atoms);
+            // Keep the focused row in view — no cursor to drag the bar.
+            if selected {
+                resp.scroll_to_me(Some(egui::Align::Center));
+            }
+            if resp.clicked() {
+                commands.push(AppCommand::Blocked(BlockedAction::Select(i)));
+            }
+        }
+    });
+}
+
+fn add_requests(
+    ui: &mut egui::Ui,
+    screen: egui::Rect,
+    view: &BlockedView,
+    commands: &mut Vec<AppCommand>,
+) {
+    let log = view.requests();
+    let heading = format!("Requests from this tab — {}", log.len());
+    ui.label(egui::RichText::new(heading).color(egui::Color32::WHITE));
+    let (up, down) = (bold::CARET_UP, bold::CARET_DOWN);
+    let hint = match view.note() {
+        Some(note) => note.to_string(),
+        None => format!("{up}{down} move   L1/R1 section   Y save as HAR   B close"),
+    };
+    ui.label(egui::RichText::new(hint).color(DIM));
+    ui.add_space(8.0);
+
+    let full_w = screen.width() - SIDES;
+    match view.selected_request() {
+        Some(entry) => {
+            ui.add(
+                egui::Label::new(
+                    egui::RichText::new(&entry.request.url).color(egui::Color32::WHITE),
+                )
+                .wrap(),
+            );
+            let mut detail = format!(
+                "{} · {} · +{} ms",
+                entry.request.method,
+                entry.request.kind(),
+                entry.offset.as_millis()
+            );
+            match &entry.outcome {
+                Outcome::Passed => {}
+                Outcome::Blocked(reason) => {
+                    detail.push_str(&format!(" · blocked by {reason}"));
+                }
+                Outcome::Redirected(to) => {
+                    detail.push_str(&format!(" · 307 to {to}"));
+                }
+                outcome => detail.push_str(&format!(" · {}", outcome.label())),
+            }
+            ui.add(egui::Label::new(egui::RichText::new(detail).color(ACCENT)).wrap());
+        }
+        None => {
+            ui.label(egui::RichText::new("No requests yet.").color(DIM));
+        }
+    }
+    ui.add_space(8.0);
+
+    section_scroll(ui, screen).show(ui, |ui| {
+        ui.spacing_mut().item_spacing.y = ROW_GAP;
+        for (i, entry) in log.entries().enumerate() {
+            let selected = i == view.selected();
+            let kind = format!("{} {}", entry.request.method, entry.request.kind());
+            let kind = egui::RichText::new(kind).size(ROW_FONT).color(ACCENT);
+            let url = egui::RichText::new(&entry.request.url)
+                .size(ROW_FONT)
+                .color(egui::Color32::WHITE);
+            let status = egui::RichText::new(entry.outcome.label())
+                .size(ROW_FONT)
+                .color(DIM);
+            let atoms = (kind, url.atom_shrink(true), egui::Atom::grow(), status);
+            let resp = row_atoms(ui, full_w, selected, atoms);
+            if selected {
+                resp.scroll_to_me(Some(egui::Align::Center));
+            }
+            if resp.clicked() {
+                commands.push(AppCommand::Blocked(BlockedAction::Select(i)));
+            }
+        }
+    });
+}
@@ -6,7 +6,7 @@
 use super::home::{paint_tile, tile_grid, GAP, GLYPH, TILE_H, TILE_W};
 use super::theme::{ACCENT, CLOSE_SIZE};
 use crate::app::{AppCommand, MenuAction};
-use crate::data::dial::SETTINGS_PIN;
+use crate::data::{dial::SETTINGS_PIN, Page};
 use crate::overlay::dial_edit::DialEdit;
 use egui_phosphor::bold;
 use egui_sdl2::egui;
@@ -21,7 +21,7 @@ const MUTED: egui::Color32 = egui::Color32::from_rgb(0x8a, 0x8f, 0x98);
 pub(super) fn add_dial_edit(
     ctx: &egui::Context,
     edit: &mut DialEdit,
-    pins: &[String],
+    pins: &[Page],
     osk_caret: Option<usize>,
     commands: &mut Vec<AppCommand>,
 ) {
@@ -68,18 +68,18 @@ pub(super) fn add_dial_edit(
 fn add_grid(
     ui: &mut egui::Ui,
     edit: &DialEdit,
-    pins: &[String],
+    pins: &[Page],
     width: f32,
     cols: usize,
     commands: &mut Vec<AppCommand>,
 ) {
     // Real dial indices kept for deletion; the settings tile is the last slot.
-    let regular: Vec<(usize, &String)> = pins
+    let regular: Vec<(usize, &Page)> = pins
         .iter()
         .enumerate()
-        .filter(|(_, u)| u.as_str() != SETTINGS_PIN)
+        .filter(|(_, pin)| pin.url != SETTINGS_PIN)
         .collect();
-    let settings_pinned = pins.iter().any(|u| u == SETTINGS_PIN);
+    let settings_pinned = pins.iter().any(|pin| pin.url == SETTINGS_PIN);
     let settings_slot = regular.len();
 
     tile_grid(ui, width, cols, settings_slot + 1, |ui, slot| {
@@ -89,8 +89,8 @@ fn add_grid(
                 commands.push(AppCommand::Menu(MenuAction::DialToggleSettings));
             }
         } else {
-            let (dial_index, url) = regular[slot];
-            if add_edit_tile(ui, url, selected, dial_index) {
+            let (dial_index, pin) = regular[slot];
+            if add_edit_tile(ui, &pin.url, selected, dial_index) {
                 commands.push(AppCommand::Menu(MenuAction::DialRemoveAt(dial_index)));
             }
         }
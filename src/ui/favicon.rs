@@ -0,0 +1,62 @@
+//! Favicons as egui textures, loaded lazily from the PNG cache
+//! ([`crate::data::favicons`]) the first time a row or tile asks for a host and
+//! memoized per host — a miss included, so a site without an icon doesn't hit
+//! the disk every frame. A freshly saved icon evicts its host (see
+//! [`super::AppUi::favicon_saved`]).
+
+use crate::data::favicons;
+use egui_sdl2::egui;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+thread_local! {
+    /// Uploaded icons by host; `None` caches a miss. Single-threaded like
+    /// [`super::home`]'s label cache — egui runs on the main thread.
+    static TEXTURES: RefCell<HashMap<String, Option<egui::TextureHandle>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// The cache key of `url`: its lowercased host, if it has one.
+fn host(url: &str) -> Option<String> {
+    Some(url::Url::parse(url).ok()?.host_str()?.to_ascii_lowercase())
+}
+
+/// The favicon of `url`'s site, if one is cached.
+pub(super) fn texture(ctx: &egui::Context, url: &str) -> Option<egui::TextureId> {
+    let host = host(url)?;
+    TEXTURES.with(|cache| {
+        if let Some(entry) = cache.borrow().get(&host) {
+            return entry.as_ref().map(|t| t.id());
+        }
+        let handle = favicons::load(url).map(|icon| {
+            let size = [icon.width as usize, icon.height as usize];
+            let image = egui::ColorImage::from_rgba_unmultiplied(size, &icon.rgba);
+            ctx.load_texture(
+                format!("favicon:{host}"),
+                image,
+                egui::TextureOptions::LINEAR,
+            )
+        });
+        let id = handle.as_ref().map(|t| t.id());
+        cache.borrow_mut().insert(host, handle);
+        id
+    })
+}
+
+/// Forget `url`'s host so its next lookup re-reads the cache file.
+pub(super) fn evict(url: &str) {
+    if let Some(host) = host(url) {
+        TEXTURES.with(|cache| cache.borrow_mut().remove(&host));
+    }
+}
+
+/// Paint `url`'s favicon filling `rect`; returns false (painting nothing)
+/// when none is cached, so the caller can draw its fallback.
+pub(super) fn paint(painter: &egui::Painter, rect: egui::Rect, url: &str) -> bool {
+    let Some(id) = texture(painter.ctx(), url) else {
+        return false;
+    };
+    let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+    painter.image(id, rect, uv, egui::Color32::WHITE);
+    true
+}
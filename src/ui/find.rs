@@ -0,0 +1,43 @@
+//! Rendering of the find-in-page bar (state lives in [`crate::overlay::find`]):
+//! a small panel pinned to the web view's top-right corner with the query, the
+//! match counter, and the controls that step it. Non-interactive — it's driven
+//! by the router, and the on-screen keyboard edits the query.
+
+use super::theme::{icon, ACCENT, DIM, PANEL_FILL, WARN};
+use crate::overlay::find::Find;
+use egui_phosphor::bold;
+use egui_sdl2::egui;
+
+/// Gap between the bar and the web view's corner (logical px).
+const MARGIN: f32 = 8.0;
+
+pub(super) fn add_find(ctx: &egui::Context, find: &Find, webview: egui::Rect) {
+    egui::Area::new(egui::Id::new("find_bar"))
+        .order(egui::Order::Foreground)
+        .interactable(false)
+        .pivot(egui::Align2::RIGHT_TOP)
+        .fixed_pos(webview.right_top() + egui::vec2(-MARGIN, MARGIN))
+        .show(ctx, |ui| {
+            egui::Frame::default()
+                .fill(PANEL_FILL)
+                .stroke(egui::Stroke::new(1.0, ACCENT))
+                .corner_radius(6.0)
+                .inner_margin(egui::Margin::symmetric(10, 6))
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(icon(bold::MAGNIFYING_GLASS).color(ACCENT));
+                        let query = match find.query() {
+                            "" => egui::RichText::new("Find in page").color(DIM),
+                            text => egui::RichText::new(text).color(egui::Color32::WHITE),
+                        };
+                        ui.label(query);
+                        let status = find.status();
+                        if !status.is_empty() {
+                            let color = if find.no_matches() { WARN } else { ACCENT };
+                            ui.label(egui::RichText::new(status).strong().color(color));
+                        }
+                        ui.label(egui::RichText::new("L1/R1 step · B close").color(DIM));
+                    });
+                });
+        });
+}
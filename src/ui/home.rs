@@ -8,7 +8,7 @@
 
 use super::theme::ACCENT;
 use crate::app::{AppCommand, MenuAction};
-use crate::data::dial::SETTINGS_PIN;
+use crate::data::{dial::SETTINGS_PIN, Page};
 use crate::overlay::home::Home;
 use egui_phosphor::bold;
 use egui_sdl2::egui;
@@ -60,9 +60,10 @@ const HINT_BAND: f32 = HINT_BASE + HINT_PILL_H / 2.0 + 8.0;
 pub(super) fn add_home(
     ctx: &egui::Context,
     home: &mut Home,
-    pins: &[String],
+    pins: &[Page],
     webview: egui::Rect,
     osk_caret: Option<usize>,
+    completion: &str,
     commands: &mut Vec<AppCommand>,
 ) {
     let area = webview;
@@ -108,7 +109,7 @@ pub(super) fn add_home(
                         ui.add_space(top);
                         add_wordmark(ui);
                         ui.add_space(gap_top);
-                        add_search(ui, home, block_w, osk_caret);
+                        add_search(ui, home, block_w, osk_caret, completion);
                         ui.add_space(GAP_MID);
                         let rest = (floor - ui.cursor().top()).max(TILE_H);
                         // The viewport spans the page and the grid centers inside it:
@@ -284,7 +285,13 @@ fn lerp_color(a: egui::Color32, b: egui::Color32, t: f32) -> egui::Color32 {
 /// The hero search / URL field. Editable directly (desktop keyboard); on the
 /// handheld the OSK writes into the same buffer. Enter submits it (handled in
 /// the keyboard/router layer).
-fn add_search(ui: &mut egui::Ui, home: &mut Home, width: f32, osk_caret: Option<usize>) {
+fn add_search(
+    ui: &mut egui::Ui,
+    home: &mut Home,
+    width: f32,
+    osk_caret: Option<usize>,
+    completion: &str,
+) {
     let selected = home.search_focused();
     let edit_id = egui::Id::new("home_search");
     // While the OSK types here, mirror its caret (egui won't follow the external
@@ -310,11 +317,13 @@ fn add_search(ui: &mut egui::Ui, home: &mut Home, width: f32, osk_caret: Option<
             .frame(egui::Frame::NONE)
             .background_color(egui::Color32::TRANSPARENT)
             .desired_width(f32::INFINITY)
-            .font(field_font)
+            .font(field_font.clone())
             // egui puts the text at the top; the frame's margins make the box taller.
             .vertical_align(egui::Align::Center)
             .text_color(INK);
-        let resp = ui.add(edit);
+        let output = edit.show(ui);
+        super::suggest::paint_completion(ui, &output, completion, field_font);
+        let resp = output.response;
         // Clicking the field selects it (so the highlight follows the mouse).
         if resp.gained_focus() {
             home.focus_search();
@@ -343,12 +352,12 @@ fn add_search(ui: &mut egui::Ui, home: &mut Home, width: f32, osk_caret: Option<
     });
 }
 
-/// The speed-dial grid: one tile per pinned shortcut (the brand initial over its
-/// name), followed by a trailing "Edit" tile that opens the speed-dial editor.
+/// The speed-dial grid: one tile per pinned shortcut (its favicon or brand
+/// initial over its name), followed by a trailing "Edit" tile that opens the speed-dial editor.
 fn add_dial(
     ui: &mut egui::Ui,
     home: &Home,
-    pins: &[String],
+    pins: &[Page],
     width: f32,
     cols: usize,
     commands: &mut Vec<AppCommand>,
@@ -357,9 +366,9 @@ fn add_dial(
     tile_grid(ui, width, cols, tiles, |ui, i| {
         let selected = home.tile() == Some(i);
         match pins.get(i) {
-            Some(url) => {
-                if add_tile(ui, url, selected).clicked() {
-                    commands.push(AppCommand::Menu(MenuAction::OpenUrl(url.clone())));
+            Some(pin) => {
+                if add_tile(ui, pin, selected).clicked() {
+                    commands.push(AppCommand::Menu(MenuAction::OpenUrl(pin.url.clone())));
                 }
             }
             // i == pins.len(): the trailing "Edit" tile.
@@ -410,16 +419,23 @@ pub(super) fn tile_grid(
 
 /// Glyph-square side length within a tile.
 pub(super) const GLYPH: f32 = 52.0;
+/// A favicon's side within the glyph square.
+const ICON: f32 = 28.0;
 
-/// One speed-dial tile: a rounded "glyph" square holding the brand initial, with
+/// One speed-dial tile: a rounded "glyph" square holding the site's icon, with
 /// the brand name beneath it — accent-ringed and brightened when selected or
-/// hovered. Custom-painted (not a Button) for the two-tier look. Returns its
-/// click response.
-fn add_tile(ui: &mut egui::Ui, url: &str, selected: bool) -> egui::Response {
+/// hovered. The page title, too long for the tile, is its hover text.
+/// Custom-painted (not a Button) for the two-tier look. Returns its click
+/// response.
+fn add_tile(ui: &mut egui::Ui, pin: &Page, selected: bool) -> egui::Response {
     let (rect, resp) = ui.allocate_exact_size(egui::vec2(TILE_W, TILE_H), egui::Sense::click());
     keep_visible(ui, rect, selected);
-    paint_tile(ui.painter(), rect, url, selected || resp.hovered());
-    resp
+    paint_tile(ui.painter(), rect, &pin.url, selected || resp.hovered());
+    if pin.title.is_empty() {
+        resp
+    } else {
+        resp.on_hover_text(pin.title.as_str())
+    }
 }
 
 /// Scroll a selected tile into view. No-op outside a scroll area, where the clip
@@ -430,8 +446,8 @@ fn keep_visible(ui: &egui::Ui, rect: egui::Rect, selected: bool) {
     }
 }
 
-/// Paint a speed-dial tile's visuals (glyph square + brand initial + name) into
-/// `rect`. Shared by the start page and the dial editor ([`super::dial_edit`]);
+/// Paint a speed-dial tile's visuals (glyph square + favicon, or the brand
+/// initial without one, + name) into `rect`. Shared by the start page and the dial editor ([`super::dial_edit`]);
 /// the caller owns the click region (and any extra overlays like a delete badge).
 pub(super) fn paint_tile(painter: &egui::Painter, rect: egui::Rect, url: &str, active: bool) {
     // Glyph square, centered near the top of the tile.
@@ -463,13 +479,17 @@ pub(super) fn paint_tile(painter: &egui::Painter, rect: egui::Rect, url: &str, a
             .unwrap_or_default();
         (initial, label)
     };
-    painter.text(
-        glyph.center(),
-        egui::Align2::CENTER_CENTER,
-        glyph_text,
-        font(22.0),
-        INK,
-    );
+    // A cached favicon takes the initial's place, at the size the cache keeps.
+    let icon = egui::Rect::from_center_size(glyph.center(), egui::vec2(ICON, ICON));
+    if url == SETTINGS_PIN || !super::favicon::paint(painter, icon, url) {
+        painter.text(
+            glyph.center(),
+            egui::Align2::CENTER_CENTER,
+            glyph_text,
+            font(22.0),
+            INK,
+        );
+    }
 
     // Brand name under the glyph (truncated so a long name can't overflow).
     painter.text(
@@ -6,8 +6,11 @@ use super::panel::{self, section_scroll, ROW_GAP, ROW_RADIUS, SIDES};
 use super::theme::{self, ACCENT, DIM, ROW_FONT, WARN};
 use crate::app::{AppCommand, MenuAction};
 use crate::browser::TabInfo;
+use crate::data::bookmarks::{Entry, Folder};
+use crate::data::downloads::{Download, State, Verdict};
 use crate::data::history;
-use crate::overlay::menu::{Menu, Section};
+use crate::overlay::bookmark_edit::{BookmarkEdit, Field};
+use crate::overlay::menu::{Menu, Section, NEW_TAB_ROWS};
 use egui_phosphor::{bold, fill};
 use egui_sdl2::egui::{self, AtomExt as _};
 
@@ -16,6 +19,8 @@ use egui_sdl2::egui::{self, AtomExt as _};
 const ROW_H: f32 = 32.0;
 /// The square delete button leading each row.
 const DEL_W: f32 = 26.0;
+/// A favicon's side at the head of a URL row.
+const ROW_ICON: f32 = 16.0;
 
 /// A row's leading delete button, accent on the selected row.
 fn delete_button(ui: &mut egui::Ui, selected: bool, dim: egui::Color32) -> egui::Response {
@@ -26,6 +31,50 @@ fn delete_button(ui: &mut egui::Ui, selected: bool, dim: egui::Color32) -> egui:
     )
 }
 
+/// A bookmark row's edit button (the sheet Y opens), sized like [`delete_button`].
+fn edit_button(ui: &mut egui::Ui, selected: bool, dim: egui::Color32) -> egui::Response {
+    let color = if selected { ACCENT } else { dim };
+    ui.add_sized(
+        [DEL_W, ROW_H],
+        egui::Button::new(theme::icon(bold::PENCIL_SIMPLE).color(color)).corner_radius(ROW_RADIUS),
+    )
+}
+
+/// A download row's pause / resume toggle, sized like [`delete_button`].
+fn pause_button(
+    ui: &mut egui::Ui,
+    paused: bool,
+    selected: bool,
+    dim: egui::Color32,
+) -> egui::Response {
+    let color = if selected { ACCENT } else { dim };
+    let icon = if paused { bold::PLAY } else { bold::PAUSE };
+    ui.add_sized(
+        [DEL_W, ROW_H],
+        egui::Button::new(theme::icon(icon).color(color)).corner_radius(ROW_RADIUS),
+    )
+}
+
+/// A finished download row's details toggle, sized like [`delete_button`];
+/// filled while its details show.
+fn details_button(
+    ui: &mut egui::Ui,
+    open: bool,
+    selected: bool,
+    dim: egui::Color32,
+) -> egui::Response {
+    let color = if selected { ACCENT } else { dim };
+    let icon = if open {
+        theme::icon_fill(fill::INFO)
+    } else {
+        theme::icon(bold::INFO)
+    };
+    ui.add_sized(
+        [DEL_W, ROW_H],
+        egui::Button::new(icon.color(color)).corner_radius(ROW_RADIUS),
+    )
+}
+
 /// A row's bookmark toggle (Tabs / History), sized like [`delete_button`] so the
 /// trailing buttons line up across rows.
 fn bookmark_button(ui: &mut egui::Ui, bookmarked: bool, dim: egui::Color32) -> egui::Response {
@@ -60,7 +109,7 @@ fn row_button(
 
 /// [`row_button`] over pre-built atoms (a multi-color label). Mark one atom
 /// `shrink` — truncation eats that one; egui otherwise picks the leading run.
-fn row_atoms<'a>(
+pub(super) fn row_atoms<'a>(
     ui: &mut egui::Ui,
     width: f32,
     selected: bool,
@@ -74,24 +123,48 @@ fn row_atoms<'a>(
     )
 }
 
-/// A URL row's label: site name in white, rest of the URL dim and middle-elided
-/// to `width`. Leading with the name makes the list scannable; keeping both ends
-/// of the path keeps what differs between two rows of the same site.
-fn url_atoms(ui: &egui::Ui, url: &str, pinned: bool, width: f32) -> egui::Atoms<'static> {
+/// A URL row's label, led by the site's favicon when one is cached. A titled
+/// page shows its title in white with the site name dim after it; an untitled
+/// one the site name in white, rest of the URL dim and middle-elided to `width`.
+/// Leading with the name makes the list scannable; keeping both ends of the path
+/// keeps what differs between two rows of the same site.
+pub(super) fn url_atoms(
+    ui: &egui::Ui,
+    url: &str,
+    title: &str,
+    pinned: bool,
+    width: f32,
+) -> egui::Atoms<'static> {
     let font = egui::FontId::proportional(ROW_FONT);
     let brand_text = super::home::brand_label(url);
-    // Row padding, the gap after the brand, and the pin when there is one.
-    let mut budget = width - text_width(ui, &brand_text, &font) - 24.0;
-    if pinned {
-        budget -= text_width(ui, bold::PUSH_PIN, &font) + 4.0;
+    let icon = super::favicon::texture(ui.ctx(), url);
+    let mut atoms = if title.is_empty() {
+        // Row padding, the gap after the brand, and the icon and pin if any.
+        let mut budget = width - text_width(ui, &brand_text, &font) - 24.0;
+        if icon.is_some() {
+            budget -= ROW_ICON + 4.0;
+        }
+        if pinned {
+            budget -= text_width(ui, bold::PUSH_PIN, &font) + 4.0;
+        }
+        let brand = egui::RichText::new(brand_text)
+            .size(ROW_FONT)
+            .color(egui::Color32::WHITE);
+        let tail = egui::RichText::new(elide_middle(ui, url_tail(url), &font, budget))
+            .size(ROW_FONT)
+            .color(DIM);
+        egui::Atoms::new((brand, tail.atom_shrink(true), egui::Atom::grow()))
+    } else {
+        // The title is what truncation eats; the site name stays whole.
+        let title = egui::RichText::new(title)
+            .size(ROW_FONT)
+            .color(egui::Color32::WHITE);
+        let brand = egui::RichText::new(brand_text).size(ROW_FONT).color(DIM);
+        egui::Atoms::new((title.atom_shrink(true), brand, egui::Atom::grow()))
+    };
+    if let Some(id) = icon {
+        atoms.push_left(egui::Image::new((id, egui::vec2(ROW_ICON, ROW_ICON))));
     }
-    let brand = egui::RichText::new(brand_text)
-        .size(ROW_FONT)
-        .color(egui::Color32::WHITE);
-    let tail = egui::RichText::new(elide_middle(ui, url_tail(url), &font, budget))
-        .size(ROW_FONT)
-        .color(DIM);
-    let mut atoms = egui::Atoms::new((brand, tail.atom_shrink(true), egui::Atom::grow()));
     if pinned {
         atoms.push_left(
             egui::RichText::new(bold::PUSH_PIN)
@@ -205,20 +278,24 @@ pub(super) fn add_menu(
         if let Some(section) = clicked {
             commands.push(AppCommand::Menu(MenuAction::SetSection(section)));
         }
-        // Y is section-specific: Bookmarks pins to the dial, History/Tabs bookmark.
+        // Y is section-specific: Bookmarks edits, History/Tabs bookmark.
         let y_hint = match menu.section() {
-            Section::Bookmarks => "   Y pin",
+            Section::Bookmarks => "   Y edit   L2/R2 move",
             Section::History | Section::Tabs => "   Y bookmark",
-            Section::Downloads => "",
+            Section::Downloads => "   Y pause/details   L2/R2 move",
         };
+        let in_folder = menu.section() == Section::Bookmarks && menu.bookmarks().in_folder();
+        let b_hint = if in_folder { "B back" } else { "B close" };
         let (left, right) = (bold::CARET_LEFT, bold::CARET_RIGHT);
         let (up, down) = (bold::CARET_UP, bold::CARET_DOWN);
-        ui.label(
-            egui::RichText::new(format!(
-                "{left}{right} section   {up}{down} select   A open   X delete{y_hint}   B close"
-            ))
-            .color(dim),
-        );
+        let hint = if menu.bookmark_edit().is_some() {
+            format!("{up}{down} select   {left}{right} change   A edit   Y save   B cancel")
+        } else {
+            format!(
+                "{left}{right} section   {up}{down} select   A open   X delete{y_hint}   {b_hint}"
+            )
+        };
+        ui.label(egui::RichText::new(hint).color(dim));
         ui.add_space(8.0);
 
         match menu.section() {
@@ -235,8 +312,9 @@ pub(super) fn add_menu(
     }
 }
 
-/// Tabs section: a leading "+ New tab" row (selection index 0) over the open tabs
-/// (indices `1..=tabs.len()`), each with a bookmark toggle and a close button.
+/// Tabs section: the leading "+ New tab" and "+ New no-history tab" rows over
+/// the open tabs (from index [`NEW_TAB_ROWS`]), each with a bookmark toggle and
+/// a close button. A no-history tab leads with the crossed-out eye.
 fn add_tabs_section(
     ui: &mut egui::Ui,
     screen: egui::Rect,
@@ -250,24 +328,30 @@ fn add_tabs_section(
     let row_w = screen.width() - SIDES - 2.0 * DEL_W - 12.0;
     section_scroll(ui, screen).show(ui, |ui| {
         ui.spacing_mut().item_spacing.y = ROW_GAP;
-        // "+ New tab" action at the top (selection index 0): a plain full-width
-        // row, same height/indent as the tab rows below, marked by the selectable
-        // highlight when it's the cursor row (no fill of its own).
-        let new_tab = row_button(
-            ui,
-            screen.width() - SIDES,
-            selected == 0,
-            egui::RichText::new("+ New tab").color(egui::Color32::WHITE),
-        );
-        if selected == 0 {
-            new_tab.scroll_to_me(Some(egui::Align::Center));
-        }
-        if new_tab.clicked() {
-            commands.push(AppCommand::Menu(MenuAction::NewTab));
+        // The new-tab actions at the top (selection indices 0 and 1): plain
+        // full-width rows, same height/indent as the tab rows below, marked by
+        // the selectable highlight when it's the cursor row (no fill of its own).
+        let actions = [
+            ("+ New tab", MenuAction::NewTab),
+            ("+ New no-history tab", MenuAction::NewNoHistoryTab),
+        ];
+        for (i, (label, action)) in actions.into_iter().enumerate() {
+            let resp = row_button(
+                ui,
+                screen.width() - SIDES,
+                selected == i,
+                egui::RichText::new(label).color(egui::Color32::WHITE),
+            );
+            if selected == i {
+                resp.scroll_to_me(Some(egui::Align::Center));
+            }
+            if resp.clicked() {
+                commands.push(AppCommand::Menu(action));
+            }
         }
 
         for (i, tab) in tabs.iter().enumerate() {
-            let sel = selected == i + 1; // index 0 is the "+ New tab" button
+            let sel = selected == i + NEW_TAB_ROWS;
             ui.horizontal(|ui| {
                 // The active (shown) tab stands out in the accent color and bold;
                 // the cursor's row uses the selectable highlight, so the two are
@@ -277,7 +361,13 @@ fn add_tabs_section(
                 } else {
                     egui::RichText::new(&tab.title).color(egui::Color32::WHITE)
                 };
-                let resp = row_button(ui, row_w, sel, text);
+                let resp = if tab.no_history {
+                    let icon = theme::icon(bold::EYE_SLASH).color(ACCENT);
+                    let text = text.size(ROW_FONT).atom_shrink(true);
+                    row_atoms(ui, row_w, sel, (icon, text, egui::Atom::grow()))
+                } else {
+                    row_button(ui, row_w, sel, text)
+                };
                 if sel {
                     resp.scroll_to_me(Some(egui::Align::Center));
                 }
@@ -300,7 +390,10 @@ fn add_tabs_section(
     });
 }
 
-/// Bookmarks section: the saved URLs, highlighted row selected.
+/// Bookmarks section: the open folder as a drill-down list — a breadcrumb
+/// back to the parent when inside one, the "+ New folder" row (selection index
+/// 0), then its subfolders and pages (indices `1..`). The edit sheet replaces
+/// the list while it's up.
 fn add_bookmarks_section(
     ui: &mut egui::Ui,
     screen: egui::Rect,
@@ -308,34 +401,88 @@ fn add_bookmarks_section(
     dim: egui::Color32,
     commands: &mut Vec<AppCommand>,
 ) {
-    let bookmarks = menu.bookmarks();
-    if bookmarks.urls().is_empty() {
-        ui.label(
-            egui::RichText::new(format!(
-                "No bookmarks yet — press {} to add this page.",
-                bold::STAR
-            ))
-            .color(dim),
-        );
+    if let Some(edit) = menu.bookmark_edit() {
+        add_bookmark_edit(ui, screen, edit, dim, commands);
         return;
     }
+    let bookmarks = menu.bookmarks();
+    if bookmarks.in_folder() {
+        let path = bookmarks.path_names().join(" › ");
+        let back = egui::Button::new(
+            egui::RichText::new(format!("{}  Bookmarks › {path}", bold::ARROW_LEFT)).color(dim),
+        )
+        .frame(false);
+        if ui.add(back).clicked() {
+            commands.push(AppCommand::Menu(MenuAction::FolderUp));
+        }
+    }
 
     // Fixed widths derived from the screen (not `ui.available_width()`, which is
     // unreliable inside a scroll area and made the list jump horizontally).
-    let row_w = screen.width() - SIDES - DEL_W - 6.0; // frame margins + delete + spacing
+    let row_w = screen.width() - SIDES - 2.0 * DEL_W - 12.0; // margins + edit + delete + spacing
+    let folder = bookmarks.folder();
     section_scroll(ui, screen).show(ui, |ui| {
         ui.spacing_mut().item_spacing.y = ROW_GAP;
-        for (i, url) in bookmarks.urls().iter().enumerate() {
-            let selected = i == bookmarks.selected();
-            // A leading pin marks a row pinned to the start-page dial (Y toggles).
+        let new_folder = row_button(
+            ui,
+            screen.width() - SIDES,
+            bookmarks.selected() == 0,
+            egui::RichText::new("+ New folder").color(egui::Color32::WHITE),
+        );
+        if bookmarks.selected() == 0 {
+            new_folder.scroll_to_me(Some(egui::Align::Center));
+        }
+        if new_folder.clicked() {
+            commands.push(AppCommand::Menu(MenuAction::NewFolder));
+        }
+        if folder.is_empty() {
+            let text = if bookmarks.is_empty() {
+                format!("No bookmarks yet — press {} to add this page.", bold::STAR)
+            } else {
+                "This folder is empty.".to_string()
+            };
+            ui.label(egui::RichText::new(text).color(dim));
+            return;
+        }
+
+        for i in 0..folder.len() {
+            let selected = bookmarks.selected() == i + 1; // index 0 is "+ New folder"
             ui.horizontal(|ui| {
-                let atoms = url_atoms(ui, url, menu.dial.contains(url), row_w);
-                let resp = row_atoms(ui, row_w, selected, atoms);
+                let (resp, open) = match folder.entry(i) {
+                    Some(Entry::Folder(sub)) => {
+                        let armed = menu.delete_armed() == Some(i);
+                        let atoms = folder_atoms(sub, armed);
+                        (
+                            row_atoms(ui, row_w, selected, atoms),
+                            MenuAction::EnterFolder(i),
+                        )
+                    }
+                    Some(Entry::Page(page)) => {
+                        // A leading pin marks a row pinned to the start-page dial.
+                        let pinned = menu.dial.contains(&page.url);
+                        let mut atoms = url_atoms(ui, &page.url, &page.title, pinned, row_w);
+                        if !page.tags.is_empty() {
+                            let tags: Vec<String> =
+                                page.tags.iter().map(|t| format!("#{t}")).collect();
+                            atoms.push_right(
+                                egui::RichText::new(tags.join(" "))
+                                    .size(ROW_FONT)
+                                    .color(DIM),
+                            );
+                        }
+                        let open = MenuAction::OpenUrl(page.url.clone());
+                        (row_atoms(ui, row_w, selected, atoms), open)
+                    }
+                    None => return,
+                };
                 if selected {
                     resp.scroll_to_me(Some(egui::Align::Center));
                 }
                 if resp.clicked() {
-                    commands.push(AppCommand::Menu(MenuAction::OpenUrl(url.clone())));
+                    commands.push(AppCommand::Menu(open));
+                }
+                if edit_button(ui, selected, dim).clicked() {
+                    commands.push(AppCommand::Menu(MenuAction::EditBookmark(i)));
                 }
                 if delete_button(ui, selected, dim).clicked() {
                     commands.push(AppCommand::Menu(MenuAction::RemoveAt(i)));
@@ -345,9 +492,94 @@ fn add_bookmarks_section(
     });
 }
 
-/// Downloads section: most-recent first, each row showing the file name and a
-/// status (progress, size + date, or the error). Delete cancels or removes the
-/// entry (the file on disk is kept); a finished row opens the file.
+/// A folder row's label: the folder icon, its name, and how many pages it holds
+/// (subfolders included) — or, once its delete is armed, the confirmation.
+fn folder_atoms(folder: &Folder, armed: bool) -> egui::Atoms<'static> {
+    let icon = theme::icon(bold::FOLDER_SIMPLE).color(ACCENT);
+    if armed {
+        let text = format!(
+            "Delete “{}” and {} bookmarks — press again to confirm",
+            folder.name,
+            folder.page_count()
+        );
+        let warn = egui::RichText::new(text).size(ROW_FONT).color(WARN);
+        return egui::Atoms::new((icon, warn.atom_shrink(true), egui::Atom::grow()));
+    }
+    let name = egui::RichText::new(folder.name.clone())
+        .size(ROW_FONT)
+        .color(egui::Color32::WHITE);
+    let count = egui::RichText::new(folder.page_count().to_string())
+        .size(ROW_FONT)
+        .color(DIM);
+    egui::Atoms::new((icon, name.atom_shrink(true), egui::Atom::grow(), count))
+}
+
+/// The bookmark edit sheet: a settings-style row per field (label left, value
+/// right in the accent), the Save row last with a cancel button beside it.
+fn add_bookmark_edit(
+    ui: &mut egui::Ui,
+    screen: egui::Rect,
+    edit: &BookmarkEdit,
+    dim: egui::Color32,
+    commands: &mut Vec<AppCommand>,
+) {
+    let heading = if edit.url().is_empty() {
+        "Edit folder".to_string()
+    } else {
+        format!("Edit bookmark — {}", edit.url())
+    };
+    ui.add(egui::Label::new(egui::RichText::new(heading).color(dim)).truncate());
+    let full_w = screen.width() - SIDES;
+    section_scroll(ui, screen).show(ui, |ui| {
+        ui.spacing_mut().item_spacing.y = ROW_GAP;
+        for (i, field) in edit.fields().iter().enumerate() {
+            let selected = i == edit.selected();
+            let (label, value) = match field {
+                Field::Title if edit.url().is_empty() => ("Name", edit.title().to_string()),
+                Field::Title => ("Title", edit.title().to_string()),
+                Field::Tags => ("Tags (comma-separated)", edit.tags().to_string()),
+                Field::Folder => {
+                    let name = match edit.folder_choice().1 {
+                        "" => "Bookmarks",
+                        label => label,
+                    };
+                    let (left, right) = (bold::CARET_LEFT, bold::CARET_RIGHT);
+                    ("Folder", format!("{left} {name} {right}"))
+                }
+                Field::Pin => {
+                    let on = if edit.pinned() { "On" } else { "Off" };
+                    ("Pinned to the speed dial", on.to_string())
+                }
+                Field::Save => ("Save", String::new()),
+            };
+            ui.horizontal(|ui| {
+                let save = *field == Field::Save;
+                let row_w = if save { full_w - DEL_W - 6.0 } else { full_w };
+                let label = egui::RichText::new(label)
+                    .size(ROW_FONT)
+                    .color(egui::Color32::WHITE);
+                let value = egui::RichText::new(value).size(ROW_FONT).color(ACCENT);
+                let resp = row_atoms(ui, row_w, selected, (label, egui::Atom::grow(), value));
+                if selected {
+                    resp.scroll_to_me(Some(egui::Align::Center));
+                }
+                if resp.clicked() {
+                    commands.push(AppCommand::Menu(MenuAction::EditField(i)));
+                }
+                if save && delete_button(ui, false, dim).clicked() {
+                    commands.push(AppCommand::Menu(MenuAction::EditClose(false)));
+                }
+            });
+        }
+    });
+}
+
+/// Downloads section: most-recent first, each row showing the file name (and its
+/// folder when a route sent it out of the download dir) and a status (progress,
+/// size + date, or the error). An unfinished row has a pause / resume toggle, a
+/// finished one a details toggle (the details show over the list); delete
+/// cancels or removes the entry (a finished file on disk is kept); a finished
+/// row opens the file, a queued one moves to the front.
 fn add_downloads_section(
     ui: &mut egui::Ui,
     screen: egui::Rect,
@@ -361,8 +593,13 @@ fn add_downloads_section(
         return;
     }
 
+    let details = downloads.details();
+    if let Some(item) = details {
+        add_download_details(ui, item, dim);
+    }
+
     let status_w = 170.0; // fits "100% · 999.9 MB / 999.9 MB"-ish, truncated past that
-    let row_w = screen.width() - SIDES - DEL_W - status_w - 12.0;
+    let row_w = screen.width() - SIDES - 2.0 * DEL_W - status_w - 18.0;
     section_scroll(ui, screen).show(ui, |ui| {
         ui.spacing_mut().item_spacing.y = ROW_GAP;
         clear_row(
@@ -377,24 +614,42 @@ fn add_downloads_section(
         for (i, item) in downloads.items().iter().enumerate() {
             let selected = downloads.selected() == i + 1; // index 0 is "Clear finished"
             ui.horizontal(|ui| {
-                let resp = row_button(
-                    ui,
-                    row_w,
-                    selected,
-                    egui::RichText::new(&item.filename).color(egui::Color32::WHITE),
-                );
+                let name = egui::RichText::new(&item.filename)
+                    .size(ROW_FONT)
+                    .color(egui::Color32::WHITE);
+                let resp = match downloads.folder_label(item) {
+                    Some(folder) => {
+                        let folder = format!("  {} {folder}", bold::FOLDER_SIMPLE);
+                        let folder = egui::RichText::new(folder).size(ROW_FONT).color(dim);
+                        let atoms = (name.atom_shrink(true), folder, egui::Atom::grow());
+                        row_atoms(ui, row_w, selected, atoms)
+                    }
+                    None => row_button(ui, row_w, selected, name),
+                };
                 if selected {
                     resp.scroll_to_me(Some(egui::Align::Center));
                 }
                 if resp.clicked() {
                     if let Some(url) = downloads.open_url(i) {
                         commands.push(AppCommand::Menu(MenuAction::OpenUrl(url)));
+                    } else if item.is_queued() {
+                        commands.push(AppCommand::Menu(MenuAction::QueueFirst(i)));
                     }
                 }
                 ui.add_sized(
                     [status_w, ROW_H],
                     egui::Label::new(egui::RichText::new(item.status_text()).color(dim)).truncate(),
                 );
+                if item.is_finished() {
+                    let open = selected && details.is_some();
+                    if details_button(ui, open, selected, dim).clicked() {
+                        commands.push(AppCommand::Menu(MenuAction::DetailsAt(i)));
+                    }
+                } else if pause_button(ui, matches!(item.state, State::Paused(_)), selected, dim)
+                    .clicked()
+                {
+                    commands.push(AppCommand::Menu(MenuAction::PauseAt(i)));
+                }
                 if delete_button(ui, selected, dim).clicked() {
                     commands.push(AppCommand::Menu(MenuAction::RemoveAt(i)));
                 }
@@ -403,7 +658,52 @@ fn add_downloads_section(
     });
 }
 
-/// History section: visited URLs (most-recent first) with their visit date, over
+/// A finished download's details, over the list: where it was saved, its
+/// hashes, and whether they matched a published one.
+fn add_download_details(ui: &mut egui::Ui, item: &Download, dim: egui::Color32) {
+    let place = if item.path.is_empty() {
+        &item.url
+    } else {
+        &item.path
+    };
+    ui.add(egui::Label::new(egui::RichText::new(place).color(egui::Color32::WHITE)).wrap());
+    match &item.checksums {
+        Some(sums) => {
+            for (label, hex) in [
+                ("SHA-256", &sums.sha256),
+                ("MD5", &sums.md5),
+                ("CRC32", &sums.crc32),
+            ] {
+                let line = egui::RichText::new(format!("{label}  {hex}")).monospace();
+                ui.add(egui::Label::new(line.color(ACCENT)).wrap());
+            }
+        }
+        None => {
+            ui.label(egui::RichText::new("No checksums recorded.").color(dim));
+        }
+    }
+    let verdict = match &item.verdict {
+        Verdict::Verified(what) => {
+            Some(egui::RichText::new(format!("{} Matches the {what}", bold::CHECK)).color(ACCENT))
+        }
+        Verdict::Mismatch(what) => {
+            Some(egui::RichText::new(format!("{} Doesn't match the {what}", bold::X)).color(WARN))
+        }
+        Verdict::Unchecked if item.is_verifying() => {
+            Some(egui::RichText::new("Looking up a published checksum…").color(dim))
+        }
+        Verdict::Unchecked => item
+            .checksums
+            .is_some()
+            .then(|| egui::RichText::new("No published checksum to verify against.").color(dim)),
+    };
+    if let Some(verdict) = verdict {
+        ui.label(verdict);
+    }
+    ui.add_space(8.0);
+}
+
+/// History section: visited pages (most-recent first) with their visit date, over
 /// a leading "Clear all" row.
 fn add_history_section(
     ui: &mut egui::Ui,
@@ -439,7 +739,7 @@ fn add_history_section(
         for (i, entry) in hist.entries().iter().enumerate() {
             let selected = hist.selected() == i + 1; // index 0 is "Clear all"
             ui.horizontal(|ui| {
-                let atoms = url_atoms(ui, &entry.url, false, row_w);
+                let atoms = url_atoms(ui, &entry.url, &entry.title, false, row_w);
                 let resp = row_atoms(ui, row_w, selected, atoms);
                 if selected {
                     resp.scroll_to_me(Some(egui::Align::Center));
@@ -3,7 +3,10 @@
 //! Servo's FBO texture under the chrome. The actual widgets are rendered by the
 //! submodules: [`toolbar`], [`menu`] (the full-screen overlay), and [`osk`].
 
+mod blocked;
 mod dial_edit;
+mod favicon;
+mod find;
 mod hints;
 mod home;
 mod memory;
@@ -12,24 +15,31 @@ mod osk;
 mod panel;
 mod prompt;
 mod settings;
+mod site_edit;
+mod suggest;
 mod theme;
 mod toolbar;
 
 use crate::{
-    app::{AppCommand, SettingsAction},
-    browser::AppBrowser,
+    app::{AppCommand, MenuAction, SettingsAction},
+    browser::{search_suggest::SearchSuggest, AppBrowser},
     config::{
         AppConfig, DebugConfig, DisplayConfig, DownloadsConfig, HistoryConfig, InputConfig,
-        OskConfig, ToolbarPosition, UpdateConfig,
+        OskConfig, SearchEngine, ToolbarPosition, UpdateConfig,
     },
+    data::history,
     event::user::UserEventSender,
+    overlay::blocked::BlockedView,
     overlay::dial_edit::{DialEdit, EditItem},
+    overlay::find::Find,
     overlay::hints::{Hint, HintInput, HintLabels, Hints, Label, Sym},
     overlay::home::Home,
     overlay::menu::Menu,
     overlay::osk::{Osk, OskCommand, OskTarget},
     overlay::prompt::Prompt,
     overlay::settings::Settings,
+    overlay::site_edit::SiteEdit,
+    overlay::suggest::{Source, Sources, Suggest},
     platform::window::AppWindow,
     update::{UpdateState, Updater},
 };
@@ -102,8 +112,15 @@ pub enum Focus {
     /// The full-screen settings overlay (the on-screen keyboard can open over it
     /// to type into a text field, hence it ranks below `Osk`).
     Settings,
+    /// The site settings sheet for the active tab's site.
+    SiteEdit,
+    /// The blocked-requests panel for the active tab's page.
+    Blocked,
     /// Link-hint navigation.
     Hints,
+    /// The find-in-page bar (the on-screen keyboard opens over it to type the
+    /// query).
+    Find,
     /// The standalone speed-dial editor (opened from the start page).
     DialEdit,
     /// The built-in start page overlay (active tab is on `retsurf:home`).
@@ -130,12 +147,20 @@ struct FrameInputs {
     tab_count: usize,
     /// Page-zoom chip percentage (`None` at the default zoom).
     zoom_pct: Option<u16>,
+    /// Whether the active tab is in game mode (the toolbar's controller chip).
+    game_mode: bool,
+    /// Whether the active tab keeps no history (the toolbar's no-history chip).
+    no_history: bool,
+    /// Loads blocked on the active tab's page (the toolbar's shield chip).
+    blocked: usize,
     /// Tab snapshots for the menu's Tabs section (empty unless the menu is open).
     tab_infos: Vec<crate::browser::TabInfo>,
     /// The field the OSK types into this frame (if any).
     osk_field: OskField,
     /// Where the OSK's caret sits, mirrored into each `TextEdit`.
     osk_caret: usize,
+    /// The inline suggestion ghosted after that field's text (empty: none).
+    completion: String,
 }
 
 pub struct AppUi {
@@ -191,11 +216,25 @@ pub struct AppUi {
     /// The full-screen settings overlay (edits a config draft). Public — driven
     /// directly; open/close/move go through the `settings_*` coordinators.
     pub settings: Settings,
+    /// The site settings sheet (opened from the toolbar). Public — driven
+    /// directly; open via [`AppUi::site_edit_open`] so competing overlays close.
+    pub site_edit: SiteEdit,
+    /// The blocked-requests panel (opened from the toolbar's shield chip).
+    /// Public — driven directly; open via [`AppUi::blocked_open`].
+    pub blocked: BlockedView,
     /// Self-update manager (About tab): in-place on PortMaster / desktop installs,
     /// "open the release page" elsewhere. See [`crate::update`].
     update: Updater,
     /// The built-in start page overlay's selection / search-field state.
     home: Home,
+    /// Suggestions for the address bar / start-page search while the OSK types
+    /// into them, re-ranked as the text changes (see [`AppUi::refresh_suggest`]).
+    suggest: Suggest,
+    /// The default search engine, whose `suggest_url` (if set) adds searches to
+    /// the suggestions; mirrored from the config.
+    search_engine: Option<SearchEngine>,
+    /// Fetches those searches off the main thread.
+    search_suggest: SearchSuggest,
     /// The standalone speed-dial editor overlay (opened from the start page).
     dial_edit: DialEdit,
     /// Whether the active tab is on the start page (mirrored each frame from
@@ -205,6 +244,9 @@ pub struct AppUi {
     /// driven directly; a round starts via [`AppUi::hints_begin_collect`] and
     /// [`AppUi::hints_apply`].
     pub hints: Hints,
+    /// The find-in-page bar. Public — the app drives its methods directly and
+    /// opens it via [`AppUi::find_open`].
+    pub find: Find,
     /// Modal page prompts: queued `<select>` pickers and JS dialogs. Public —
     /// the router and main loop drive [`Prompt`]'s own methods directly.
     pub prompt: Prompt,
@@ -238,6 +280,7 @@ impl AppUi {
         debug: &DebugConfig,
         update: &UpdateConfig,
         user_agent: String,
+        event_sender: UserEventSender,
     ) -> Self {
         let mut egui = EguiGlow::new(window.sdl2_window(), window.glow_ctx(), None, false);
         // Install the shared accent theme so every selectable widget, text
@@ -281,11 +324,17 @@ impl AppUi {
             osk: Osk::new(osk),
             menu: Menu::new(history, downloads, user_agent),
             settings: Settings::new(),
+            site_edit: SiteEdit::new(),
+            blocked: BlockedView::new(),
             update: Updater::new(update),
             home: Home::new(),
+            suggest: Suggest::new(),
+            search_engine: None,
+            search_suggest: SearchSuggest::new(event_sender),
             dial_edit: DialEdit::new(),
             home_active: false,
             hints: Hints::new(),
+            find: Find::new(),
             prompt: Prompt::new(),
             scroll_mode: false,
             hint_badges: input.hint_badges,
@@ -316,6 +365,18 @@ impl AppUi {
         self.memory_summary = Some(memory::MemorySummary::from_report(report));
     }
 
+    /// Adopt the default search engine (at startup and on a settings save): its
+    /// `suggest_url`, if any, feeds the address-bar suggestions.
+    pub fn set_search_engine(&mut self, engine: Option<SearchEngine>) {
+        self.search_engine = engine;
+    }
+
+    /// A new favicon was cached for `url`'s site: drop the uploaded one so rows
+    /// and tiles pick the new file up.
+    pub fn favicon_saved(&self, url: &str) {
+        favicon::evict(url);
+    }
+
     /// Whether an egui widget (e.g. the address bar) currently wants keyboard
     /// input. Used on Android to show/hide the system soft keyboard.
     #[allow(dead_code)] // only called on Android
@@ -420,8 +481,14 @@ impl AppUi {
             Focus::Menu
         } else if self.settings.visible() {
             Focus::Settings
+        } else if self.site_edit.visible() {
+            Focus::SiteEdit
+        } else if self.blocked.visible() {
+            Focus::Blocked
         } else if self.hints.visible {
             Focus::Hints
+        } else if self.find.visible {
+            Focus::Find
         } else if self.dial_edit.visible() {
             Focus::DialEdit
         } else if self.home_active {
@@ -435,9 +502,17 @@ impl AppUi {
     /// to a modal `prompt()` dialog's field when one is up, else the address
     /// bar when it holds focus, otherwise the focused page element.
     pub fn osk(&mut self, cmd: OskCommand, browser: &AppBrowser, commands: &mut Vec<AppCommand>) {
+        if self.suggest_key(cmd, browser, commands) {
+            return;
+        }
         let to_address_bar = self.address_bar_focused();
         let target = if self.prompt.visible() && self.prompt.has_text_field() {
             OskTarget::Prompt(self.prompt.input_mut())
+        } else if self.menu.bookmark_edit().is_some_and(|e| e.text_selected()) {
+            // The bookmark edit sheet's focused text row (title, tags or a
+            // folder's name).
+            let edit = self.menu.bookmark_edit_mut().expect("edit sheet");
+            OskTarget::Bookmark(edit.text_mut().expect("text row"))
         } else if self.settings.visible() && self.settings.selected_is_text() {
             // The settings overlay's focused text row: typing lands in the draft
             // (the OSK only opens over a text row — see `App::settings_confirm`).
@@ -445,6 +520,9 @@ impl AppUi {
         } else if self.dial_edit.visible() {
             // The speed-dial editor's URL field (its own buffer); Enter pins it.
             OskTarget::DialEdit(self.dial_edit.input_mut())
+        } else if self.find.visible {
+            // The find bar's query; Enter runs the search.
+            OskTarget::Find(self.find.input_mut())
         } else if self.home_active {
             // On the start page, typed text goes to its own search field, not
             // the address bar (which only ever shows `retsurf:home` there).
@@ -462,6 +540,142 @@ impl AppUi {
         if to_page && matches!(cmd, OskCommand::Show) {
             self.osk_lift_pending = true;
         }
+        if matches!(cmd, OskCommand::Show) {
+            if let Some(text) = self.suggest_text(browser) {
+                self.suggest.open(&text);
+            }
+        }
+    }
+
+    /// Give the suggestion list first pick of a keyboard command: the D-pad
+    /// steps into it off the top key row and through it, A / R2 open the
+    /// highlighted row, and R2 (or the Enter key) otherwise takes the inline
+    /// completion before submitting. Returns whether `cmd` was used up.
+    fn suggest_key(
+        &mut self,
+        cmd: OskCommand,
+        browser: &AppBrowser,
+        commands: &mut Vec<AppCommand>,
+    ) -> bool {
+        if !self.osk.visible || !self.suggest.is_open() {
+            return false;
+        }
+        if let Some(index) = self.suggest.selected() {
+            match cmd {
+                OskCommand::Move(_, dy) => {
+                    // Down off the best match hands the D-pad back to the keys.
+                    self.suggest.move_sel(dy);
+                    return true;
+                }
+                OskCommand::Activate | OskCommand::Enter => {
+                    self.open_suggestion(index, commands);
+                    return true;
+                }
+                _ => self.suggest.leave_list(),
+            }
+            return false;
+        }
+        match cmd {
+            OskCommand::Move(0, -1) if self.osk.on_top_row() => self.suggest.enter_list(),
+            OskCommand::Enter => {
+                self.accept_completion(browser);
+                false
+            }
+            OskCommand::Activate if self.osk.on_enter_key() => {
+                self.accept_completion(browser);
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Open suggestion `index` — switch to its tab, or load it in the active one
+    /// (the menu rows' paths) — and put the keyboard away.
+    pub fn open_suggestion(&mut self, index: usize, commands: &mut Vec<AppCommand>) {
+        if let Some(item) = self.suggest.items().get(index) {
+            let action = match item.source {
+                Source::Tab(tab) => MenuAction::OpenTab(tab),
+                _ => MenuAction::OpenUrl(item.url.clone()),
+            };
+            commands.push(AppCommand::Menu(action));
+        }
+        self.osk.visible = false;
+        self.suggest.close();
+    }
+
+    /// Append the inline completion to the field about to be submitted — only
+    /// with the caret at the text's end, where the completion is drawn.
+    fn accept_completion(&mut self, browser: &AppBrowser) {
+        let field = self.osk_target_field();
+        let caret = self.osk.caret();
+        let completion = self.suggest.completion();
+        if completion.is_empty() {
+            return;
+        }
+        match field {
+            OskField::AddressBar => {
+                let mut state = browser.get_state_mut();
+                let location = state.get_location_mut();
+                if caret >= location.chars().count() {
+                    location.push_str(completion);
+                }
+            }
+            OskField::Home => {
+                let input = self.home.input_mut();
+                if caret >= input.chars().count() {
+                    input.push_str(completion);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The text suggestions are ranked for: the address bar's or the start-page
+    /// search's, while the OSK types into it (`None` otherwise).
+    fn suggest_text(&self, browser: &AppBrowser) -> Option<String> {
+        match self.osk_target_field() {
+            OskField::AddressBar => Some(browser.get_state_mut().get_location().to_string()),
+            OskField::Home => Some(self.home.input().to_string()),
+            _ => None,
+        }
+    }
+
+    /// Re-rank the suggestions when their field's text changed since the last
+    /// frame, or drop them once the OSK has left it. The sources are read only
+    /// on a change — the tab list is a fresh snapshot. A search engine with a
+    /// `suggest_url` is asked too; its answer joins the list when it arrives.
+    fn refresh_suggest(&mut self, browser: &AppBrowser) {
+        let Some(text) = self.suggest_text(browser) else {
+            self.suggest.close();
+            return;
+        };
+        if let (Some((query, searches)), Some(engine)) =
+            (self.search_suggest.take(), &self.search_engine)
+        {
+            let results = |q: &str| crate::browser::search_url(&engine.url, q).map(String::from);
+            self.suggest.add_searches(&query, searches, results);
+        }
+        if !self.suggest.stale(&text) {
+            return;
+        }
+        let tabs = browser.tabs();
+        let sources = Sources {
+            history: self.menu.history().entries(),
+            bookmarks: self.menu.bookmarks().pages(),
+            dial: self.menu.dial.pins(),
+            tabs: &tabs,
+        };
+        self.suggest.refresh(&text, &sources, history::now_unix());
+        // Addresses aren't searches.
+        let engine = self
+            .search_engine
+            .as_ref()
+            .filter(|e| !e.suggest_url.is_empty());
+        if let Some(engine) = engine {
+            if !text.trim().is_empty() && !text.contains("://") {
+                self.search_suggest.request(&engine.suggest_url, &text);
+            }
+        }
     }
 
     /// Open the menu. It takes over the stick and A, so the other user
@@ -473,6 +687,14 @@ impl AppUi {
         self.menu.open();
     }
 
+    /// Open the find bar. It and hint mode both own the shoulders, so hints
+    /// close.
+    #[inline]
+    pub fn find_open(&mut self) {
+        self.hints.hide();
+        self.find.open();
+    }
+
     /// Open the settings overlay, seeding its draft from the live config. Like
     /// the menu it takes over the stick and A, so the other user overlays close.
     #[inline]
@@ -483,6 +705,37 @@ impl AppUi {
         self.settings.open(config);
     }
 
+    /// Open the site settings sheet on `host`'s overrides. Like settings it
+    /// takes over the stick and A, so the other user overlays close.
+    #[inline]
+    pub fn site_edit_open(
+        &mut self,
+        host: String,
+        site: crate::data::site_settings::Site,
+        blocking: bool,
+    ) {
+        self.osk.visible = false;
+        self.hints.hide();
+        self.menu.close();
+        self.site_edit.open(host, site, blocking);
+    }
+
+    /// Open the blocked-requests panel on `section`, with the active page's
+    /// `log` and the tab's `requests`, closing the other user overlays like the
+    /// site sheet.
+    #[inline]
+    pub fn blocked_open(
+        &mut self,
+        log: crate::browser::block_log::BlockLog,
+        requests: crate::browser::net_log::NetLog,
+        section: crate::overlay::blocked::BlockedSection,
+    ) {
+        self.osk.visible = false;
+        self.hints.hide();
+        self.menu.close();
+        self.blocked.open(log, requests, section);
+    }
+
     /// Close the settings overlay, handing back its edited config and bindings
     /// drafts so the app can save them and re-apply what changes live.
     #[inline]
@@ -655,7 +908,7 @@ impl AppUi {
     /// one tile per pin plus a trailing "+ Add" tile, hence `len() + 1`.
     #[inline]
     pub fn home_move(&mut self, dx: i32, dy: i32) {
-        let count = self.menu.dial.urls().len() + 1;
+        let count = self.menu.dial.pins().len() + 1;
         self.home.move_sel(dx, dy, count);
     }
 
@@ -665,13 +918,14 @@ impl AppUi {
     pub fn home_selected_url(&self) -> Option<String> {
         self.home
             .tile()
-            .and_then(|i| self.menu.dial.urls().get(i).cloned())
+            .and_then(|i| self.menu.dial.pins().get(i))
+            .map(|pin| pin.url.clone())
     }
 
     /// Whether the trailing "Edit" tile (index == pin count) is focused.
     #[inline]
     pub fn home_tile_is_edit(&self) -> bool {
-        self.home.tile() == Some(self.menu.dial.urls().len())
+        self.home.tile() == Some(self.menu.dial.pins().len())
     }
 
     // --- Speed-dial editor (the standalone overlay opened from the start page) ---
@@ -731,10 +985,10 @@ impl AppUi {
     fn dial_edit_pin_indices(&self) -> Vec<usize> {
         self.menu
             .dial
-            .urls()
+            .pins()
             .iter()
             .enumerate()
-            .filter(|(_, u)| u.as_str() != crate::data::dial::SETTINGS_PIN)
+            .filter(|(_, pin)| pin.url != crate::data::dial::SETTINGS_PIN)
             .map(|(i, _)| i)
             .collect()
     }
@@ -981,18 +1235,32 @@ impl AppUi {
     fn frame_snapshot(&mut self, browser: &mut AppBrowser) -> FrameInputs {
         let tab_count = browser.tab_count();
         let zoom_pct = browser.zoom_chip();
+        let game_mode = browser.game_mode();
+        let no_history = browser.no_history();
+        let blocked = browser.blocked_count();
         let tab_infos = if self.menu.visible {
             self.menu.set_tab_count(browser.tab_count());
             browser.tabs()
         } else {
             Vec::new()
         };
+        // Ghosted only with the caret at the text's end, where it would land.
+        let completion = match self.suggest_text(browser) {
+            Some(text) if self.osk.caret() >= text.chars().count() => {
+                self.suggest.completion().to_string()
+            }
+            _ => String::new(),
+        };
         FrameInputs {
             tab_count,
             zoom_pct,
+            game_mode,
+            no_history,
+            blocked,
             tab_infos,
             osk_field: self.osk_target_field(),
             osk_caret: self.osk.caret(),
+            completion,
         }
     }
 
@@ -1001,7 +1269,7 @@ impl AppUi {
     /// straight from the live store at their call sites, so there's no per-frame
     /// clone of the (kept-in-sync) speed-dial Vec.
     fn clamp_overlay_selections(&mut self) {
-        let pin_count = self.menu.dial.urls().len();
+        let pin_count = self.menu.dial.pins().len();
         if self.home_active {
             // +1 for the trailing "Edit" tile, so its selection isn't clamped off.
             self.home.clamp(pin_count + 1);
@@ -1042,6 +1310,7 @@ impl AppUi {
         };
         self.schedule_idle_repaints(cursor_visible);
 
+        self.refresh_suggest(browser);
         let snapshot = self.frame_snapshot(browser);
         self.clamp_overlay_selections();
 
@@ -1054,11 +1323,22 @@ impl AppUi {
             let FrameInputs {
                 tab_count,
                 zoom_pct,
+                game_mode,
+                no_history,
+                blocked,
                 tab_infos,
                 osk_field,
                 osk_caret,
+                completion,
             } = snapshot;
             let caret_for = |f| (osk_field == f).then_some(osk_caret);
+            let completion_for = |f| {
+                if osk_field == f {
+                    completion.as_str()
+                } else {
+                    ""
+                }
+            };
             let ToolbarLayout {
                 position,
                 shown: toolbar_shown,
@@ -1079,8 +1359,16 @@ impl AppUi {
                 );
                 root.set_clip_rect(ctx.content_rect());
 
-                let bookmarked = self.menu.is_bookmarked(state.get_location());
-                let active_downloads = self.menu.downloads.active_count();
+                let chips = toolbar::Chips {
+                    bookmarked: self.menu.is_bookmarked(state.get_location()),
+                    tab_count,
+                    active_downloads: self.menu.downloads.active_count(),
+                    update_available,
+                    zoom_pct,
+                    game_mode,
+                    no_history,
+                    blocked,
+                };
 
                 // 1) Reserved-space toolbar: the panel reserves its strip and the
                 //    page reflows below it. Drawn unless we're in overlay mode (a
@@ -1091,12 +1379,9 @@ impl AppUi {
                         &mut root,
                         &mut state,
                         commands,
-                        bookmarked,
-                        tab_count,
-                        active_downloads,
-                        update_available,
-                        zoom_pct,
+                        chips,
                         caret_for(OskField::AddressBar),
+                        completion_for(OskField::AddressBar),
                         position,
                     );
                 } else {
@@ -1143,12 +1428,9 @@ impl AppUi {
                             ctx.content_rect().width(),
                             &mut state,
                             commands,
-                            bookmarked,
-                            tab_count,
-                            active_downloads,
-                            update_available,
-                            zoom_pct,
+                            chips,
                             caret_for(OskField::AddressBar),
+                            completion_for(OskField::AddressBar),
                             position,
                         );
                         self.toolbar_height = self.toolbar_rect.height();
@@ -1166,9 +1448,10 @@ impl AppUi {
                     home::add_home(
                         ctx,
                         &mut self.home,
-                        self.menu.dial.urls(),
+                        self.menu.dial.pins(),
                         self.webview_rect,
                         caret_for(OskField::Home),
+                        completion_for(OskField::Home),
                         commands,
                     );
                 }
@@ -1179,7 +1462,7 @@ impl AppUi {
                     dial_edit::add_dial_edit(
                         ctx,
                         &mut self.dial_edit,
-                        self.menu.dial.urls(),
+                        self.menu.dial.pins(),
                         caret_for(OskField::DialEdit),
                         commands,
                     );
@@ -1192,6 +1475,22 @@ impl AppUi {
                 if self.settings.visible() {
                     settings::add_settings(ctx, &self.settings, &update, commands);
                 }
+                if self.site_edit.visible() {
+                    site_edit::add_site_edit(ctx, &self.site_edit, commands);
+                }
+                if self.blocked.visible() {
+                    blocked::add_blocked(ctx, &self.blocked, commands);
+                }
+
+                // The find bar pins to the page corner; the OSK (below) opens
+                // under it to type the query. The full-screen overlays hide it.
+                let full_screen = self.menu.visible
+                    || self.settings.visible()
+                    || self.site_edit.visible()
+                    || self.blocked.visible();
+                if self.find.visible && !full_screen {
+                    find::add_find(ctx, &self.find, self.webview_rect);
+                }
 
                 // The modal prompt draws on top of whatever else is up (its
                 // egui layer order puts it above the other overlays).
@@ -1221,6 +1520,9 @@ impl AppUi {
                         ToolbarPosition::Top => 0.0,
                     };
                     self.osk_height = osk::add_osk(ctx, &self.osk, bottom_inset) + bottom_inset;
+                    if !self.suggest.items().is_empty() {
+                        suggest::add_suggest(ctx, &self.suggest, self.osk_height, commands);
+                    }
                 } else if self.hints.visible {
                     hints::add_hints(ctx, &self.hints, self.webview_rect, self.hint_badges);
                 } else if cursor_visible.is_some() {
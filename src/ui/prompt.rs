@@ -1,7 +1,7 @@
 //! Rendering of the modal page-prompt overlay (state and resolution live in
 //! [`crate::overlay::prompt`]): `<select>` pickers and the JS `alert` / `confirm` /
-//! `prompt` dialogs, drawn as a centered panel above everything else with the
-//! page dimmed behind it.
+//! `prompt` dialogs, plus the app's own questions, drawn as a centered panel
+//! above everything else with the page dimmed behind it.
 
 use super::theme::{ACCENT, DIM, PANEL_FILL};
 use crate::app::{AppCommand, PromptAction};
@@ -12,6 +12,21 @@ use servo::{EmbedderControl, SelectElement, SelectElementOption, SimpleDialog};
 
 const ROW_H: f32 = 26.0;
 
+/// Web content can't draw browser chrome — this header marks a message as
+/// coming from the page so it can't impersonate the UI.
+const PAGE_HEADER: &str = "The page says:";
+
+/// What a two-button dialog shows, page or app alike.
+struct Dialog {
+    header: &'static str,
+    message: String,
+    /// A `prompt()` text field.
+    has_input: bool,
+    confirm: &'static str,
+    /// `None` for a lone OK (`alert`).
+    cancel: Option<&'static str>,
+}
+
 /// Draw the front pending control as a modal: a dimmed backdrop and a centered
 /// panel. Gamepad/keyboard: up/down move, A/Enter activate, B/Esc dismiss; the
 /// mouse clicks rows and buttons directly.
@@ -32,15 +47,28 @@ pub(super) fn add_prompt(
 
     // Dialog text is cloned out first so the prompt can hand out its edit
     // buffer mutably while rendering (the select arm only needs reads).
-    let dialog = match prompt.front() {
-        Some(EmbedderControl::SimpleDialog(d)) => {
-            let (has_input, has_cancel) = match d {
-                SimpleDialog::Alert(_) => (false, false),
-                SimpleDialog::Confirm(_) => (false, true),
-                SimpleDialog::Prompt(_) => (true, true),
+    let dialog = match (prompt.front(), prompt.front_app()) {
+        (Some(EmbedderControl::SimpleDialog(d)), _) => {
+            let (has_input, cancel) = match d {
+                SimpleDialog::Alert(_) => (false, None),
+                SimpleDialog::Confirm(_) => (false, Some("Cancel")),
+                SimpleDialog::Prompt(_) => (true, Some("Cancel")),
             };
-            Some((d.message().to_string(), has_input, has_cancel))
+            Some(Dialog {
+                header: PAGE_HEADER,
+                message: d.message().to_string(),
+                has_input,
+                confirm: "OK",
+                cancel,
+            })
         }
+        (_, Some(d)) => Some(Dialog {
+            header: "retsurf",
+            message: d.message.clone(),
+            has_input: false,
+            confirm: d.confirm,
+            cancel: Some(d.cancel),
+        }),
         _ => None,
     };
 
@@ -58,11 +86,8 @@ pub(super) fn add_prompt(
                 .inner_margin(14.0)
                 .show(ui, |ui| {
                     ui.set_max_width((screen.width() - 64.0).min(480.0));
-                    if let Some((message, has_input, has_cancel)) = dialog {
-                        add_dialog(
-                            ui, screen, prompt, &message, has_input, has_cancel, osk_caret,
-                            commands,
-                        );
+                    if let Some(dialog) = dialog {
+                        add_dialog(ui, screen, prompt, &dialog, osk_caret, commands);
                     } else if let Some(EmbedderControl::SelectElement(select)) = prompt.front() {
                         add_select(ui, screen, prompt, select, commands);
                     }
@@ -200,32 +225,27 @@ fn add_option_row(
     }
 }
 
-/// A simple dialog: the page's message (scrollable when long), the `prompt()`
-/// text field, and the OK / Cancel buttons (slots 0 / 1).
-#[allow(clippy::too_many_arguments)]
+/// A simple dialog: the header and message (scrollable when long), the
+/// `prompt()` text field, and the OK / Cancel buttons (slots 0 / 1).
 fn add_dialog(
     ui: &mut egui::Ui,
     screen: egui::Rect,
     prompt: &mut Prompt,
-    message: &str,
-    has_input: bool,
-    has_cancel: bool,
+    dialog: &Dialog,
     osk_caret: Option<usize>,
     commands: &mut Vec<AppCommand>,
 ) {
     let dim = DIM;
-    // Web content can't draw browser chrome — the header marks the message as
-    // coming from the page so it can't impersonate the UI.
-    ui.label(egui::RichText::new("The page says:").color(dim));
+    ui.label(egui::RichText::new(dialog.header).color(dim));
     ui.add_space(4.0);
     egui::ScrollArea::vertical()
         .max_height(screen.height() * 0.5)
         .show(ui, |ui| {
-            ui.label(egui::RichText::new(message).color(egui::Color32::WHITE));
+            ui.label(egui::RichText::new(&dialog.message).color(egui::Color32::WHITE));
         });
     ui.add_space(8.0);
 
-    if has_input {
+    if dialog.has_input {
         // The gamepad types into this buffer through the on-screen keyboard
         // (X opens it); a physical keyboard can click and type directly.
         let edit_id = egui::Id::new("prompt_input");
@@ -249,18 +269,18 @@ fn add_dialog(
             [90.0, ROW_H],
             egui::Button::selectable(
                 selected == 0,
-                egui::RichText::new("OK").color(egui::Color32::WHITE),
+                egui::RichText::new(dialog.confirm).color(egui::Color32::WHITE),
             ),
         );
         if ok.clicked() {
             commands.push(AppCommand::Prompt(PromptAction::ClickSlot(0)));
         }
-        if has_cancel {
+        if let Some(label) = dialog.cancel {
             let cancel = ui.add_sized(
                 [90.0, ROW_H],
                 egui::Button::selectable(
                     selected == 1,
-                    egui::RichText::new("Cancel").color(egui::Color32::WHITE),
+                    egui::RichText::new(label).color(egui::Color32::WHITE),
                 ),
             );
             if cancel.clicked() {
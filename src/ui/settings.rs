@@ -474,6 +474,13 @@ pub(super) fn add_settings(
         if let Some(note) = settings.controls_note() {
             ui.label(egui::RichText::new(note).color(WARN));
         }
+        if let Some(note) = settings.action_note() {
+            let (note, color) = match note {
+                Ok(note) => (note, ACCENT),
+                Err(note) => (note, WARN),
+            };
+            ui.label(egui::RichText::new(note).color(color));
+        }
         ui.add_space(8.0);
 
         // The About tab is read-only info, not a FIELDS list.
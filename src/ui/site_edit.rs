@@ -0,0 +1,56 @@
+//! Rendering of the site settings sheet (state lives in
+//! [`crate::overlay::site_edit`]): the active site's overrides as menu-style
+//! `label : value` rows. Up/Down move, Left/Right change, A changes (or resets on
+//! the last row), B saves & closes.
+
+use super::menu::row_atoms;
+use super::panel::{self, section_scroll, ROW_GAP, SIDES};
+use super::theme::{ACCENT, DIM, ROW_FONT};
+use crate::app::{AppCommand, SiteAction};
+use crate::overlay::site_edit::{SiteEdit, FIELDS};
+use egui_phosphor::bold;
+use egui_sdl2::egui;
+
+/// Draw the sheet full-screen, a row per [`FIELDS`] entry. A click focuses and
+/// changes the row; both close paths store the edit.
+pub(super) fn add_site_edit(ctx: &egui::Context, edit: &SiteEdit, commands: &mut Vec<AppCommand>) {
+    let screen = ctx.content_rect();
+    let closed = panel::panel(ctx, "site_settings", screen, |ui| {
+        let heading = format!("Site settings — {}", edit.host());
+        ui.add(
+            egui::Label::new(egui::RichText::new(heading).color(egui::Color32::WHITE)).truncate(),
+        );
+        let (up, down) = (bold::CARET_UP, bold::CARET_DOWN);
+        let (left, right) = (bold::CARET_LEFT, bold::CARET_RIGHT);
+        let hint =
+            format!("{up}{down} move   {left}{right} change   A change / reset   B save & close");
+        ui.label(egui::RichText::new(hint).color(DIM));
+        ui.add_space(8.0);
+
+        let full_w = screen.width() - SIDES;
+        section_scroll(ui, screen).show(ui, |ui| {
+            ui.spacing_mut().item_spacing.y = ROW_GAP;
+            for (i, field) in FIELDS.iter().enumerate() {
+                let selected = i == edit.selected();
+                let label = egui::RichText::new(SiteEdit::label(*field))
+                    .size(ROW_FONT)
+                    .color(egui::Color32::WHITE);
+                let value = egui::RichText::new(edit.value(*field))
+                    .size(ROW_FONT)
+                    .color(ACCENT);
+                let resp = row_atoms(ui, full_w, selected, (label, egui::Atom::grow(), value));
+                // Keep the focused row in view — no cursor to drag the bar.
+                if selected {
+                    resp.scroll_to_me(Some(egui::Align::Center));
+                }
+                if resp.clicked() {
+                    commands.push(AppCommand::Site(SiteAction::Select(i)));
+                    commands.push(AppCommand::Site(SiteAction::Activate));
+                }
+            }
+        });
+    });
+    if closed {
+        commands.push(AppCommand::Site(SiteAction::Close));
+    }
+}
@@ -0,0 +1,82 @@
+//! Rendering of the address-bar suggestions (state lives in
+//! [`crate::overlay::suggest`]): the ranked list sitting on top of the
+//! on-screen keyboard, and the inline completion ghosted after the typed text.
+
+use super::theme::{icon, ACCENT, DIM, PANEL_FILL};
+use crate::app::AppCommand;
+use crate::overlay::suggest::{Source, Suggest};
+use egui_phosphor::bold;
+use egui_sdl2::egui;
+
+/// The list's width — the keyboard's, so the two read as one panel.
+const WIDTH: f32 = 598.0;
+/// Gap between the list and the keys (logical px).
+const GAP: f32 = 4.0;
+
+/// Draw the suggestions just above the keyboard, best match at the bottom
+/// (nearest the keys, where D-pad Up lands first). `osk_height` is the
+/// keyboard's drawn height, bottom inset included. A click opens the row.
+pub(super) fn add_suggest(
+    ctx: &egui::Context,
+    suggest: &Suggest,
+    osk_height: f32,
+    commands: &mut Vec<AppCommand>,
+) {
+    egui::Area::new(egui::Id::new("suggest"))
+        .order(egui::Order::Foreground)
+        .anchor(
+            egui::Align2::CENTER_BOTTOM,
+            egui::vec2(0.0, -(osk_height + GAP)),
+        )
+        .show(ctx, |ui| {
+            egui::Frame::default()
+                .fill(PANEL_FILL)
+                .corner_radius(12.0)
+                .inner_margin(6.0)
+                .show(ui, |ui| {
+                    ui.spacing_mut().item_spacing.y = 2.0;
+                    let width = WIDTH - 12.0;
+                    for (i, item) in suggest.items().iter().enumerate().rev() {
+                        let selected = suggest.selected() == Some(i);
+                        let mut atoms =
+                            super::menu::url_atoms(ui, &item.url, &item.title, false, width);
+                        let color = if selected { ACCENT } else { DIM };
+                        atoms.push_left(icon(source_icon(item.source)).color(color));
+                        if super::menu::row_atoms(ui, width, selected, atoms).clicked() {
+                            commands.push(AppCommand::OpenSuggestion(i));
+                        }
+                    }
+                });
+        });
+}
+
+fn source_icon(source: Source) -> &'static str {
+    match source {
+        Source::Tab(_) => bold::BROWSERS,
+        Source::Bookmark => bold::STAR,
+        Source::Dial => bold::PUSH_PIN,
+        Source::History => bold::CLOCK_COUNTER_CLOCKWISE,
+        Source::Search => bold::MAGNIFYING_GLASS,
+    }
+}
+
+/// Ghost `completion` in dim text right after a field's typed text, from the
+/// field's layout `output`. Clipped to the field, so a long completion just
+/// runs off its end.
+pub(super) fn paint_completion(
+    ui: &egui::Ui,
+    output: &egui::text_edit::TextEditOutput,
+    completion: &str,
+    font: egui::FontId,
+) {
+    if completion.is_empty() {
+        return;
+    }
+    let ghost = ui
+        .painter()
+        .layout_no_wrap(completion.to_string(), font, DIM);
+    let at = output.galley_pos + egui::vec2(output.galley.size().x, 0.0);
+    ui.painter()
+        .with_clip_rect(output.text_clip_rect)
+        .galley(at, ghost, DIM);
+}
@@ -1,9 +1,10 @@
 //! The toolbar (top or bottom, per the display config): navigation buttons, the
-//! address bar, bookmark toggle, and the chips that jump into menu sections (tab
-//! count, active downloads).
+//! address bar with its reader and site-settings buttons, bookmark toggle, and
+//! the chips that jump into menu sections (tab count, active downloads) or flag
+//! per-tab state (zoom, game mode, no history).
 
 use super::theme;
-use crate::app::{AppCommand, MenuAction, SettingsAction};
+use crate::app::{AppCommand, BlockedAction, MenuAction, SettingsAction, SiteAction};
 use crate::browser::{BrowserCommand, BrowserState};
 use crate::config::ToolbarPosition;
 use crate::overlay::menu::Section;
@@ -92,6 +93,32 @@ fn add_update_dot(ui: &mut egui::Ui) -> egui::Response {
     resp
 }
 
+/// What the toolbar's chips show this frame, snapshotted from the app state
+/// before the egui closure.
+#[derive(Clone, Copy)]
+pub(super) struct Chips {
+    pub bookmarked: bool,
+    /// Open tabs, shown in the tab chip.
+    pub tab_count: usize,
+    /// Downloads still in flight; shown as a download-icon + count chip that
+    /// jumps to the section.
+    pub active_downloads: usize,
+    /// A newer build was found; shown as an "Update" chip that opens
+    /// Settings->About.
+    pub update_available: bool,
+    /// Active tab's page zoom percent when off the config default (chip hidden
+    /// at it).
+    pub zoom_pct: Option<u16>,
+    /// The active tab has the controller (see [`crate::browser::AppBrowser::game_mode`]).
+    pub game_mode: bool,
+    /// The active tab keeps no history (see
+    /// [`crate::browser::AppBrowser::open_no_history_tab`]).
+    pub no_history: bool,
+    /// Loads blocked on the active tab's page; shown as a shield + count chip
+    /// that opens the blocked-requests panel (hidden at zero).
+    pub blocked: usize,
+}
+
 #[inline]
 fn is_key_pressed(ui: &mut egui::Ui, response: egui::Response, key: egui::Key) -> bool {
     response.lost_focus() && ui.input(|i| i.key_pressed(key))
@@ -100,25 +127,27 @@ fn is_key_pressed(ui: &mut egui::Ui, response: egui::Response, key: egui::Key) -
 /// The toolbar row — nav buttons, address-bar field, and the menu/tab/download
 /// chips — laid out left-to-right. Shared by the space-reserving panel
 /// ([`add_toolbar`]) and the auto-hide overlay ([`add_toolbar_overlay`]).
-#[allow(clippy::too_many_arguments)]
 fn toolbar_contents(
     ui: &mut egui::Ui,
     state: &mut std::cell::RefMut<'_, BrowserState>,
     commands: &mut Vec<AppCommand>,
-    bookmarked: bool,
-    // Open tabs, shown in the tab chip.
-    tab_count: usize,
-    // Downloads still in flight; shown as a download-icon + count chip that jumps
-    // to the section.
-    active_downloads: usize,
-    // A newer build was found; shown as an "Update" chip that opens Settings->About.
-    update_available: bool,
-    // Active tab's page zoom percent when off the config default (chip hidden at it).
-    zoom_pct: Option<u16>,
+    chips: Chips,
     // When the OSK types into the address bar, its caret position — park egui's
     // caret here so it tracks the external edit (it won't follow on its own).
     osk_caret: Option<usize>,
+    // The suggestion's inline completion, ghosted after the typed text.
+    completion: &str,
 ) {
+    let Chips {
+        bookmarked,
+        tab_count,
+        active_downloads,
+        update_available,
+        zoom_pct,
+        game_mode,
+        no_history,
+        blocked,
+    } = chips;
     // Height 0 so the row sizes to its content — the panel measures it,
     // and the overlay's `Area` has no bounded height to center within.
     ui.allocate_ui_with_layout(
@@ -217,6 +246,30 @@ fn toolbar_contents(
                         commands.push(AppCommand::Menu(MenuAction::Open));
                         commands.push(AppCommand::Menu(MenuAction::SetSection(Section::Tabs)));
                     }
+                    // No-history chip: what's browsed here stays out of history, so
+                    // the tab says so. Clicking lists the tabs, no-history ones marked.
+                    if no_history {
+                        let chip = theme::icon(bold::EYE_SLASH).color(theme::ACCENT);
+                        let chip = ui
+                            .add(new_toolbar_button(chip))
+                            .on_hover_text("No-history tab (shares cookies and site data)");
+                        if chip.clicked() {
+                            commands.push(AppCommand::Menu(MenuAction::Open));
+                            commands.push(AppCommand::Menu(MenuAction::SetSection(Section::Tabs)));
+                        }
+                    }
+                    // Shield chip: how much the ad blocker and lightweight
+                    // mode blocked on this page; clicking lists it, to tell
+                    // whether blocking is what broke a site.
+                    if blocked > 0 {
+                        let label = format!("{}{blocked}", bold::SHIELD_CHECK);
+                        let chip = ui
+                            .add(new_toolbar_button(theme::icon(&label)))
+                            .on_hover_text("Blocked on this page");
+                        if chip.clicked() {
+                            commands.push(AppCommand::Blocked(BlockedAction::Open));
+                        }
+                    }
                     // Page-zoom chip (e.g. "125%"), shown only while the
                     // active tab is off the config default; clicking resets.
                     if let Some(pct) = zoom_pct {
@@ -225,6 +278,17 @@ fn toolbar_contents(
                             commands.push(AppCommand::Browser(BrowserCommand::Zoom(0)));
                         }
                     }
+                    // Game-mode chip: the cursor is gone while the page has the
+                    // controller, so this is the sign of why. Clicking leaves.
+                    if game_mode {
+                        let chip = theme::icon(bold::GAME_CONTROLLER).color(theme::ACCENT);
+                        let chip = ui
+                            .add(new_toolbar_button(chip))
+                            .on_hover_text("Game mode — hold Start, press Select to leave");
+                        if chip.clicked() {
+                            commands.push(AppCommand::Browser(BrowserCommand::GameMode));
+                        }
+                    }
                     let star = if bookmarked {
                         theme::icon_fill(fill::STAR)
                     } else {
@@ -261,6 +325,13 @@ fn toolbar_contents(
                                 {
                                     commands.push(AppCommand::Browser(BrowserCommand::Reader));
                                 }
+                                // Site settings, beside it (right-to-left).
+                                if ui
+                                    .add(new_toolbar_button(theme::icon(bold::SLIDERS_HORIZONTAL)))
+                                    .clicked()
+                                {
+                                    commands.push(AppCommand::Site(SiteAction::Open));
+                                }
                                 if let Some(pos) = osk_caret {
                                     super::park_caret(
                                         ui.ctx(),
@@ -270,11 +341,24 @@ fn toolbar_contents(
                                     );
                                 }
                                 let char_count = state.get_location().chars().count();
-                                let location = ui.add_sized(
-                                    ui.available_size(),
-                                    new_text_edit(state.get_location_mut(), "location")
-                                        .frame(egui::Frame::new()),
-                                );
+                                // `add_sized`, spelled out to keep the layout
+                                // output the completion is placed from.
+                                let output = ui
+                                    .allocate_ui_with_layout(
+                                        ui.available_size(),
+                                        egui::Layout::centered_and_justified(
+                                            ui.layout().main_dir(),
+                                        ),
+                                        |ui| {
+                                            new_text_edit(state.get_location_mut(), "location")
+                                                .frame(egui::Frame::new())
+                                                .show(ui)
+                                        },
+                                    )
+                                    .inner;
+                                let font = egui::TextStyle::Body.resolve(ui.style());
+                                super::suggest::paint_completion(ui, &output, completion, font);
+                                let location = output.response;
                                 // Focusing the bar selects the URL, so typing
                                 // replaces it. Skipped while the OSK types here:
                                 // it owns the caret (parked just above).
@@ -339,17 +423,13 @@ fn paint_loading_edge(
 
 /// Draw the toolbar as a space-reserving panel anchored to `position`'s edge
 /// (the central panel takes whatever's left). Returns the panel's screen rect.
-#[allow(clippy::too_many_arguments)]
 pub(super) fn add_toolbar(
     ui: &mut egui::Ui,
     state: &mut std::cell::RefMut<'_, BrowserState>,
     commands: &mut Vec<AppCommand>,
-    bookmarked: bool,
-    tab_count: usize,
-    active_downloads: usize,
-    update_available: bool,
-    zoom_pct: Option<u16>,
+    chips: Chips,
     osk_caret: Option<usize>,
+    completion: &str,
     position: ToolbarPosition,
 ) -> egui::Rect {
     let frame = egui::Frame::default()
@@ -362,17 +442,7 @@ pub(super) fn add_toolbar(
     let response = panel
         .frame(frame)
         .show(ui, |ui| {
-            toolbar_contents(
-                ui,
-                state,
-                commands,
-                bookmarked,
-                tab_count,
-                active_downloads,
-                update_available,
-                zoom_pct,
-                osk_caret,
-            )
+            toolbar_contents(ui, state, commands, chips, osk_caret, completion)
         })
         .response;
     let rect = response.rect;
@@ -386,18 +456,17 @@ pub(super) fn add_toolbar(
 /// auto-hide, where the web view stays full-height (no reflow) and the bar is
 /// drawn only while shown (the caller skips this call to hide it). `width`
 /// spans the window. Returns the bar's screen rect.
+// The panel's inputs plus the overlay's width; bundling them would only move
+// the same list into a struct.
 #[allow(clippy::too_many_arguments)]
 pub(super) fn add_toolbar_overlay(
     ctx: &egui::Context,
     width: f32,
     state: &mut std::cell::RefMut<'_, BrowserState>,
     commands: &mut Vec<AppCommand>,
-    bookmarked: bool,
-    tab_count: usize,
-    active_downloads: usize,
-    update_available: bool,
-    zoom_pct: Option<u16>,
+    chips: Chips,
     osk_caret: Option<usize>,
+    completion: &str,
     position: ToolbarPosition,
 ) -> egui::Rect {
     let frame = egui::Frame::default()
@@ -414,17 +483,7 @@ pub(super) fn add_toolbar_overlay(
             ui.set_width(width);
             frame
                 .show(ui, |ui| {
-                    toolbar_contents(
-                        ui,
-                        state,
-                        commands,
-                        bookmarked,
-                        tab_count,
-                        active_downloads,
-                        update_available,
-                        zoom_pct,
-                        osk_caret,
-                    )
+                    toolbar_contents(ui, state, commands, chips, osk_caret, completion)
                 })
                 .response
                 .rect
@@ -36,7 +36,8 @@ struct Asset {
 /// network/parse failures.
 pub(super) fn latest_release(asset: Option<&str>) -> Result<UpdateState, String> {
     let url = format!("https://api.github.com/repos/{REPO}/releases/latest");
-    let mut response = match ureq::get(&url)
+    let mut response = match crate::net::agent(&url)
+        .get(&url)
         .header("User-Agent", USER_AGENT)
         .header("Accept", "application/vnd.github+json")
         .call()
@@ -63,7 +64,8 @@ pub(super) fn latest_release(asset: Option<&str>) -> Result<UpdateState, String>
 /// releases) is [`UpdateState::UpToDate`]. This is the `beta` channel.
 pub(super) fn latest_beta(asset: Option<&str>) -> Result<UpdateState, String> {
     let url = format!("https://api.github.com/repos/{REPO}/releases?per_page=30");
-    let mut response = ureq::get(&url)
+    let mut response = crate::net::agent(&url)
+        .get(&url)
         .header("User-Agent", USER_AGENT)
         .header("Accept", "application/vnd.github+json")
         .call()
@@ -171,7 +173,8 @@ struct WorkflowRun {
 pub(super) fn latest_ci(artifact: &str, token: &str) -> Result<UpdateState, String> {
     let url =
         format!("https://api.github.com/repos/{REPO}/actions/artifacts?per_page=100&name={artifact}");
-    let mut response = ureq::get(&url)
+    let mut response = crate::net::agent(&url)
+        .get(&url)
         .header("User-Agent", USER_AGENT)
         .header("Accept", "application/vnd.github+json")
         .header("Authorization", format!("Bearer {token}"))
@@ -239,7 +242,8 @@ fn current_sha() -> &'static str {
 /// `<hex>  <filename>`. Best-effort: any failure or malformed body yields `None`,
 /// so the install falls back to HTTPS trust alone.
 fn fetch_sha256(url: &str) -> Option<String> {
-    let body = ureq::get(url)
+    let body = crate::net::agent(url)
+        .get(url)
         .header("User-Agent", USER_AGENT)
         .call()
         .ok()?
@@ -131,7 +131,9 @@ fn download(
     // The CI channel downloads from the GitHub API and needs the token; ureq drops
     // the Authorization header on the 302 to blob storage (redirect_auth_headers =
     // Never), so it never leaks to the CDN. Release URLs pass `None`.
-    let mut request = ureq::get(url).header("User-Agent", USER_AGENT);
+    let mut request = crate::net::agent(url)
+        .get(url)
+        .header("User-Agent", USER_AGENT);
     if let Some(token) = auth {
         request = request.header("Authorization", format!("Bearer {token}"));
     }